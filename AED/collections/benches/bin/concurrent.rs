@@ -0,0 +1,20 @@
+use collections::benches::core::THROUGHPUT_CSV_HEADER;
+use collections::benches::*;
+use collections::set::*;
+use std::sync::Mutex;
+
+fn main() {
+    println!("{THROUGHPUT_CSV_HEADER}");
+
+    let ops = 2_000_000;
+
+    // acima do número de núcleos, as threads só disputam a CPU: a curva
+    // mostra até onde cada estratégia de trava escala
+    for threads in [1, 2, 4, 8] {
+        bench_concurrent_dedup("StripedHashSet", StripedHashSet::new(), threads, ops);
+        bench_concurrent_dedup("Mutex<HashChainingSet>", Mutex::new(HashChainingSet::new()), threads, ops);
+
+        bench_concurrent_read_mostly("StripedHashSet", StripedHashSet::new(), threads, ops);
+        bench_concurrent_read_mostly("Mutex<HashChainingSet>", Mutex::new(HashChainingSet::new()), threads, ops);
+    }
+}
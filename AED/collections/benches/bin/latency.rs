@@ -0,0 +1,59 @@
+use collections::benches::*;
+use collections::seq::{ArraySeq, SkipListSeq};
+use collections::set::*;
+
+// 1 = cada operação cronometrada sozinha (picos aparecem inteiros)
+const BATCH: usize = 1;
+
+fn main() {
+    println!("{LATENCY_CSV_HEADER}");
+
+    let n_points = 12;
+
+    let start = 1_000.0_f64;
+    let end   = 1_000_000.0_f64;
+
+    let log_start = start.log10();
+    let log_end   = end.log10();
+
+    let ns: Vec<usize> = (0..n_points)
+        .map(|i| {
+            let t = i as f64 / (n_points - 1) as f64; // 0 → 1
+            10f64.powf(log_start + t * (log_end - log_start)) as usize
+        })
+        .collect();
+
+    for &n in ns.iter() {
+        latency_insert::<HashChainingSet<i32>>(n, BATCH);
+        latency_remove::<HashChainingSet<i32>>(n, BATCH);
+
+        let incremental = || {
+            HashChainingSet::builder()
+                .rehash(Rehash::Incremental { buckets_per_op: 4 })
+                .build()
+        };
+        latency_insert_with("HashChainingSetIncremental", incremental(), n, BATCH);
+        latency_remove_with("HashChainingSetIncremental", incremental(), n, BATCH);
+
+        latency_insert::<BstAvlSet<i32>>(n, BATCH);
+        latency_remove::<BstAvlSet<i32>>(n, BATCH);
+
+        latency_insert::<BstRBSet<i32>>(n, BATCH);
+        latency_remove::<BstRBSet<i32>>(n, BATCH);
+
+        latency_insert::<BTreeSet<i32>>(n, BATCH);
+        latency_remove::<BTreeSet<i32>>(n, BATCH);
+
+        latency_insert::<TreapSet<i32>>(n, BATCH);
+        latency_remove::<TreapSet<i32>>(n, BATCH);
+
+        latency_insert::<SplaySet<i32>>(n, BATCH);
+        latency_remove::<SplaySet<i32>>(n, BATCH);
+
+        latency_append::<ArraySeq<i32>>("ArraySeq", n, BATCH);
+        latency_pop_back::<ArraySeq<i32>>("ArraySeq", n, BATCH);
+
+        latency_append::<SkipListSeq<i32>>("SkipListSeq", n, BATCH);
+        latency_pop_back::<SkipListSeq<i32>>("SkipListSeq", n, BATCH);
+    }
+}
@@ -17,6 +17,19 @@ struct Record {
     time_per_op: f64,
 }
 
+/// Linha de `latency.csv` (ver `collections::benches::latency`), em ns.
+#[derive(Debug, Deserialize)]
+struct LatencyRecord {
+    implementation: String,
+    workload: String,
+    n: usize,
+    p50: f64,
+    p90: f64,
+    p99: f64,
+    p999: f64,
+    max: f64,
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     std::fs::create_dir_all(OUTPUT_DIR)?;
 
@@ -46,6 +59,12 @@ fn main() -> Result<(), Box<dyn Error>> {
         "lookup_hit",
         "lookup_miss",
         "insert_sorted",
+        "union",
+        "intersection",
+        "difference",
+        "lookup_zipf",
+        "lookup_hot",
+        "lookup_sequential",
     ];
 
     for &workload in &workloads {
@@ -57,6 +76,105 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
+    // Percentis de latência (opcional: só se `bench_latency` já rodou)
+    let latency_path = format!("{}/latency.csv", OUTPUT_DIR);
+    if std::path::Path::new(&latency_path).exists() {
+        let mut rdr = ReaderBuilder::new()
+            .delimiter(b';')
+            .from_path(&latency_path)?;
+
+        let mut by_series: HashMap<(String, String), Vec<LatencyRecord>> = HashMap::new();
+        for result in rdr.deserialize() {
+            let rec: LatencyRecord = result?;
+            by_series
+                .entry((rec.workload.clone(), rec.implementation.clone()))
+                .or_default()
+                .push(rec);
+        }
+
+        for ((workload, impl_name), recs) in &by_series {
+            plot_latency(workload, impl_name, recs)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Uma figura por (workload, implementação): p50, p90, p99, p99.9 e máximo
+/// contra n, em log-log. A distância entre p50 e máximo é o custo que a
+/// média amortizada esconde (rehash, realocação, rebalanceamento).
+fn plot_latency(workload: &str, impl_name: &str, recs: &[LatencyRecord]) -> Result<(), Box<dyn Error>> {
+    let mut recs: Vec<&LatencyRecord> = recs.iter().filter(|r| r.n > 0).collect();
+    recs.sort_by_key(|r| r.n);
+    if recs.len() < 2 {
+        return Ok(());
+    }
+
+    type Pick = fn(&LatencyRecord) -> f64;
+    let series: [(&str, Pick, RGBColor); 5] = [
+        ("p50", |r| r.p50, RGBColor(76, 114, 176)),
+        ("p90", |r| r.p90, RGBColor(85, 168, 104)),
+        ("p99", |r| r.p99, RGBColor(221, 132, 82)),
+        ("p99.9", |r| r.p999, RGBColor(196, 78, 82)),
+        ("max", |r| r.max, RGBColor(55, 55, 55)),
+    ];
+
+    // latência 0 (abaixo da resolução do relógio) vira 1 ns para o log
+    let log_y = |v: f64| v.max(1.0).log10();
+
+    let min_x = (recs[0].n as f64).log10();
+    let max_x = (recs[recs.len() - 1].n as f64).log10();
+    let min_y = recs.iter().map(|r| log_y(r.p50)).fold(f64::INFINITY, f64::min);
+    let max_y = recs.iter().map(|r| log_y(r.max)).fold(f64::NEG_INFINITY, f64::max);
+
+    // `BTreeSet<6>` etc.: só caracteres seguros no nome do arquivo
+    let safe_name: String = impl_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let filename = format!("{}/latency_{}_{}.png", OUTPUT_DIR, workload, safe_name);
+    let root = BitMapBackend::new(&filename, (900, 700)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(
+            format!("{impl_name} — {workload}: percentis de latência"),
+            ("sans-serif", 30),
+        )
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(min_x..max_x, (min_y - 0.1)..(max_y + 0.1))?;
+
+    chart
+        .configure_mesh()
+        .x_desc("log10(n)")
+        .y_desc("log10(ns por operação)")
+        .label_style(("sans-serif", 15))
+        .draw()?;
+
+    for (label, pick, color) in series {
+        let points: Vec<(f64, f64)> = recs
+            .iter()
+            .map(|r| ((r.n as f64).log10(), log_y(pick(r))))
+            .collect();
+
+        chart.draw_series(points.iter().map(|&p| Circle::new(p, 3, color.filled())))?;
+        chart
+            .draw_series(std::iter::once(PathElement::new(points, color.stroke_width(2))))?
+            .label(label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+    }
+
+    chart
+        .configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .label_font(("sans-serif", 15))
+        .position(SeriesLabelPosition::UpperLeft)
+        .draw()?;
+
+    println!("Gerado: {filename}");
     Ok(())
 }
 
@@ -126,6 +244,14 @@ fn plot_workload(workload: &str, recs: &[Record]) -> Result<(), Box<dyn Error>>
         RGBColor(129, 114, 178),
         RGBColor(147, 120, 96),
         RGBColor(140, 140, 140),
+        RGBColor(218, 139, 195),
+        RGBColor(204, 185, 116),
+        RGBColor(100, 181, 205),
+        RGBColor(55, 55, 55),
+        RGBColor(230, 85, 13),
+        RGBColor(49, 163, 84),
+        RGBColor(158, 202, 225),
+        RGBColor(188, 128, 189),
     ];
 
 
@@ -196,8 +322,8 @@ fn plot_workload(workload: &str, recs: &[Record]) -> Result<(), Box<dyn Error>>
     // Desenhar legenda manualmente
     chart
         .configure_series_labels()
-        .border_style(&BLACK)
-        .background_style(&WHITE.mix(0.8))
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
         .label_font(("sans-serif", 15))
         .draw()?;
 
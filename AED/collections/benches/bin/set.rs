@@ -1,8 +1,9 @@
 use collections::set::*;
 use collections::benches::*;
+use std::collections::hash_map::RandomState;
 
 fn main() {
-    println!("implementation;workload;n;time_total;time_per_op");
+    println!("{}", core::csv_header());
 
     let n_points = 20;
 
@@ -24,30 +25,140 @@ fn main() {
         bench_lookup_hit::<ListSet<i32>>(n);
         bench_lookup_miss::<ListSet<i32>>(n); 
         bench_insert_sorted::<ListSet<i32>>(n);
+        bench_union::<ListSet<i32>>(n);
+        bench_intersection::<ListSet<i32>>(n);
+        bench_difference::<ListSet<i32>>(n);
 
         bench_insert_distinct::<BstSet<i32>>(n);
         bench_lookup_hit::<BstSet<i32>>(n);
         bench_lookup_miss::<BstSet<i32>>(n);
         bench_insert_sorted::<BstSet<i32>>(n);
+        bench_union::<BstSet<i32>>(n);
+        bench_intersection::<BstSet<i32>>(n);
+        bench_difference::<BstSet<i32>>(n);
 
         bench_insert_distinct::<BstAvlSet<i32>>(n);
         bench_lookup_hit::<BstAvlSet<i32>>(n);
         bench_lookup_miss::<BstAvlSet<i32>>(n);
         bench_insert_sorted::<BstAvlSet<i32>>(n);
+        bench_insert_sorted_bulk("BstAvlSet (from_sorted_iter)", n, |keys| {
+            BstAvlSet::from_sorted_iter(keys).unwrap()
+        });
+        bench_union::<BstAvlSet<i32>>(n);
+        bench_intersection::<BstAvlSet<i32>>(n);
+        bench_difference::<BstAvlSet<i32>>(n);
 
         bench_insert_distinct::<BstRBSet<i32>>(n);
         bench_lookup_hit::<BstRBSet<i32>>(n);
         bench_lookup_miss::<BstRBSet<i32>>(n);
         bench_insert_sorted::<BstRBSet<i32>>(n);
+        bench_insert_sorted_bulk("BstRBSet (from_sorted_iter)", n, |keys| {
+            BstRBSet::from_sorted_iter(keys).unwrap()
+        });
+        bench_union::<BstRBSet<i32>>(n);
+        bench_intersection::<BstRBSet<i32>>(n);
+        bench_difference::<BstRBSet<i32>>(n);
+
+        bench_insert_distinct::<SkipListSet<i32>>(n);
+        bench_lookup_hit::<SkipListSet<i32>>(n);
+        bench_lookup_miss::<SkipListSet<i32>>(n);
+        bench_insert_sorted::<SkipListSet<i32>>(n);
+        bench_union::<SkipListSet<i32>>(n);
+        bench_intersection::<SkipListSet<i32>>(n);
+        bench_difference::<SkipListSet<i32>>(n);
+
+        bench_insert_distinct::<BTreeSet<i32>>(n);
+        bench_lookup_hit::<BTreeSet<i32>>(n);
+        bench_lookup_miss::<BTreeSet<i32>>(n);
+        bench_insert_sorted::<BTreeSet<i32>>(n);
+        bench_union::<BTreeSet<i32>>(n);
+        bench_intersection::<BTreeSet<i32>>(n);
+        bench_difference::<BTreeSet<i32>>(n);
+
+        // nós largos: mesma árvore com 63 chaves por nó
+        bench_insert_distinct::<BTreeSet<i32, 32>>(n);
+        bench_lookup_hit::<BTreeSet<i32, 32>>(n);
+        bench_lookup_miss::<BTreeSet<i32, 32>>(n);
+        bench_insert_sorted::<BTreeSet<i32, 32>>(n);
+        bench_union::<BTreeSet<i32, 32>>(n);
+        bench_intersection::<BTreeSet<i32, 32>>(n);
+        bench_difference::<BTreeSet<i32, 32>>(n);
+
+        bench_insert_distinct::<TreapSet<i32>>(n);
+        bench_lookup_hit::<TreapSet<i32>>(n);
+        bench_lookup_miss::<TreapSet<i32>>(n);
+        bench_insert_sorted::<TreapSet<i32>>(n);
+        bench_union::<TreapSet<i32>>(n);
+        bench_intersection::<TreapSet<i32>>(n);
+        bench_difference::<TreapSet<i32>>(n);
+
+        bench_insert_distinct::<SplaySet<i32>>(n);
+        bench_lookup_hit::<SplaySet<i32>>(n);
+        bench_lookup_miss::<SplaySet<i32>>(n);
+        bench_insert_sorted::<SplaySet<i32>>(n);
+        bench_union::<SplaySet<i32>>(n);
+        bench_intersection::<SplaySet<i32>>(n);
+        bench_difference::<SplaySet<i32>>(n);
 
         bench_insert_distinct::<HashChainingSet<i32>>(n);
         bench_lookup_hit::<HashChainingSet<i32>>(n);
         bench_lookup_miss::<HashChainingSet<i32>>(n);
         bench_insert_sorted::<HashChainingSet<i32>>(n);
+        bench_union::<HashChainingSet<i32>>(n);
+        bench_intersection::<HashChainingSet<i32>>(n);
+        bench_difference::<HashChainingSet<i32>>(n);
+
+        bench_insert_distinct::<HashProbingSet<i32, RandomState, LinearProbing>>(n);
+        bench_lookup_hit::<HashProbingSet<i32, RandomState, LinearProbing>>(n);
+        bench_lookup_miss::<HashProbingSet<i32, RandomState, LinearProbing>>(n);
+        bench_insert_sorted::<HashProbingSet<i32, RandomState, LinearProbing>>(n);
+        bench_union::<HashProbingSet<i32, RandomState, LinearProbing>>(n);
+        bench_intersection::<HashProbingSet<i32, RandomState, LinearProbing>>(n);
+        bench_difference::<HashProbingSet<i32, RandomState, LinearProbing>>(n);
+
+        bench_insert_distinct::<HashProbingSet<i32, RandomState, QuadraticProbing>>(n);
+        bench_lookup_hit::<HashProbingSet<i32, RandomState, QuadraticProbing>>(n);
+        bench_lookup_miss::<HashProbingSet<i32, RandomState, QuadraticProbing>>(n);
+        bench_insert_sorted::<HashProbingSet<i32, RandomState, QuadraticProbing>>(n);
+        bench_union::<HashProbingSet<i32, RandomState, QuadraticProbing>>(n);
+        bench_intersection::<HashProbingSet<i32, RandomState, QuadraticProbing>>(n);
+        bench_difference::<HashProbingSet<i32, RandomState, QuadraticProbing>>(n);
+
+        bench_insert_distinct::<HashProbingSet<i32, RandomState, DoubleHashing>>(n);
+        bench_lookup_hit::<HashProbingSet<i32, RandomState, DoubleHashing>>(n);
+        bench_lookup_miss::<HashProbingSet<i32, RandomState, DoubleHashing>>(n);
+        bench_insert_sorted::<HashProbingSet<i32, RandomState, DoubleHashing>>(n);
+        bench_union::<HashProbingSet<i32, RandomState, DoubleHashing>>(n);
+        bench_intersection::<HashProbingSet<i32, RandomState, DoubleHashing>>(n);
+        bench_difference::<HashProbingSet<i32, RandomState, DoubleHashing>>(n);
+
+        bench_insert_distinct::<HashRobinHoodSet<i32>>(n);
+        bench_lookup_hit::<HashRobinHoodSet<i32>>(n);
+        bench_lookup_miss::<HashRobinHoodSet<i32>>(n);
+        bench_insert_sorted::<HashRobinHoodSet<i32>>(n);
+        bench_union::<HashRobinHoodSet<i32>>(n);
+        bench_intersection::<HashRobinHoodSet<i32>>(n);
+        bench_difference::<HashRobinHoodSet<i32>>(n);
+
+        bench_insert_distinct::<HashCuckooSet<i32>>(n);
+        bench_lookup_hit::<HashCuckooSet<i32>>(n);
+        bench_lookup_miss::<HashCuckooSet<i32>>(n);
+        bench_insert_sorted::<HashCuckooSet<i32>>(n);
+        bench_union::<HashCuckooSet<i32>>(n);
+        bench_intersection::<HashCuckooSet<i32>>(n);
+        bench_difference::<HashCuckooSet<i32>>(n);
+
+        // acessos enviesados: árvore autoajustável contra as balanceadas
+        bench_lookup_zipf::<BstAvlSet<i32>>(n);
+        bench_lookup_hot::<BstAvlSet<i32>>(n);
+        bench_lookup_sequential::<BstAvlSet<i32>>(n);
+
+        bench_lookup_zipf::<BstRBSet<i32>>(n);
+        bench_lookup_hot::<BstRBSet<i32>>(n);
+        bench_lookup_sequential::<BstRBSet<i32>>(n);
 
-        bench_insert_distinct::<HashProbingSet<i32>>(n);
-        bench_lookup_hit::<HashProbingSet<i32>>(n);
-        bench_lookup_miss::<HashProbingSet<i32>>(n); 
-        bench_insert_sorted::<HashProbingSet<i32>>(n);
+        bench_lookup_zipf::<SplaySet<i32>>(n);
+        bench_lookup_hot::<SplaySet<i32>>(n);
+        bench_lookup_sequential::<SplaySet<i32>>(n);
     }
 }
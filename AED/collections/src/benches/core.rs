@@ -1,19 +1,64 @@
 use std::time::Instant;
 
+use crate::metrics::{self, Counts};
+
+/// Cabeçalho do CSV impresso por `time_it`.
+/// Com a feature `metrics`, inclui as colunas dos contadores de operações.
+pub fn csv_header() -> String {
+    let header = "implementation;workload;n;time_total;time_per_op";
+    if metrics::ENABLED {
+        format!("{header};{}", Counts::CSV_HEADER)
+    } else {
+        header.to_string()
+    }
+}
+
 /// Mede o tempo de um workload e imprime:
 /// implementação;workload;n;tempo_total;tempo_por_op
+///
+/// Com a feature `metrics`, acrescenta os contadores de operações
+/// (totais do workload, ver `collections::metrics`).
 pub fn time_it<F>(impl_name: &str, workload: &str, n: usize, ops: usize, f: F)
 where
     F: FnOnce(),
 {
+    metrics::reset();
     let start = Instant::now();
     f();
     let elapsed = start.elapsed();
+    let counts = metrics::snapshot();
 
     let time = elapsed.as_secs_f64();
     let time_per_op = time / ops as f64;
 
+    if metrics::ENABLED {
+        println!(
+            "{impl_name};{workload};{n};{time};{time_per_op};{}",
+            counts.to_csv()
+        );
+    } else {
+        println!(
+            "{impl_name};{workload};{n};{time};{time_per_op}"
+        );
+    }
+}
+
+/// Cabeçalho do CSV impresso por `throughput_it`.
+pub const THROUGHPUT_CSV_HEADER: &str = "implementation;workload;threads;ops;time_total;ops_per_sec";
+
+/// Mede um workload concorrente de `ops` operações no total, repartidas
+/// entre `threads` threads, e imprime:
+/// implementação;workload;threads;ops;tempo_total;ops_por_segundo
+pub fn throughput_it<F>(impl_name: &str, workload: &str, threads: usize, ops: usize, f: F)
+where
+    F: FnOnce(),
+{
+    let start = Instant::now();
+    f();
+    let time = start.elapsed().as_secs_f64();
+
     println!(
-        "{impl_name};{workload};{n};{time};{time_per_op}"
+        "{impl_name};{workload};{threads};{ops};{time};{}",
+        ops as f64 / time
     );
 }
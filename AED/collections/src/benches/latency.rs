@@ -0,0 +1,223 @@
+//! Latência por operação.
+//!
+//! `time_it` mede o workload inteiro e divide por `ops`: um rehash de O(n)
+//! diluído em n inserções some na média. Aqui cada operação (ou lote de
+//! `batch` operações) é cronometrada à parte e vai para um histograma; o
+//! CSV traz p50/p90/p99/p99.9/máximo, em nanossegundos por operação:
+//!
+//! ```text
+//! implementation;workload;n;batch;p50;p90;p99;p999;max
+//! ```
+//!
+//! Com `batch > 1` o tempo de cada lote é dividido pelo tamanho do lote:
+//! diminui o peso do custo do relógio, mas suaviza os picos.
+//!
+//! ```text
+//! cargo bench --bench bench_latency > benches/output/latency.csv
+//! cargo bench --bench plot_sets    # também desenha latency_<workload>_<impl>.png
+//! ```
+
+use std::time::Instant;
+
+use crate::seq::Seq;
+use crate::set::{Set, SetName};
+use rand::prelude::*;
+
+// ========================
+// Histograma
+// ========================
+
+/// Bits da mantissa de cada faixa: 16 sub-faixas por potência de 2,
+/// ou seja, erro relativo de no máximo 1/16 (~6%).
+const SUB_BITS: u32 = 4;
+const SUB_BUCKETS: usize = 1 << SUB_BITS;
+const BUCKETS: usize = (64 - SUB_BITS as usize + 1) * SUB_BUCKETS;
+
+/// Histograma log-linear de latências (em ns), no estilo HDR: valores
+/// abaixo de 16 ficam exatos; acima, cada potência de 2 é dividida em 16
+/// faixas iguais. Memória fixa, independente do número de amostras.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    counts: Vec<u64>,
+    total: u64,
+    max: u64,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Self {
+            counts: vec![0; BUCKETS],
+            total: 0,
+            max: 0,
+        }
+    }
+
+    /// Registra uma amostra.
+    pub fn record(&mut self, value: u64) {
+        self.counts[bucket_of(value)] += 1;
+        self.total += 1;
+        self.max = self.max.max(value);
+    }
+
+    /// Número de amostras.
+    pub fn count(&self) -> u64 {
+        self.total
+    }
+
+    /// Maior amostra (exata).
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Menor valor `v` tal que pelo menos a fração `q` das amostras é `<= v`
+    /// (arredondado para o topo da faixa). `q` em [0, 1]; 0 se vazio.
+    pub fn percentile(&self, q: f64) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+        let target = ((q * self.total as f64).ceil() as u64).clamp(1, self.total);
+
+        let mut seen = 0;
+        for (idx, &c) in self.counts.iter().enumerate() {
+            seen += c;
+            if seen >= target {
+                return upper_bound(idx).min(self.max);
+            }
+        }
+        self.max
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn bucket_of(value: u64) -> usize {
+    if value < SUB_BUCKETS as u64 {
+        return value as usize;
+    }
+    let exp = 63 - value.leading_zeros(); // >= SUB_BITS
+    let sub = (value >> (exp - SUB_BITS)) as usize & (SUB_BUCKETS - 1);
+    ((exp - SUB_BITS + 1) as usize) * SUB_BUCKETS + sub
+}
+
+/// Maior valor que cai na faixa `idx`.
+fn upper_bound(idx: usize) -> u64 {
+    if idx < SUB_BUCKETS {
+        return idx as u64;
+    }
+    let shift = (idx / SUB_BUCKETS - 1) as u32;
+    let sub = (idx % SUB_BUCKETS) as u64;
+    let lower = (SUB_BUCKETS as u64 + sub) << shift;
+    lower + ((1u64 << shift) - 1)
+}
+
+// ========================
+// Medição
+// ========================
+
+/// Cabeçalho do CSV impresso por `latency_it`.
+pub const LATENCY_CSV_HEADER: &str = "implementation;workload;n;batch;p50;p90;p99;p999;max";
+
+/// Chama `op(0)`, `op(1)`, ..., `op(n - 1)`, cronometrando cada lote de
+/// `batch` chamadas, e imprime a linha do CSV com os percentis.
+pub fn latency_it<F>(impl_name: &str, workload: &str, n: usize, batch: usize, mut op: F)
+where
+    F: FnMut(usize),
+{
+    assert!(batch > 0, "latency_it: batch precisa ser positivo");
+    let mut hist = Histogram::new();
+
+    let mut i = 0;
+    while i < n {
+        let end = (i + batch).min(n);
+        let start = Instant::now();
+        for j in i..end {
+            op(j);
+        }
+        let elapsed = start.elapsed().as_nanos() as u64;
+        hist.record(elapsed / (end - i) as u64);
+        i = end;
+    }
+
+    println!(
+        "{impl_name};{workload};{n};{batch};{};{};{};{};{}",
+        hist.percentile(0.50),
+        hist.percentile(0.90),
+        hist.percentile(0.99),
+        hist.percentile(0.999),
+        hist.max()
+    );
+}
+
+// ========================
+// Workloads
+// ========================
+
+fn shuffled(n: usize) -> Vec<i32> {
+    let mut values: Vec<i32> = (0..n as i32).collect();
+    values.shuffle(&mut thread_rng());
+    values
+}
+
+/// Latência de `n` inserções embaralhadas em `set` (que deve começar vazio).
+/// Para variantes que não saem de `Default` (ex.: um builder).
+pub fn latency_insert_with<S: Set<i32>>(impl_name: &str, mut set: S, n: usize, batch: usize) {
+    let values = shuffled(n);
+    latency_it(impl_name, "insert", n, batch, |i| {
+        set.insert(values[i]);
+    });
+}
+
+/// Latência de `n` remoções embaralhadas de um `set` com `0..n`.
+pub fn latency_remove_with<S: Set<i32>>(impl_name: &str, mut set: S, n: usize, batch: usize) {
+    for v in shuffled(n) {
+        set.insert(v);
+    }
+    let values = shuffled(n);
+    latency_it(impl_name, "remove", n, batch, |i| {
+        set.remove(&values[i]);
+    });
+}
+
+pub fn latency_insert<S>(n: usize, batch: usize)
+where
+    S: Set<i32> + SetName + Default,
+{
+    latency_insert_with(&S::name(), S::default(), n, batch);
+}
+
+pub fn latency_remove<S>(n: usize, batch: usize)
+where
+    S: Set<i32> + SetName + Default,
+{
+    latency_remove_with(&S::name(), S::default(), n, batch);
+}
+
+/// Latência de `n` inserções no fim da sequência.
+pub fn latency_append<Q>(impl_name: &str, n: usize, batch: usize)
+where
+    Q: Seq<i32> + Default,
+{
+    let mut seq = Q::default();
+    latency_it(impl_name, "append", n, batch, |i| {
+        seq.append(i as i32);
+    });
+}
+
+/// Latência de `n` remoções do fim de uma sequência com `n` elementos.
+pub fn latency_pop_back<Q>(impl_name: &str, n: usize, batch: usize)
+where
+    Q: Seq<i32> + Default,
+{
+    let mut seq = Q::default();
+    for i in 0..n {
+        seq.append(i as i32);
+    }
+    latency_it(impl_name, "pop_back", n, batch, |_| {
+        let last = seq.len() - 1;
+        seq.remove_from(last);
+    });
+}
@@ -1,4 +1,12 @@
 pub mod core;
+pub mod latency;
 pub mod set;
 
 pub use set::{bench_insert_distinct, bench_insert_sorted, bench_lookup_hit, bench_lookup_miss};
+pub use set::bench_insert_sorted_bulk;
+pub use set::{bench_difference, bench_intersection, bench_union};
+pub use set::{bench_lookup_hot, bench_lookup_sequential, bench_lookup_zipf};
+pub use set::{bench_concurrent_dedup, bench_concurrent_read_mostly};
+
+pub use latency::{latency_append, latency_insert, latency_insert_with, latency_pop_back};
+pub use latency::{latency_remove, latency_remove_with, Histogram, LATENCY_CSV_HEADER};
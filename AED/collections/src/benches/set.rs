@@ -1,5 +1,5 @@
-use crate::set::{Set, SetName};
-use super::core::time_it;
+use crate::set::{ConcurrentSet, Set, SetName};
+use super::core::{throughput_it, time_it};
 use rand::prelude::*;
 
 pub fn bench_insert_distinct<S>(n: usize)
@@ -7,7 +7,7 @@ where
     S: Set<i32> + SetName + Default,
 {
     // n inserções distintas
-    time_it(S::name(), "insert_distinct", n, n, || {
+    time_it(&S::name(), "insert_distinct", n, n, || {
         let mut set = S::default();
 
         // valores embaralhados
@@ -25,7 +25,7 @@ where
     S: Set<i32> + SetName + Default,
 {
     // n inserções em ordem crescente
-    time_it(S::name(), "insert_sorted", n, n, || {
+    time_it(&S::name(), "insert_sorted", n, n, || {
         let mut set = S::default();
 
         for v in 0..n as i32 {
@@ -34,12 +34,26 @@ where
     });
 }
 
+/// Referência para `insert_sorted`: as mesmas n chaves crescentes, mas
+/// montando a árvore de uma vez com `build` (ex.: `from_sorted_iter`).
+/// Sai no mesmo workload, com `impl_name` distinguindo a linha no gráfico.
+pub fn bench_insert_sorted_bulk<S, F>(impl_name: &str, n: usize, build: F)
+where
+    S: Set<i32>,
+    F: Fn(std::ops::Range<i32>) -> S,
+{
+    time_it(impl_name, "insert_sorted", n, n, || {
+        let set = build(0..n as i32);
+        assert_eq!(set.len(), n);
+    });
+}
+
 pub fn bench_lookup_hit<S>(n: usize)
 where
     S: Set<i32> + SetName + Default,
 {
     // n lookups bem-sucedidos
-    time_it(S::name(), "lookup_hit", n, n, || {
+    time_it(&S::name(), "lookup_hit", n, n, || {
         let mut set = S::default();
 
         let mut values: Vec<i32> = (0..n as i32).collect();
@@ -63,7 +77,7 @@ where
     S: Set<i32> + SetName + Default,
 {
     // n lookups malsucedidos
-    time_it(S::name(), "lookup_miss", n, n, || {
+    time_it(&S::name(), "lookup_miss", n, n, || {
         let mut set = S::default();
 
         let mut values: Vec<i32> = (0..n as i32).collect();
@@ -84,3 +98,247 @@ where
         }
     });
 }
+
+/// Constrói dois conjuntos de n elementos com metade em comum:
+/// A = [0, n) e B = [n/2, n + n/2), ambos inseridos em ordem embaralhada.
+fn build_overlapping_pair<S>(n: usize) -> (S, S)
+where
+    S: Set<i32> + Default,
+{
+    let mut a = S::default();
+    let mut b = S::default();
+
+    let mut values: Vec<i32> = (0..n as i32).collect();
+    values.shuffle(&mut thread_rng());
+    for v in &values {
+        a.insert(*v);
+        b.insert(*v + n as i32 / 2);
+    }
+
+    (a, b)
+}
+
+pub fn bench_union<S>(n: usize)
+where
+    S: Set<i32> + SetName + Default,
+{
+    // construção fora da medição: só a operação de conjunto conta
+    let (a, b) = build_overlapping_pair::<S>(n);
+
+    time_it(&S::name(), "union", n, n, || {
+        let count = a.union(&b).count();
+        assert_eq!(count, n + n / 2);
+    });
+}
+
+pub fn bench_intersection<S>(n: usize)
+where
+    S: Set<i32> + SetName + Default,
+{
+    let (a, b) = build_overlapping_pair::<S>(n);
+
+    time_it(&S::name(), "intersection", n, n, || {
+        let count = a.intersection(&b).count();
+        assert_eq!(count, n - n / 2);
+    });
+}
+
+pub fn bench_difference<S>(n: usize)
+where
+    S: Set<i32> + SetName + Default,
+{
+    let (a, b) = build_overlapping_pair::<S>(n);
+
+    time_it(&S::name(), "difference", n, n, || {
+        let count = a.difference(&b).count();
+        assert_eq!(count, n / 2);
+    });
+}
+
+// ========================
+// Acessos concentrados
+// ========================
+//
+// Nos workloads acima toda chave tem a mesma chance de ser buscada. Aqui o
+// acesso é enviesado, que é onde árvores autoajustáveis (SplaySet) podem
+// ganhar das balanceadas. A construção fica fora da medição: só as n
+// buscas contam.
+
+/// Amostrador de Zipf sobre os postos 0..n: o posto k sai com
+/// probabilidade proporcional a 1 / (k + 1)^s.
+struct Zipf {
+    cdf: Vec<f64>,
+}
+
+impl Zipf {
+    fn new(n: usize, s: f64) -> Self {
+        let mut cdf = Vec::with_capacity(n);
+        let mut total = 0.0;
+        for k in 0..n {
+            total += 1.0 / ((k + 1) as f64).powf(s);
+            cdf.push(total);
+        }
+        for p in &mut cdf {
+            *p /= total;
+        }
+        Self { cdf }
+    }
+
+    fn sample<R: Rng>(&self, rng: &mut R) -> usize {
+        let u: f64 = rng.gen();
+        self.cdf.partition_point(|&p| p < u).min(self.cdf.len() - 1)
+    }
+}
+
+/// Conjunto com [0, n) inserido em ordem embaralhada.
+fn build_shuffled<S>(n: usize) -> S
+where
+    S: Set<i32> + Default,
+{
+    let mut set = S::default();
+
+    let mut values: Vec<i32> = (0..n as i32).collect();
+    values.shuffle(&mut thread_rng());
+    for v in values {
+        set.insert(v);
+    }
+
+    set
+}
+
+/// Mede as buscas de `queries` (todas devem acertar).
+fn time_lookups<S>(workload: &str, n: usize, set: &S, queries: &[i32])
+where
+    S: Set<i32> + SetName,
+{
+    time_it(&S::name(), workload, n, queries.len(), || {
+        let hits = queries.iter().filter(|v| set.contains(v)).count();
+        assert_eq!(hits, queries.len());
+    });
+}
+
+pub fn bench_lookup_zipf<S>(n: usize)
+where
+    S: Set<i32> + SetName + Default,
+{
+    // n buscas com popularidade Zipf (s = 1); os postos são espalhados
+    // ao acaso pelas chaves, para a chave popular não ser sempre a menor
+    let set = build_shuffled::<S>(n);
+
+    let mut rng = thread_rng();
+    let mut keys: Vec<i32> = (0..n as i32).collect();
+    keys.shuffle(&mut rng);
+
+    let zipf = Zipf::new(n, 1.0);
+    let queries: Vec<i32> = (0..n).map(|_| keys[zipf.sample(&mut rng)]).collect();
+
+    time_lookups("lookup_zipf", n, &set, &queries);
+}
+
+pub fn bench_lookup_hot<S>(n: usize)
+where
+    S: Set<i32> + SetName + Default,
+{
+    // n buscas: 90% nas mesmas 16 chaves quentes, 10% uniformes
+    let set = build_shuffled::<S>(n);
+
+    let mut rng = thread_rng();
+    let hot: Vec<i32> = (0..16).map(|_| rng.gen_range(0..n as i32)).collect();
+
+    let queries: Vec<i32> = (0..n)
+        .map(|_| {
+            if rng.gen_bool(0.9) {
+                *hot.choose(&mut rng).unwrap()
+            } else {
+                rng.gen_range(0..n as i32)
+            }
+        })
+        .collect();
+
+    time_lookups("lookup_hot", n, &set, &queries);
+}
+
+pub fn bench_lookup_sequential<S>(n: usize)
+where
+    S: Set<i32> + SetName + Default,
+{
+    // n buscas em ordem crescente: varredura de todas as chaves
+    let set = build_shuffled::<S>(n);
+    let queries: Vec<i32> = (0..n as i32).collect();
+
+    time_lookups("lookup_sequential", n, &set, &queries);
+}
+
+// ========================
+// Concorrência
+// ========================
+//
+// Vazão (operações por segundo) contra o número de threads. O conjunto é
+// recebido pronto, para comparar o `StripedHashSet` com um `Set` comum
+// atrás de um único `Mutex`. As chaves de cada thread são sorteadas antes
+// da medição.
+
+/// Chaves de cada thread: `ops` no total, sorteadas em `0..range`.
+fn per_thread_keys(threads: usize, ops: usize, range: i32) -> Vec<Vec<i32>> {
+    let mut rng = thread_rng();
+    (0..threads)
+        .map(|t| {
+            let share = ops / threads + usize::from(t < ops % threads);
+            (0..share).map(|_| rng.gen_range(0..range)).collect()
+        })
+        .collect()
+}
+
+/// Deduplicação: `ops` inserções de chaves em `0..ops/2` (metade repetida,
+/// inclusive entre threads).
+pub fn bench_concurrent_dedup<C>(impl_name: &str, set: C, threads: usize, ops: usize)
+where
+    C: ConcurrentSet<i32>,
+{
+    let keys = per_thread_keys(threads, ops, (ops / 2).max(1) as i32);
+
+    throughput_it(impl_name, "concurrent_dedup", threads, ops, || {
+        std::thread::scope(|scope| {
+            for chunk in &keys {
+                let set = &set;
+                scope.spawn(move || {
+                    for &k in chunk {
+                        set.insert(k);
+                    }
+                });
+            }
+        });
+    });
+    assert!(set.len() <= ops / 2);
+}
+
+/// Leitura predominante: conjunto com `ops/2` chaves, depois `ops`
+/// operações com 90% de buscas e 10% de inserções.
+pub fn bench_concurrent_read_mostly<C>(impl_name: &str, set: C, threads: usize, ops: usize)
+where
+    C: ConcurrentSet<i32>,
+{
+    let range = ops as i32;
+    for k in 0..range / 2 {
+        set.insert(k);
+    }
+    let keys = per_thread_keys(threads, ops, range);
+
+    throughput_it(impl_name, "concurrent_read_mostly", threads, ops, || {
+        std::thread::scope(|scope| {
+            for chunk in &keys {
+                let set = &set;
+                scope.spawn(move || {
+                    for &k in chunk {
+                        // a chave decide a operação: ~10% são inserções
+                        if k % 10 == 0 {
+                            set.insert(k);
+                        } else {
+                            set.contains(&k);
+                        }
+                    }
+                });
+            }
+        });
+    });
+}
@@ -0,0 +1,89 @@
+//! Reproduz uma sequência de inserções/remoções numa árvore e grava um
+//! arquivo DOT por passo, para acompanhar as rotações.
+//!
+//! ```text
+//! cargo run --bin tree_dot -- <bst|avl|rb> <diretório> <ops...>
+//! cargo run --bin tree_dot -- rb saida/ +10 +20 +30 +15 -20
+//! for f in saida/*.dot; do dot -Tpng "$f" -o "${f%.dot}.png"; done
+//! ```
+//!
+//! `+x` insere `x`, `-x` remove `x`. O arquivo `step_000.dot` é a árvore
+//! vazia; `step_NNN.dot` é a árvore depois da operação `NNN`.
+
+use collections::set::{BstAvlSet, BstRBSet, BstSet, Set};
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+
+enum Op {
+    Insert(i64),
+    Remove(i64),
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Op::Insert(x) => write!(f, "insert {x}"),
+            Op::Remove(x) => write!(f, "remove {x}"),
+        }
+    }
+}
+
+fn parse_op(s: &str) -> Result<Op, String> {
+    let invalid = || format!("operação inválida: {s:?} (use +x ou -x)");
+    if let Some(x) = s.strip_prefix('+') {
+        x.parse().map(Op::Insert).map_err(|_| invalid())
+    } else if let Some(x) = s.strip_prefix('-') {
+        x.parse().map(Op::Remove).map_err(|_| invalid())
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Aplica as operações em `set`, gravando `dot(set)` antes da primeira e
+/// depois de cada uma.
+fn replay<S: Set<i64>>(
+    mut set: S,
+    dot: impl Fn(&S) -> String,
+    ops: &[Op],
+    dir: &Path,
+) -> Result<(), Box<dyn Error>> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join("step_000.dot"), format!("// passo 0: vazia\n{}", dot(&set)))?;
+
+    for (i, op) in ops.iter().enumerate() {
+        let changed = match *op {
+            Op::Insert(x) => set.insert(x),
+            Op::Remove(x) => set.remove(&x),
+        };
+        let note = if changed { "" } else { " (sem efeito)" };
+        let path = dir.join(format!("step_{:03}.dot", i + 1));
+        std::fs::write(&path, format!("// passo {}: {op}{note}\n{}", i + 1, dot(&set)))?;
+        println!("{}: {op}{note}", path.display());
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 2 {
+        eprintln!("uso: tree_dot <bst|avl|rb> <diretório> <ops...>   (ex.: +10 +20 -10)");
+        std::process::exit(2);
+    }
+
+    let dir = Path::new(&args[1]);
+    let ops = args[2..]
+        .iter()
+        .map(|s| parse_op(s))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match args[0].as_str() {
+        "bst" => replay(BstSet::new(), BstSet::to_dot, &ops, dir),
+        "avl" => replay(BstAvlSet::new(), BstAvlSet::to_dot, &ops, dir),
+        "rb" => replay(BstRBSet::new(), BstRBSet::to_dot, &ops, dir),
+        other => {
+            eprintln!("árvore desconhecida: {other:?} (use bst, avl ou rb)");
+            std::process::exit(2);
+        }
+    }
+}
@@ -2,5 +2,10 @@ pub mod queue;
 pub mod stack;
 pub mod seq;
 pub mod set;
+pub mod map;
+pub mod multiset;
 
+pub mod metrics;
+#[cfg(feature = "serde")]
+mod serde_util;
 pub mod benches;
@@ -0,0 +1,76 @@
+use crate::map::{KeyValue, Map};
+use crate::set::{BstAvlSet, Set};
+
+/// Mapa implementado como árvore AVL.
+/// Operações são O(log n).
+/// Reaproveita o `BstAvlSet`: os pares ficam ordenados pela chave.
+pub struct AvlMap<K, V> {
+    set: BstAvlSet<KeyValue<K, V>>,
+}
+
+impl<K, V> AvlMap<K, V> {
+    pub fn new() -> Self {
+        Self { set: BstAvlSet::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+}
+
+impl<K: Ord, V> Map<K, V> for AvlMap<K, V> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(kv) = self.set.find_by_mut(|kv| key.cmp(&kv.key)) {
+            return Some(std::mem::replace(&mut kv.value, value));
+        }
+
+        self.set.insert(KeyValue { key, value });
+        None
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.set.find_by(|kv| key.cmp(&kv.key)).map(|kv| &kv.value)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.set.find_by_mut(|kv| key.cmp(&kv.key)).map(|kv| &mut kv.value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.set.remove_by(|kv| key.cmp(&kv.key)).map(|kv| kv.value)
+    }
+
+    fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, default: F) -> &mut V {
+        if self.set.find_by(|kv| key.cmp(&kv.key)).is_some() {
+            return self.get_mut(&key).unwrap();
+        }
+
+        self.insert_vacant(key, default())
+    }
+
+    fn insert_vacant(&mut self, key: K, value: V) -> &mut V {
+        let kv = self
+            .set
+            .insert_new(KeyValue { key, value })
+            .expect("AvlMap: a chave já estava no mapa");
+        &mut kv.value
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(self.set.iter().map(|kv| (&kv.key, &kv.value)))
+    }
+}
+
+impl<K, V> Default for AvlMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,98 @@
+use crate::map::{KeyValue, Map};
+use crate::set::HashChainingSet;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+/// Mapa implementado com tabela de hash usando encadeamento.
+/// Reaproveita o `HashChainingSet`: cada par cai no bucket do hash da sua chave.
+pub struct HashChainingMap<K, V, S = RandomState> {
+    set: HashChainingSet<KeyValue<K, V>, S>,
+}
+
+impl<K, V> HashChainingMap<K, V, RandomState> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> HashChainingMap<K, V, S>
+where
+    S: BuildHasher,
+{
+    /// Cria um mapa com um construtor de hasher customizado.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            set: HashChainingSet::with_hasher(hash_builder),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+}
+
+impl<K, V, S> Map<K, V> for HashChainingMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let h = self.set.hash_of(&key);
+        if let Some(kv) = self.set.find_by_mut(h, |kv| kv.key == key) {
+            return Some(std::mem::replace(&mut kv.value, value));
+        }
+
+        self.set.insert_new(KeyValue { key, value });
+        None
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        let h = self.set.hash_of(key);
+        self.set.find_by(h, |kv| kv.key == *key).map(|kv| &kv.value)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let h = self.set.hash_of(key);
+        self.set.find_by_mut(h, |kv| kv.key == *key).map(|kv| &mut kv.value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let h = self.set.hash_of(key);
+        self.set.remove_by(h, |kv| kv.key == *key).map(|kv| kv.value)
+    }
+
+    fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, default: F) -> &mut V {
+        let h = self.set.hash_of(&key);
+        if self.set.find_by(h, |kv| kv.key == key).is_some() {
+            return self.get_mut(&key).unwrap();
+        }
+
+        self.insert_vacant(key, default())
+    }
+
+    fn insert_vacant(&mut self, key: K, value: V) -> &mut V {
+        let kv = self
+            .set
+            .insert_new(KeyValue { key, value })
+            .expect("HashChainingMap: a chave já estava no mapa");
+        &mut kv.value
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(self.set.iter().map(|kv| (&kv.key, &kv.value)))
+    }
+}
+
+impl<K, V> Default for HashChainingMap<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
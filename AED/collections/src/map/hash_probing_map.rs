@@ -0,0 +1,100 @@
+use crate::map::{KeyValue, Map};
+use crate::set::{HashProbingSet, LinearProbing, ProbeSequence};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+/// Mapa implementado com tabela de hash de endereçamento aberto.
+/// Reaproveita o `HashProbingSet`; `P` escolhe a sequência de sondagem.
+pub struct HashProbingMap<K, V, S = RandomState, P = LinearProbing> {
+    set: HashProbingSet<KeyValue<K, V>, S, P>,
+}
+
+impl<K, V, P: ProbeSequence> HashProbingMap<K, V, RandomState, P> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S, P> HashProbingMap<K, V, S, P>
+where
+    S: BuildHasher,
+    P: ProbeSequence,
+{
+    /// Cria um mapa com um construtor de hasher customizado.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            set: HashProbingSet::with_hasher(hash_builder),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+}
+
+impl<K, V, S, P> Map<K, V> for HashProbingMap<K, V, S, P>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    P: ProbeSequence,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let h = self.set.hash_of(&key);
+        if let Some(kv) = self.set.find_by_mut(h, |kv| kv.key == key) {
+            return Some(std::mem::replace(&mut kv.value, value));
+        }
+
+        self.set.insert_new(KeyValue { key, value });
+        None
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        let h = self.set.hash_of(key);
+        self.set.find_by(h, |kv| kv.key == *key).map(|kv| &kv.value)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let h = self.set.hash_of(key);
+        self.set.find_by_mut(h, |kv| kv.key == *key).map(|kv| &mut kv.value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let h = self.set.hash_of(key);
+        self.set.remove_by(h, |kv| kv.key == *key).map(|kv| kv.value)
+    }
+
+    fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, default: F) -> &mut V {
+        let h = self.set.hash_of(&key);
+        if self.set.find_by(h, |kv| kv.key == key).is_some() {
+            return self.get_mut(&key).unwrap();
+        }
+
+        self.insert_vacant(key, default())
+    }
+
+    fn insert_vacant(&mut self, key: K, value: V) -> &mut V {
+        let kv = self
+            .set
+            .insert_new(KeyValue { key, value })
+            .expect("HashProbingMap: a chave já estava no mapa");
+        &mut kv.value
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(self.set.iter().map(|kv| (&kv.key, &kv.value)))
+    }
+}
+
+impl<K, V, P: ProbeSequence> Default for HashProbingMap<K, V, RandomState, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,76 @@
+use crate::map::{KeyValue, Map};
+use crate::set::ListSet;
+
+/// Mapa implementado como lista ligada de pares.
+/// Operações são O(n).
+/// Reaproveita o `ListSet`, comparando os pares só pela chave.
+pub struct ListMap<K, V> {
+    set: ListSet<KeyValue<K, V>>,
+}
+
+impl<K, V> ListMap<K, V> {
+    pub fn new() -> Self {
+        Self { set: ListSet::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+}
+
+impl<K: PartialEq, V> Map<K, V> for ListMap<K, V> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(kv) = self.set.find_by_mut(|kv| kv.key == key) {
+            return Some(std::mem::replace(&mut kv.value, value));
+        }
+
+        self.set.insert_new(KeyValue { key, value });
+        None
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.set.find_by(|kv| kv.key == *key).map(|kv| &kv.value)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.set.find_by_mut(|kv| kv.key == *key).map(|kv| &mut kv.value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.set.remove_by(|kv| kv.key == *key).map(|kv| kv.value)
+    }
+
+    fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, default: F) -> &mut V {
+        if self.set.find_by(|kv| kv.key == key).is_some() {
+            return self.get_mut(&key).unwrap();
+        }
+
+        self.insert_vacant(key, default())
+    }
+
+    fn insert_vacant(&mut self, key: K, value: V) -> &mut V {
+        let kv = self
+            .set
+            .insert_new(KeyValue { key, value })
+            .expect("ListMap: a chave já estava no mapa");
+        &mut kv.value
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(self.set.iter().map(|kv| (&kv.key, &kv.value)))
+    }
+}
+
+impl<K, V> Default for ListMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
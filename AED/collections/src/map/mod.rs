@@ -0,0 +1,254 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+/// TAD Mapa (dicionário): associa no máximo um valor a cada chave.
+pub trait Map<K, V> {
+    /// Insere o par. Se a chave já existia, troca o valor e devolve o antigo.
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+
+    /// Valor associado à chave.
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Valor associado à chave, para alteração.
+    fn get_mut(&mut self, key: &K) -> Option<&mut V>;
+
+    /// Remove a chave e devolve o valor que estava associado a ela.
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    /// A chave está no mapa?
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Número de pares no mapa.
+    fn len(&self) -> usize;
+
+    /// Mapa vazio?
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Valor da chave; se ela não existe, insere `default()` antes.
+    fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, default: F) -> &mut V
+    where
+        Self: Sized;
+
+    /// Insere um par cuja chave não está no mapa e devolve o valor.
+    ///
+    /// É o que `VacantEntry::insert` usa: a entrada já sabe que a chave
+    /// está ausente, então os mapas inserem direto, sem procurá-la antes.
+    ///
+    /// # Panics
+    ///
+    /// Se a chave já está no mapa.
+    fn insert_vacant(&mut self, key: K, value: V) -> &mut V
+    where
+        Self: Sized,
+    {
+        let mut inserted = false;
+        let value = self.get_or_insert_with(key, || {
+            inserted = true;
+            value
+        });
+        assert!(inserted, "Map::insert_vacant: a chave já estava no mapa");
+        value
+    }
+
+    /// Entrada da chave, para consultar e alterar com uma única chamada:
+    ///
+    /// ```ignore
+    /// *contagem.entry(palavra).or_insert(0) += 1;
+    /// ```
+    fn entry(&mut self, key: K) -> Entry<'_, K, V, Self>
+    where
+        Self: Sized,
+    {
+        if self.contains_key(&key) {
+            Entry::Occupied(OccupiedEntry {
+                map: self,
+                key,
+                _value: PhantomData,
+            })
+        } else {
+            Entry::Vacant(VacantEntry {
+                map: self,
+                key,
+                _value: PhantomData,
+            })
+        }
+    }
+
+    /// Percorre os pares. Mapas em árvore devolvem em ordem crescente de chave;
+    /// mapas de hash devolvem na ordem da tabela.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a>;
+}
+
+// ========================
+// Entry API
+// ========================
+
+/// Entrada de uma chave no mapa: ocupada (a chave existe) ou vaga.
+pub enum Entry<'a, K, V, M> {
+    Occupied(OccupiedEntry<'a, K, V, M>),
+    Vacant(VacantEntry<'a, K, V, M>),
+}
+
+pub struct OccupiedEntry<'a, K, V, M> {
+    map: &'a mut M,
+    key: K,
+    _value: PhantomData<V>,
+}
+
+pub struct VacantEntry<'a, K, V, M> {
+    map: &'a mut M,
+    key: K,
+    _value: PhantomData<V>,
+}
+
+impl<'a, K, V, M: Map<K, V>> Entry<'a, K, V, M> {
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(e) => e.key(),
+            Entry::Vacant(e) => e.key(),
+        }
+    }
+
+    /// Valor da entrada, inserindo `default` se estiver vaga.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(default),
+        }
+    }
+
+    /// Valor da entrada, inserindo `default()` se estiver vaga.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(default()),
+        }
+    }
+
+    /// Valor da entrada, inserindo `V::default()` se estiver vaga.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Aplica `f` ao valor se a entrada estiver ocupada.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut e) => {
+                f(e.get_mut());
+                Entry::Occupied(e)
+            }
+            Entry::Vacant(e) => Entry::Vacant(e),
+        }
+    }
+}
+
+impl<'a, K, V, M: Map<K, V>> OccupiedEntry<'a, K, V, M> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn get(&self) -> &V {
+        self.map.get(&self.key).expect("OccupiedEntry: chave sumiu do mapa")
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        self.map.get_mut(&self.key).expect("OccupiedEntry: chave sumiu do mapa")
+    }
+
+    /// Converte a entrada em uma referência com o tempo de vida do mapa.
+    pub fn into_mut(self) -> &'a mut V {
+        self.map.get_mut(&self.key).expect("OccupiedEntry: chave sumiu do mapa")
+    }
+
+    /// Troca o valor e devolve o antigo.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove o par do mapa e devolve o valor.
+    pub fn remove(self) -> V {
+        self.map.remove(&self.key).expect("OccupiedEntry: chave sumiu do mapa")
+    }
+}
+
+impl<'a, K, V, M: Map<K, V>> VacantEntry<'a, K, V, M> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Insere o valor e devolve uma referência para ele.
+    pub fn insert(self, value: V) -> &'a mut V {
+        // a busca de `entry` já mostrou que a chave não está no mapa
+        self.map.insert_vacant(self.key, value)
+    }
+}
+
+// ========================
+// Par chave/valor
+// ========================
+
+/// Elemento guardado pelos mapas dentro dos conjuntos de `crate::set`.
+///
+/// Comparação, igualdade e hash olham só para a chave: assim um
+/// `BstAvlSet<KeyValue<K, V>>` já é um mapa ordenado por `K`, e um
+/// `HashChainingSet<KeyValue<K, V>>` espalha os pares pelo hash de `K`.
+pub(crate) struct KeyValue<K, V> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+}
+
+impl<K: PartialEq, V> PartialEq for KeyValue<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Eq, V> Eq for KeyValue<K, V> {}
+
+impl<K: Ord, V> PartialOrd for KeyValue<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for KeyValue<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl<K: Hash, V> Hash for KeyValue<K, V> {
+    // mesmo hash da chave sozinha: `hash_of(&key)` acha o par na tabela
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+pub mod list_map;
+
+pub mod bst_map;
+pub mod avl_map;
+pub mod rb_map;
+
+pub mod hash_chaining_map;
+pub mod hash_probing_map;
+
+pub use list_map::ListMap;
+pub use bst_map::BstMap;
+pub use avl_map::AvlMap;
+pub use rb_map::RbMap;
+
+pub use hash_chaining_map::HashChainingMap;
+pub use hash_probing_map::HashProbingMap;
@@ -0,0 +1,163 @@
+//! Contadores de operações (feature `metrics`).
+//!
+//! Tempo de relógio é ruidoso; para conferir uma afirmação como "a busca
+//! faz O(log n) comparações" é melhor contar as comparações. Com a feature
+//! `metrics` ligada, as estruturas de `set` (e os mapas construídos sobre
+//! elas) registram:
+//!
+//! - comparações de chave (`Ord::cmp` nas árvores, igualdade nas listas e
+//!   tabelas de hash);
+//! - hashes calculados;
+//! - passos de sondagem: elementos de bucket ou posições da tabela visitados;
+//! - rotações (AVL, rubro-negra, splay);
+//! - trocas de cor (rubro-negra);
+//! - rehashes (reconstruções da tabela).
+//!
+//! Contam-se as operações pontuais (`insert`, `remove`, `contains`) e as
+//! intercalações da álgebra de conjuntos; as consultas de vizinhança de
+//! `OrderedSet` (`floor`, `ceiling`, ...) não são instrumentadas.
+//!
+//! Os contadores são por thread: `reset` antes do trecho medido e
+//! `snapshot` depois. Sem a feature, `record` é vazio e some na compilação,
+//! e `snapshot` devolve tudo zerado.
+//!
+//! ```text
+//! cargo bench --bench bench_set --features metrics
+//! ```
+
+use std::cmp::Ordering;
+
+/// `true` se a crate foi compilada com a feature `metrics`.
+pub const ENABLED: bool = cfg!(feature = "metrics");
+
+/// Tipo de operação contada.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Counter {
+    Comparisons,
+    Hashes,
+    Probes,
+    Rotations,
+    ColorFlips,
+    Rehashes,
+}
+
+/// Valores dos contadores num dado momento.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Counts {
+    pub comparisons: u64,
+    pub hashes: u64,
+    pub probes: u64,
+    pub rotations: u64,
+    pub color_flips: u64,
+    pub rehashes: u64,
+}
+
+impl Counts {
+    const ZERO: Counts = Counts {
+        comparisons: 0,
+        hashes: 0,
+        probes: 0,
+        rotations: 0,
+        color_flips: 0,
+        rehashes: 0,
+    };
+
+    /// Cabeçalho das colunas, na ordem de `to_csv`.
+    pub const CSV_HEADER: &'static str = "comparisons;hashes;probes;rotations;color_flips;rehashes";
+
+    /// Os contadores separados por `;`.
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{};{};{};{};{};{}",
+            self.comparisons, self.hashes, self.probes, self.rotations, self.color_flips, self.rehashes
+        )
+    }
+
+    /// O contador correspondente a `counter`.
+    pub fn get(&self, counter: Counter) -> u64 {
+        match counter {
+            Counter::Comparisons => self.comparisons,
+            Counter::Hashes => self.hashes,
+            Counter::Probes => self.probes,
+            Counter::Rotations => self.rotations,
+            Counter::ColorFlips => self.color_flips,
+            Counter::Rehashes => self.rehashes,
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn get_mut(&mut self, counter: Counter) -> &mut u64 {
+        match counter {
+            Counter::Comparisons => &mut self.comparisons,
+            Counter::Hashes => &mut self.hashes,
+            Counter::Probes => &mut self.probes,
+            Counter::Rotations => &mut self.rotations,
+            Counter::ColorFlips => &mut self.color_flips,
+            Counter::Rehashes => &mut self.rehashes,
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+thread_local! {
+    static COUNTS: std::cell::Cell<Counts> = const { std::cell::Cell::new(Counts::ZERO) };
+}
+
+/// Zera os contadores desta thread.
+pub fn reset() {
+    #[cfg(feature = "metrics")]
+    COUNTS.with(|c| c.set(Counts::ZERO));
+}
+
+/// Contadores desta thread desde o último `reset`.
+pub fn snapshot() -> Counts {
+    #[cfg(feature = "metrics")]
+    return COUNTS.with(|c| c.get());
+
+    #[cfg(not(feature = "metrics"))]
+    Counts::ZERO
+}
+
+/// Soma 1 ao contador.
+#[inline(always)]
+pub(crate) fn record(counter: Counter) {
+    #[cfg(feature = "metrics")]
+    COUNTS.with(|c| {
+        let mut counts = c.get();
+        *counts.get_mut(counter) += 1;
+        c.set(counts);
+    });
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = counter;
+}
+
+// ========================
+// Atalhos para as estruturas
+// ========================
+
+/// `a.cmp(b)`, contando uma comparação.
+#[inline(always)]
+pub(crate) fn cmp<Q: Ord + ?Sized>(a: &Q, b: &Q) -> Ordering {
+    record(Counter::Comparisons);
+    a.cmp(b)
+}
+
+/// Envolve a função de comparação dos métodos `*_by` das árvores:
+/// cada chamada conta uma comparação.
+#[inline(always)]
+pub(crate) fn counted_cmp<T: ?Sized>(cmp: impl Fn(&T) -> Ordering) -> impl Fn(&T) -> Ordering {
+    move |x| {
+        record(Counter::Comparisons);
+        cmp(x)
+    }
+}
+
+/// Idem para os predicados de igualdade das listas e tabelas de hash.
+#[inline(always)]
+pub(crate) fn counted_eq<T: ?Sized>(eq: impl Fn(&T) -> bool) -> impl Fn(&T) -> bool {
+    move |x| {
+        record(Counter::Comparisons);
+        eq(x)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::map::KeyValue;
+use crate::multiset::MultiSet;
+use crate::set::{BstAvlSet, Set};
+
+/// Multiconjunto implementado como árvore AVL de pares (elemento, contagem).
+/// Operações são O(log d), com d o número de elementos distintos.
+/// Reaproveita o `BstAvlSet`, como o `AvlMap`.
+pub struct AvlMultiSet<T> {
+    set: BstAvlSet<KeyValue<T, usize>>,
+    total: usize,
+}
+
+impl<T> AvlMultiSet<T> {
+    pub fn new() -> Self {
+        Self {
+            set: BstAvlSet::new(),
+            total: 0,
+        }
+    }
+}
+
+impl<T: Ord> MultiSet<T> for AvlMultiSet<T> {
+    fn insert(&mut self, value: T) -> usize {
+        self.total += 1;
+        if let Some(kv) = self.set.find_by_mut(|kv| value.cmp(&kv.key)) {
+            kv.value += 1;
+            return kv.value;
+        }
+
+        self.set.insert(KeyValue { key: value, value: 1 });
+        1
+    }
+
+    fn remove_one(&mut self, value: &T) -> bool {
+        let Some(kv) = self.set.find_by_mut(|kv| value.cmp(&kv.key)) else {
+            return false;
+        };
+
+        self.total -= 1;
+        kv.value -= 1;
+        if kv.value == 0 {
+            self.set.remove_by(|kv| value.cmp(&kv.key));
+        }
+        true
+    }
+
+    fn remove_all(&mut self, value: &T) -> usize {
+        let removed = self.set.remove_by(|kv| value.cmp(&kv.key)).map_or(0, |kv| kv.value);
+        self.total -= removed;
+        removed
+    }
+
+    fn count(&self, value: &T) -> usize {
+        self.set.find_by(|kv| value.cmp(&kv.key)).map_or(0, |kv| kv.value)
+    }
+
+    fn total_len(&self) -> usize {
+        self.total
+    }
+
+    fn distinct_len(&self) -> usize {
+        self.set.len()
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a T, usize)> + 'a> {
+        Box::new(self.set.iter().map(|kv| (&kv.key, kv.value)))
+    }
+}
+
+impl<T: Ord> FromIterator<T> for AvlMultiSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ms = Self::new();
+        ms.extend(iter);
+        ms
+    }
+}
+
+impl<T: Ord> Extend<T> for AvlMultiSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> Default for AvlMultiSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
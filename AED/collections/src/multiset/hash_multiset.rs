@@ -0,0 +1,118 @@
+use crate::map::KeyValue;
+use crate::multiset::MultiSet;
+use crate::set::HashChainingSet;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+/// Multiconjunto implementado com tabela de hash (encadeamento) de pares
+/// (elemento, contagem). Operações são O(1) em média.
+/// Reaproveita o `HashChainingSet`, como o `HashChainingMap`.
+pub struct HashMultiSet<T, S = RandomState> {
+    set: HashChainingSet<KeyValue<T, usize>, S>,
+    total: usize,
+}
+
+impl<T> HashMultiSet<T, RandomState> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<T, S> HashMultiSet<T, S>
+where
+    S: BuildHasher,
+{
+    /// Cria um multiconjunto com um construtor de hasher customizado.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            set: HashChainingSet::with_hasher(hash_builder),
+            total: 0,
+        }
+    }
+}
+
+impl<T, S> MultiSet<T> for HashMultiSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn insert(&mut self, value: T) -> usize {
+        self.total += 1;
+        let h = self.set.hash_of(&value);
+        if let Some(kv) = self.set.find_by_mut(h, |kv| kv.key == value) {
+            kv.value += 1;
+            return kv.value;
+        }
+
+        self.set.insert_new_hashed(h, KeyValue { key: value, value: 1 });
+        1
+    }
+
+    fn remove_one(&mut self, value: &T) -> bool {
+        let h = self.set.hash_of(value);
+        let Some(kv) = self.set.find_by_mut(h, |kv| kv.key == *value) else {
+            return false;
+        };
+
+        self.total -= 1;
+        kv.value -= 1;
+        if kv.value == 0 {
+            self.set.remove_by(h, |kv| kv.key == *value);
+        }
+        true
+    }
+
+    fn remove_all(&mut self, value: &T) -> usize {
+        let h = self.set.hash_of(value);
+        let removed = self.set.remove_by(h, |kv| kv.key == *value).map_or(0, |kv| kv.value);
+        self.total -= removed;
+        removed
+    }
+
+    fn count(&self, value: &T) -> usize {
+        let h = self.set.hash_of(value);
+        self.set.find_by(h, |kv| kv.key == *value).map_or(0, |kv| kv.value)
+    }
+
+    fn total_len(&self) -> usize {
+        self.total
+    }
+
+    fn distinct_len(&self) -> usize {
+        self.set.len()
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a T, usize)> + 'a> {
+        Box::new(self.set.iter().map(|kv| (&kv.key, kv.value)))
+    }
+}
+
+impl<T, S> FromIterator<T> for HashMultiSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ms = Self::with_hasher(S::default());
+        ms.extend(iter);
+        ms
+    }
+}
+
+impl<T, S> Extend<T> for HashMultiSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> Default for HashMultiSet<T, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
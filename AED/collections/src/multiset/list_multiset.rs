@@ -0,0 +1,90 @@
+use crate::map::KeyValue;
+use crate::multiset::MultiSet;
+use crate::set::ListSet;
+
+/// Multiconjunto implementado como lista ligada de pares (elemento, contagem).
+/// Operações são O(d), com d o número de elementos distintos.
+/// Reaproveita o `ListSet`, como o `ListMap`.
+pub struct ListMultiSet<T> {
+    set: ListSet<KeyValue<T, usize>>,
+    total: usize,
+}
+
+impl<T> ListMultiSet<T> {
+    pub fn new() -> Self {
+        Self {
+            set: ListSet::new(),
+            total: 0,
+        }
+    }
+}
+
+impl<T: PartialEq> MultiSet<T> for ListMultiSet<T> {
+    fn insert(&mut self, value: T) -> usize {
+        self.total += 1;
+        if let Some(kv) = self.set.find_by_mut(|kv| kv.key == value) {
+            kv.value += 1;
+            return kv.value;
+        }
+
+        self.set.insert_new(KeyValue { key: value, value: 1 });
+        1
+    }
+
+    fn remove_one(&mut self, value: &T) -> bool {
+        let Some(kv) = self.set.find_by_mut(|kv| kv.key == *value) else {
+            return false;
+        };
+
+        self.total -= 1;
+        kv.value -= 1;
+        if kv.value == 0 {
+            self.set.remove_by(|kv| kv.key == *value);
+        }
+        true
+    }
+
+    fn remove_all(&mut self, value: &T) -> usize {
+        let removed = self.set.remove_by(|kv| kv.key == *value).map_or(0, |kv| kv.value);
+        self.total -= removed;
+        removed
+    }
+
+    fn count(&self, value: &T) -> usize {
+        self.set.find_by(|kv| kv.key == *value).map_or(0, |kv| kv.value)
+    }
+
+    fn total_len(&self) -> usize {
+        self.total
+    }
+
+    fn distinct_len(&self) -> usize {
+        self.set.len()
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a T, usize)> + 'a> {
+        Box::new(self.set.iter().map(|kv| (&kv.key, kv.value)))
+    }
+}
+
+impl<T: PartialEq> FromIterator<T> for ListMultiSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ms = Self::new();
+        ms.extend(iter);
+        ms
+    }
+}
+
+impl<T: PartialEq> Extend<T> for ListMultiSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> Default for ListMultiSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,46 @@
+/// TAD Multiconjunto (bag): como um conjunto, mas cada elemento pode
+/// aparecer várias vezes.
+///
+/// Cada elemento distinto é guardado uma vez só, junto com o número de
+/// ocorrências; repetir uma inserção só incrementa o contador.
+pub trait MultiSet<T> {
+    /// Insere uma ocorrência de `value`. Devolve quantas há agora.
+    fn insert(&mut self, value: T) -> usize;
+
+    /// Remove uma ocorrência de `value`. Devolve `true` se havia alguma.
+    fn remove_one(&mut self, value: &T) -> bool;
+
+    /// Remove todas as ocorrências de `value`. Devolve quantas eram.
+    fn remove_all(&mut self, value: &T) -> usize;
+
+    /// Número de ocorrências de `value` (0 se não está).
+    fn count(&self, value: &T) -> usize;
+
+    /// O elemento aparece pelo menos uma vez?
+    fn contains(&self, value: &T) -> bool {
+        self.count(value) > 0
+    }
+
+    /// Número de ocorrências, contando repetidos.
+    fn total_len(&self) -> usize;
+
+    /// Número de elementos distintos.
+    fn distinct_len(&self) -> usize;
+
+    /// Multiconjunto vazio?
+    fn is_empty(&self) -> bool {
+        self.distinct_len() == 0
+    }
+
+    /// Percorre os elementos distintos com suas contagens. Multiconjuntos em
+    /// árvore devolvem em ordem crescente; os de hash, na ordem da tabela.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a T, usize)> + 'a>;
+}
+
+pub mod list_multiset;
+pub mod avl_multiset;
+pub mod hash_multiset;
+
+pub use list_multiset::ListMultiSet;
+pub use avl_multiset::AvlMultiSet;
+pub use hash_multiset::HashMultiSet;
@@ -37,6 +37,10 @@ impl<T> ArrayQueue<T> {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     fn grow(&mut self) {
         let old_cap = self.capacity;
         let new_cap = old_cap * 2;
@@ -139,3 +143,38 @@ impl<T> Drop for ArrayQueue<T> {
         }
     }
 }
+
+impl<T> Default for ArrayQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ========================
+// Serde (feature `serde`)
+// ========================
+
+/// Da frente para o fim, independente de onde o anel começa no arranjo.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ArrayQueue<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let values = (0..self.len).map(|i| {
+            // SAFETY: as `len` posições a partir de `head` (com a volta do
+            // anel) estão inicializadas
+            unsafe { &*self.ptr.add((self.head + i) % self.capacity) }
+        });
+        crate::serde_util::serialize_iter(self.len, values, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ArrayQueue<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        let mut queue = Self::with_capacity(values.len());
+        for value in values {
+            queue.enqueue(value);
+        }
+        Ok(queue)
+    }
+}
@@ -29,6 +29,9 @@ impl<T> ListQueue<T> {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 }
 
 impl<T> Queue<T> for ListQueue<T> {
@@ -79,3 +82,48 @@ impl<T> Queue<T> for ListQueue<T> {
     }
 
 }
+
+// Drop iterativo: desliga um nó de cada vez em vez de deixar cada `Box`
+// derrubar o próximo recursivamente.
+impl<T> Drop for ListQueue<T> {
+    fn drop(&mut self) {
+        self.tail = ptr::null_mut();
+
+        let mut cur = self.head.take();
+        while let Some(mut node) = cur {
+            cur = node.next.take();
+        }
+    }
+}
+
+impl<T> Default for ListQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ========================
+// Serde (feature `serde`)
+// ========================
+
+/// Da frente para o fim.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ListQueue<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let values = std::iter::successors(self.head.as_deref(), |node| node.next.as_deref())
+            .map(|node| &node.elem);
+        crate::serde_util::serialize_iter(self.len, values, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ListQueue<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        let mut queue = Self::new();
+        for value in values {
+            queue.enqueue(value);
+        }
+        Ok(queue)
+    }
+}
@@ -286,3 +286,32 @@ impl<'a, T> Iterator for ArraySeqIterMut<'a, T> {
         }
     }
 }
+
+impl<T> Default for ArraySeq<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ========================
+// Serde (feature `serde`)
+// ========================
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ArraySeq<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_util::serialize_iter(self.len, self.iter(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ArraySeq<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        let mut seq = Self::with_capacity(values.len().max(1));
+        for value in values {
+            seq.append(value);
+        }
+        Ok(seq)
+    }
+}
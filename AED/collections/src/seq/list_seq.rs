@@ -215,3 +215,50 @@ impl<T> IndexMut<usize> for ListSeq<T> {
             .expect("ListSeq::index_mut: índice fora dos limites")
     }
 }
+
+// ========================
+// Drop
+// ========================
+
+// O drop gerado pelo compilador é recursivo (cada nó derruba o próximo)
+// e estoura a pilha em listas com milhões de nós. Aqui desligamos um nó
+// de cada vez.
+impl<T> Drop for ListSeq<T> {
+    fn drop(&mut self) {
+        let mut cur = self.head.take();
+        while let Some(mut node) = cur {
+            cur = node.next.take();
+        }
+    }
+}
+
+impl<T> Default for ListSeq<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ========================
+// Serde (feature `serde`)
+// ========================
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ListSeq<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_util::serialize_iter(self.len, self.iter(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ListSeq<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // `append` percorre a lista inteira; de trás para frente com
+        // `prepend` a reconstrução fica O(n)
+        let values = Vec::<T>::deserialize(deserializer)?;
+        let mut seq = Self::new();
+        for value in values.into_iter().rev() {
+            seq.prepend(value);
+        }
+        Ok(seq)
+    }
+}
@@ -42,6 +42,8 @@ pub trait Seq<T>: Index<usize, Output = T> + IndexMut<usize, Output = T> {
 
 pub mod array_seq;
 pub mod list_seq;
+pub mod skip_list_seq;
 
 pub use array_seq::ArraySeq;
 pub use list_seq::ListSeq;
+pub use skip_list_seq::SkipListSeq;
@@ -0,0 +1,324 @@
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+use super::{Seq, SeqError};
+use crate::set::skip_list_set::{random_level, MAX_LEVEL};
+
+// ========================
+// Nó da skip list indexável
+// ========================
+//
+// Mesma organização do `SkipListSet` (arena de nós, links por índice), mas
+// ordenada por posição em vez de por valor. Cada link guarda também a sua
+// largura: quantos passos no nível 0 ele pula. Somando larguras, a busca
+// pela posição i desce como uma busca por chave.
+//
+// Posições contam a partir da cabeça: cabeça = 0, elementos = 1..=len,
+// e um link `None` aponta para um "fim" virtual em len + 1.
+
+struct Node<T> {
+    data: T,
+    next: Vec<Option<usize>>,
+    width: Vec<usize>,
+}
+
+/// Caminho de uma busca: em cada nível, o último nó antes do alvo
+/// e a posição dele.
+type Path = [(Option<usize>, usize); MAX_LEVEL];
+
+// ========================
+// Estrutura principal: SkipListSeq<T>
+// ========================
+
+/// Implementação de Seq<T> como skip list indexável: `get`, `insert_at` e
+/// `remove_from` são O(log n) esperado, contra O(n) do `ListSeq`.
+pub struct SkipListSeq<T, R = StdRng> {
+    head: [Option<usize>; MAX_LEVEL],
+    head_width: [usize; MAX_LEVEL],
+    nodes: Vec<Option<Node<T>>>,
+    free: Vec<usize>,
+    level: usize, // níveis em uso
+    len: usize,
+    rng: R,
+}
+
+impl<T> SkipListSeq<T> {
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Sequência vazia com gerador de semente fixa (estrutura reprodutível).
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<T, R> SkipListSeq<T, R> {
+    pub fn with_rng(rng: R) -> Self {
+        Self {
+            head: [None; MAX_LEVEL],
+            head_width: [1; MAX_LEVEL],
+            nodes: Vec::new(),
+            free: Vec::new(),
+            level: 0,
+            len: 0,
+            rng,
+        }
+    }
+
+    fn node(&self, i: usize) -> &Node<T> {
+        self.nodes[i].as_ref().expect("SkipListSeq: link para posição livre")
+    }
+
+    fn node_mut(&mut self, i: usize) -> &mut Node<T> {
+        self.nodes[i].as_mut().expect("SkipListSeq: link para posição livre")
+    }
+
+    /// Link e largura do nível `lvl` que saem de `pos` (`None` = cabeça).
+    fn link(&self, pos: Option<usize>, lvl: usize) -> (Option<usize>, usize) {
+        match pos {
+            Some(i) => {
+                let node = self.node(i);
+                (node.next[lvl], node.width[lvl])
+            }
+            None => (self.head[lvl], self.head_width[lvl]),
+        }
+    }
+
+    fn set_link(&mut self, pos: Option<usize>, lvl: usize, next: Option<usize>, width: usize) {
+        match pos {
+            Some(i) => {
+                let node = self.node_mut(i);
+                node.next[lvl] = next;
+                node.width[lvl] = width;
+            }
+            None => {
+                self.head[lvl] = next;
+                self.head_width[lvl] = width;
+            }
+        }
+    }
+
+    /// Em cada nível, o último nó com posição `< target` (e a posição dele).
+    fn path_to(&self, target: usize) -> Path {
+        let mut path = [(None, 0); MAX_LEVEL];
+        let mut pos = None;
+        let mut rank = 0;
+
+        for lvl in (0..self.level).rev() {
+            loop {
+                let (next, width) = self.link(pos, lvl);
+                match next {
+                    Some(n) if rank + width < target => {
+                        pos = Some(n);
+                        rank += width;
+                    }
+                    _ => break,
+                }
+            }
+            path[lvl] = (pos, rank);
+        }
+
+        path
+    }
+
+    /// Nó na posição `index` (0-based), se existir.
+    fn find(&self, index: usize) -> Option<usize> {
+        if index >= self.len {
+            return None;
+        }
+        let (pos, _) = self.path_to(index + 1)[0];
+        self.link(pos, 0).0
+    }
+}
+
+// ========================
+// Iterador imutável
+// ========================
+
+pub struct SkipListSeqIter<'a, T> {
+    nodes: &'a [Option<Node<T>>],
+    next: Option<usize>,
+}
+
+impl<'a, T> Iterator for SkipListSeqIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes[self.next?].as_ref()?;
+        self.next = node.next[0];
+        Some(&node.data)
+    }
+}
+
+// ========================
+// Iterador mutável
+// ========================
+
+/// Anda pelos índices do nível 0. Cada nó é visitado uma única vez, então
+/// as referências `&mut` devolvidas nunca se sobrepõem.
+pub struct SkipListSeqIterMut<'a, T> {
+    nodes: *mut Option<Node<T>>,
+    next: Option<usize>,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for SkipListSeqIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.next?;
+
+        unsafe {
+            // `i` é um índice válido da arena, emprestada via `&'a mut`
+            let node = (*self.nodes.add(i)).as_mut()?;
+            self.next = node.next[0];
+            Some(&mut node.data)
+        }
+    }
+}
+
+// ========================
+// Implementação do TAD Seq<T>
+// ========================
+
+impl<T, R: RngCore> Seq<T> for SkipListSeq<T, R> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn insert_at(&mut self, index: usize, value: T) -> Result<(), SeqError> {
+        if index > self.len {
+            return Err(SeqError::OutOfBounds { index, len: self.len });
+        }
+
+        // o nó novo ocupará a posição `rank`
+        let rank = index + 1;
+        let path = self.path_to(rank);
+
+        let height = random_level(&mut self.rng);
+        while self.level < height {
+            // nível novo: a cabeça pula direto para o fim
+            self.head[self.level] = None;
+            self.head_width[self.level] = self.len + 1;
+            self.level += 1;
+        }
+
+        let idx = match self.free.pop() {
+            Some(i) => i,
+            None => {
+                self.nodes.push(None);
+                self.nodes.len() - 1
+            }
+        };
+
+        let mut node = Node {
+            data: value,
+            next: Vec::with_capacity(height),
+            width: Vec::with_capacity(height),
+        };
+
+        for (lvl, &(pos, pos_rank)) in path.iter().enumerate().take(self.level) {
+            let (next, width) = self.link(pos, lvl);
+
+            if lvl < height {
+                // o nó novo entra entre `pos` e `next`; `next` anda uma
+                // posição para frente
+                node.next.push(next);
+                node.width.push(pos_rank + width + 1 - rank);
+                self.set_link(pos, lvl, Some(idx), rank - pos_rank);
+            } else {
+                // link passa por cima do nó novo
+                self.set_link(pos, lvl, next, width + 1);
+            }
+        }
+
+        self.nodes[idx] = Some(node);
+        self.len += 1;
+        Ok(())
+    }
+
+    fn remove_from(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let path = self.path_to(index + 1);
+        let target = self.link(path[0].0, 0).0?;
+        let node = self.nodes[target].take().expect("SkipListSeq: nó já removido");
+
+        for (lvl, &(pos, _)) in path.iter().enumerate().take(self.level) {
+            let (next, width) = self.link(pos, lvl);
+
+            if next == Some(target) {
+                // `pos` herda o link do nó removido
+                self.set_link(pos, lvl, node.next[lvl], width + node.width[lvl] - 1);
+            } else {
+                self.set_link(pos, lvl, next, width - 1);
+            }
+        }
+
+        // níveis do topo que ficaram vazios deixam de ser usados
+        while self.level > 0 && self.head[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+
+        self.free.push(target);
+        self.len -= 1;
+        Some(node.data)
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(SkipListSeqIter {
+            nodes: &self.nodes,
+            next: self.head[0],
+        })
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &'a mut T> + 'a> {
+        Box::new(SkipListSeqIterMut {
+            nodes: self.nodes.as_mut_ptr(),
+            next: self.head[0],
+            _marker: PhantomData,
+        })
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        let i = self.find(index)?;
+        Some(&self.node(i).data)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let i = self.find(index)?;
+        Some(&mut self.node_mut(i).data)
+    }
+}
+
+// ========================
+// Index e IndexMut
+// ========================
+
+impl<T, R: RngCore> Index<usize> for SkipListSeq<T, R> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index)
+            .expect("SkipListSeq::index: índice fora dos limites")
+    }
+}
+
+impl<T, R: RngCore> IndexMut<usize> for SkipListSeq<T, R> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index)
+            .expect("SkipListSeq::index_mut: índice fora dos limites")
+    }
+}
+
+impl<T> Default for SkipListSeq<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
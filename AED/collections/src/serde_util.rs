@@ -0,0 +1,31 @@
+//! Apoio à feature `serde`.
+//!
+//! Todas as coleções viram uma sequência de elementos (`[1, 2, 3]` em JSON):
+//!
+//! - sequências, na ordem dos índices;
+//! - pilhas, da base para o topo (empilhar na mesma ordem reconstrói a pilha);
+//! - filas, da frente para o fim (enfileirar na mesma ordem reconstrói a fila);
+//! - conjuntos, na ordem de `iter` (crescente nas árvores, a dos buckets nas
+//!   tabelas). Na volta, aceitam qualquer ordem e elementos repetidos; as
+//!   árvores são reconstruídas balanceadas (ver `set::serde_impl`).
+//!
+//! A desserialização lê primeiro um `Vec<T>` e monta a coleção a partir
+//! dele: os conjuntos ordenados precisam do vetor inteiro de qualquer jeito,
+//! e o resto fica mais simples.
+
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+/// Serializa `len` elementos de `iter` como sequência. Informar o tamanho
+/// de antemão ajuda formatos binários, que gravam o comprimento primeiro.
+pub(crate) fn serialize_iter<'a, T, I, S>(len: usize, iter: I, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + 'a,
+    I: IntoIterator<Item = &'a T>,
+    S: Serializer,
+{
+    let mut seq = serializer.serialize_seq(Some(len))?;
+    for value in iter {
+        seq.serialize_element(value)?;
+    }
+    seq.end()
+}
@@ -0,0 +1,195 @@
+//! Operações de conjunto (união, interseção, diferença, ...).
+//!
+//! Há duas estratégias:
+//! - por busca (probe): percorre um conjunto e pergunta `contains` ao outro.
+//!   Funciona com quaisquer dois backends; custa O(n · custo de `contains`).
+//! - por intercalação (merge): quando os dois conjuntos percorrem os elementos
+//!   em ordem crescente (árvores), anda nos dois iteradores ao mesmo tempo,
+//!   como no merge do mergesort. Custa O(n + m).
+//!
+//! Os métodos de `Set` escolhem a estratégia olhando `Set::sorted_by`.
+
+use std::cmp::Ordering;
+use std::iter::Peekable;
+
+use super::Set;
+use crate::metrics::{self, Counter};
+
+type BoxIter<'a, T> = Box<dyn Iterator<Item = &'a T> + 'a>;
+
+/// Função de comparação de um conjunto ordenado.
+pub type CmpFn<T> = fn(&T, &T) -> Ordering;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Op {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+}
+
+impl Op {
+    /// Elemento só no primeiro conjunto entra no resultado?
+    fn keeps_left_only(self) -> bool {
+        matches!(self, Op::Union | Op::Difference | Op::SymmetricDifference)
+    }
+
+    /// Elemento só no segundo conjunto entra no resultado?
+    fn keeps_right_only(self) -> bool {
+        matches!(self, Op::Union | Op::SymmetricDifference)
+    }
+
+    /// Elemento nos dois conjuntos entra no resultado?
+    fn keeps_both(self) -> bool {
+        matches!(self, Op::Union | Op::Intersection)
+    }
+}
+
+// ========================
+// Versão por intercalação
+// ========================
+
+/// Intercala dois iteradores em ordem crescente aplicando `op`.
+pub(crate) struct Merge<'a, T> {
+    a: Peekable<BoxIter<'a, T>>,
+    b: Peekable<BoxIter<'a, T>>,
+    cmp: CmpFn<T>,
+    op: Op,
+}
+
+impl<'a, T> Merge<'a, T> {
+    pub(crate) fn new(a: BoxIter<'a, T>, b: BoxIter<'a, T>, cmp: CmpFn<T>, op: Op) -> Self {
+        Self {
+            a: a.peekable(),
+            b: b.peekable(),
+            cmp,
+            op,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Merge<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let order = match (self.a.peek(), self.b.peek()) {
+                (None, None) => return None,
+                (Some(_), None) => {
+                    return if self.op.keeps_left_only() { self.a.next() } else { None };
+                }
+                (None, Some(_)) => {
+                    return if self.op.keeps_right_only() { self.b.next() } else { None };
+                }
+                (Some(x), Some(y)) => {
+                    metrics::record(Counter::Comparisons);
+                    (self.cmp)(x, y)
+                }
+            };
+
+            match order {
+                Ordering::Less => {
+                    let x = self.a.next();
+                    if self.op.keeps_left_only() {
+                        return x;
+                    }
+                }
+                Ordering::Greater => {
+                    let y = self.b.next();
+                    if self.op.keeps_right_only() {
+                        return y;
+                    }
+                }
+                Ordering::Equal => {
+                    let x = self.a.next();
+                    self.b.next();
+                    if self.op.keeps_both() {
+                        return x;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Todo elemento de `a` está em `b`? (ambos em ordem crescente)
+pub(crate) fn merge_is_subset<'a, T: 'a>(a: BoxIter<'a, T>, b: BoxIter<'a, T>, cmp: CmpFn<T>) -> bool {
+    let mut b = b.peekable();
+
+    for x in a {
+        loop {
+            let Some(y) = b.peek() else {
+                return false;
+            };
+
+            metrics::record(Counter::Comparisons);
+            match cmp(x, y) {
+                // `x` é menor que tudo que resta em `b`: não está lá
+                Ordering::Less => return false,
+                Ordering::Greater => {
+                    b.next();
+                }
+                Ordering::Equal => {
+                    b.next();
+                    break;
+                }
+            }
+        }
+    }
+
+    true
+}
+
+/// Nenhum elemento em comum? (ambos em ordem crescente)
+pub(crate) fn merge_is_disjoint<'a, T: 'a>(a: BoxIter<'a, T>, b: BoxIter<'a, T>, cmp: CmpFn<T>) -> bool {
+    Merge::new(a, b, cmp, Op::Intersection).next().is_none()
+}
+
+// ========================
+// Versão por busca
+// ========================
+
+pub(crate) fn probe<'a, T, A, B>(a: &'a A, b: &'a B, op: Op) -> BoxIter<'a, T>
+where
+    T: 'a,
+    A: Set<T> + ?Sized,
+    B: Set<T> + ?Sized,
+{
+    match op {
+        Op::Union => Box::new(a.iter().chain(b.iter().filter(move |x| !a.contains(x)))),
+        Op::Intersection => {
+            // percorre o menor e busca no maior
+            if a.len() <= b.len() {
+                Box::new(a.iter().filter(move |x| b.contains(x)))
+            } else {
+                Box::new(b.iter().filter(move |x| a.contains(x)))
+            }
+        }
+        Op::Difference => Box::new(a.iter().filter(move |x| !b.contains(x))),
+        Op::SymmetricDifference => Box::new(
+            a.iter()
+                .filter(move |x| !b.contains(x))
+                .chain(b.iter().filter(move |x| !a.contains(x))),
+        ),
+    }
+}
+
+pub(crate) fn probe_is_subset<T, A, B>(a: &A, b: &B) -> bool
+where
+    A: Set<T> + ?Sized,
+    B: Set<T> + ?Sized,
+{
+    a.len() <= b.len() && a.iter().all(|x| b.contains(x))
+}
+
+pub(crate) fn probe_is_disjoint<T, A, B>(a: &A, b: &B) -> bool
+where
+    A: Set<T> + ?Sized,
+    B: Set<T> + ?Sized,
+{
+    if a.len() <= b.len() {
+        !a.iter().any(|x| b.contains(x))
+    } else {
+        !b.iter().any(|x| a.contains(x))
+    }
+}
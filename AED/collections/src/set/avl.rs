@@ -0,0 +1,151 @@
+//! Rebalanceamento de árvores AVL.
+//!
+//! `BstAvlSet` guarda os filhos em `Box` e `PersistentAvlSet` em `Rc`, mas
+//! as rotações e o rebalanceamento são os mesmos. A única diferença é como
+//! se consegue um `&mut` para um nó: pelo `Box` é direto; pelo `Rc` passa
+//! por `Rc::make_mut`, que copia o nó se outra versão também o enxerga.
+//! Cada árvore implementa `AvlNode` para o seu nó, e o ponteiro dos filhos
+//! (`AvlLink`) diz como alterá-lo.
+
+use crate::metrics::{self, Counter};
+use std::rc::Rc;
+
+/// Ponteiro para um filho.
+pub(crate) trait AvlLink: Sized {
+    type Node: AvlNode<Link = Self>;
+
+    fn node(&self) -> &Self::Node;
+    /// Acesso para alterar o nó (copiando-o antes, se for compartilhado).
+    fn node_mut(&mut self) -> &mut Self::Node;
+}
+
+/// Nó de árvore AVL: altura guardada e dois filhos.
+pub(crate) trait AvlNode: Sized {
+    type Link: AvlLink<Node = Self>;
+
+    fn height(&self) -> i32;
+    fn set_height(&mut self, height: i32);
+    fn left_link(&self) -> &Option<Self::Link>;
+    fn right_link(&self) -> &Option<Self::Link>;
+    fn left_link_mut(&mut self) -> &mut Option<Self::Link>;
+    fn right_link_mut(&mut self) -> &mut Option<Self::Link>;
+}
+
+impl<N: AvlNode<Link = Box<N>>> AvlLink for Box<N> {
+    type Node = N;
+
+    fn node(&self) -> &N {
+        self
+    }
+
+    fn node_mut(&mut self) -> &mut N {
+        self
+    }
+}
+
+impl<N: AvlNode<Link = Rc<N>> + Clone> AvlLink for Rc<N> {
+    type Node = N;
+
+    fn node(&self) -> &N {
+        self
+    }
+
+    fn node_mut(&mut self) -> &mut N {
+        Rc::make_mut(self)
+    }
+}
+
+fn height<L: AvlLink>(link: &Option<L>) -> i32 {
+    match link {
+        Some(link) => link.node().height(),
+        None => 0,
+    }
+}
+
+pub(crate) fn update_height<N: AvlNode>(node: &mut N) {
+    let hl = height(node.left_link());
+    let hr = height(node.right_link());
+    node.set_height(1 + hl.max(hr));
+}
+
+pub(crate) fn balance_factor<N: AvlNode>(node: &N) -> i32 {
+    height(node.left_link()) - height(node.right_link())
+}
+
+/// Rotação simples à direita em `root`
+fn rotate_right<L: AvlLink>(root: &mut Option<L>) {
+    metrics::record(Counter::Rotations);
+    let mut x = root.take().expect("rotate_right: root vazio");
+    let xm = x.node_mut();
+    let mut y = xm
+        .left_link_mut()
+        .take()
+        .expect("rotate_right: nó sem filho esquerdo");
+    let ym = y.node_mut();
+
+    // y.right sobe para x.left
+    *xm.left_link_mut() = ym.right_link_mut().take();
+
+    // atualiza alturas de baixo pra cima: x primeiro, y depois de
+    // receber x como filho
+    update_height(xm);
+
+    // x vira filho direito de y
+    *ym.right_link_mut() = Some(x);
+    update_height(ym);
+    *root = Some(y);
+}
+
+/// Rotação simples à esquerda em `root`
+fn rotate_left<L: AvlLink>(root: &mut Option<L>) {
+    metrics::record(Counter::Rotations);
+    let mut x = root.take().expect("rotate_left: root vazio");
+    let xm = x.node_mut();
+    let mut y = xm
+        .right_link_mut()
+        .take()
+        .expect("rotate_left: nó sem filho direito");
+    let ym = y.node_mut();
+
+    // y.left sobe para x.right
+    *xm.right_link_mut() = ym.left_link_mut().take();
+
+    // atualiza alturas (mesma ordem da rotação à direita)
+    update_height(xm);
+
+    // x vira filho esquerdo de y
+    *ym.left_link_mut() = Some(x);
+    update_height(ym);
+    *root = Some(y);
+}
+
+/// Rebalanceia a subárvore enraizada em `root`,
+/// assumindo que as alturas dos filhos estão corretas.
+pub(crate) fn rebalance<L: AvlLink>(root: &mut Option<L>) {
+    if let Some(link) = root.as_mut() {
+        let node = link.node_mut();
+        update_height(node);
+        let bf = balance_factor(node);
+
+        // árvore pesada à esquerda
+        if bf > 1 {
+            if let Some(left) = node.left_link().as_ref() {
+                // caso LR (Left-Right): primeiro rotaciona filho para a esquerda
+                if balance_factor(left.node()) < 0 {
+                    rotate_left(node.left_link_mut());
+                }
+            }
+            rotate_right(root);
+        }
+        // árvore pesada à direita
+        else if bf < -1 {
+            if let Some(right) = node.right_link().as_ref() {
+                // caso RL (Right-Left): primeiro rotaciona filho para a direita
+                if balance_factor(right.node()) > 0 {
+                    rotate_right(node.right_link_mut());
+                }
+            }
+            rotate_left(root);
+        }
+    }
+}
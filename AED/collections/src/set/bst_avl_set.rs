@@ -1,4 +1,13 @@
-use crate::set::Set;
+use crate::metrics;
+use crate::set::avl::{balance_factor, rebalance, update_height, AvlNode};
+use crate::set::bst_nav::{self, BstNode, InOrder, InsertPath, IntoInOrder, OwnedBstNode};
+use crate::set::comparator::{self, Comparator, NaturalOrder};
+use crate::set::{bulk, dot, invariants};
+use crate::set::{CmpFn, InvariantError, Lookup, OrderedSet, Set, UnsortedError};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::RangeBounds;
 
 type Link<T> = Option<Box<Node<T>>>;
 
@@ -12,115 +21,89 @@ struct Node<T> {
 /// Conjunto implementado como árvore AVL.
 /// Não permite elementos repetidos.
 /// Operações são O(log n) em média.
-pub struct BstAvlSet<T> {
+///
+/// A ordem vem de `C` (ver `set::comparator`); o padrão é o `Ord` de `T`.
+pub struct BstAvlSet<T, C = NaturalOrder> {
     root: Link<T>,
     len: usize,
+    cmp: C,
 }
 
 impl<T> BstAvlSet<T> {
     pub fn new() -> Self {
-        Self { root: None, len: 0 }
+        Self::with_comparator(NaturalOrder)
     }
+}
 
-    pub fn len(&self) -> usize {
-        self.len
+impl<T, C> BstAvlSet<T, C> {
+    /// Conjunto vazio ordenado por `cmp`.
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            root: None,
+            len: 0,
+            cmp,
+        }
     }
-}
 
-// ========================
-// Helpers de AVL
-// ========================
+    /// A ordem do conjunto.
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
 
-fn height<T>(link: &Link<T>) -> i32 {
-    match link {
-        Some(node) => node.height,
-        None => 0,
+    pub fn len(&self) -> usize {
+        self.len
     }
-}
 
-fn update_height<T>(node: &mut Box<Node<T>>) {
-    let hl = height(&node.left);
-    let hr = height(&node.right);
-    node.height = 1 + hl.max(hr);
-}
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 
-fn balance_factor<T>(node: &Box<Node<T>>) -> i32 {
-    height(&node.left) - height(&node.right)
+    /// Itera em ordem crescente.
+    pub fn iter(&self) -> BstAvlSetIter<'_, T> {
+        BstAvlSetIter {
+            inner: InOrder::new(self.root.as_deref()),
+        }
+    }
 }
 
-/// Rotação simples à direita em `root`
-fn rotate_right<T>(root: &mut Link<T>) {
-    let mut x = root.take().expect("rotate_right: root vazio");
-    let mut y = x
-        .left
-        .take()
-        .expect("rotate_right: nó sem filho esquerdo");
-
-    // y.right sobe para x.left
-    x.left = y.right.take();
+// ========================
+// Helpers de AVL
+// ========================
+//
+// Rotações e rebalanceamento ficam em `set::avl`, compartilhados com o
+// `PersistentAvlSet`.
 
-    // atualiza alturas de baixo pra cima
-    update_height(&mut x);
-    update_height(&mut y);
+impl<T> AvlNode for Node<T> {
+    type Link = Box<Node<T>>;
 
-    // x vira filho direito de y
-    y.right = Some(x);
-    *root = Some(y);
-}
-
-/// Rotação simples à esquerda em `root`
-fn rotate_left<T>(root: &mut Link<T>) {
-    let mut x = root.take().expect("rotate_left: root vazio");
-    let mut y = x
-        .right
-        .take()
-        .expect("rotate_left: nó sem filho direito");
+    fn height(&self) -> i32 {
+        self.height
+    }
 
-    // y.left sobe para x.right
-    x.right = y.left.take();
+    fn set_height(&mut self, height: i32) {
+        self.height = height;
+    }
 
-    // atualiza alturas
-    update_height(&mut x);
-    update_height(&mut y);
+    fn left_link(&self) -> &Link<T> {
+        &self.left
+    }
 
-    // x vira filho esquerdo de y
-    y.left = Some(x);
-    *root = Some(y);
-}
+    fn right_link(&self) -> &Link<T> {
+        &self.right
+    }
 
-/// Rebalanceia a subárvore enraizada em `root`,
-/// assumindo que as alturas dos filhos estão corretas.
-fn rebalance<T>(root: &mut Link<T>) {
-    if let Some(node) = root.as_mut() {
-        update_height(node);
-        let bf = balance_factor(node);
+    fn left_link_mut(&mut self) -> &mut Link<T> {
+        &mut self.left
+    }
 
-        // árvore pesada à esquerda
-        if bf > 1 {
-            if let Some(left) = node.left.as_ref() {
-                // caso LR (Left-Right): primeiro rotaciona filho para a esquerda
-                if balance_factor(left) < 0 {
-                    rotate_left(&mut node.left);
-                }
-            }
-            rotate_right(root);
-        }
-        // árvore pesada à direita
-        else if bf < -1 {
-            if let Some(right) = node.right.as_ref() {
-                // caso RL (Right-Left): primeiro rotaciona filho para a direita
-                if balance_factor(right) > 0 {
-                    rotate_right(&mut node.right);
-                }
-            }
-            rotate_left(root);
-        }
+    fn right_link_mut(&mut self) -> &mut Link<T> {
+        &mut self.right
     }
 }
 
 // Remove o nó com menor elemento da subárvore `link`
 // e devolve esse nó (como Box<Node<T>>).
-fn take_min_node<T: Ord>(link: &mut Link<T>) -> Box<Node<T>> {
+fn take_min_node<T>(link: &mut Link<T>) -> Box<Node<T>> {
     // pré-condição: link é Some
     if link.as_ref().unwrap().left.is_none() {
         // este é o mínimo
@@ -140,11 +123,27 @@ fn take_min_node<T: Ord>(link: &mut Link<T>) -> Box<Node<T>> {
     }
 }
 
+// Espelho de `take_min_node`: remove o nó com maior elemento.
+fn take_max_node<T>(link: &mut Link<T>) -> Box<Node<T>> {
+    if link.as_ref().unwrap().right.is_none() {
+        let mut boxed = link.take().unwrap();
+        // o filho esquerdo sobe
+        *link = boxed.left.take();
+        boxed
+    } else {
+        let max_node = {
+            let right_link = &mut link.as_mut().unwrap().right;
+            take_max_node(right_link)
+        };
+        rebalance(link);
+        max_node
+    }
+}
+
 // Remove a raiz da subárvore apontada por `link`
-// (sabendo que ela EXISTE) e ajusta `len`.
-fn remove_at_root<T: Ord>(link: &mut Link<T>, len: &mut usize) {
+// (sabendo que ela EXISTE) e devolve o elemento removido.
+fn remove_at_root<T>(link: &mut Link<T>) -> T {
     let mut node = link.take().unwrap();
-    *len -= 1;
 
     match (node.left.take(), node.right.take()) {
         (None, None) => {
@@ -169,7 +168,7 @@ fn remove_at_root<T: Ord>(link: &mut Link<T>, len: &mut usize) {
             min_node.left = Some(left);
             min_node.right = right_link;
 
-            update_height(&mut min_node);
+            update_height(&mut *min_node);
             *link = Some(min_node);
         }
     }
@@ -177,31 +176,151 @@ fn remove_at_root<T: Ord>(link: &mut Link<T>, len: &mut usize) {
     if link.is_some() {
         rebalance(link);
     }
+
+    node.elem
+}
+
+impl<T> BstNode<T> for Node<T> {
+    fn elem(&self) -> &T {
+        &self.elem
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+impl<T> OwnedBstNode<T> for Node<T> {
+    fn take_left(&mut self) -> Option<Box<Self>> {
+        self.left.take()
+    }
+
+    fn take_right(&mut self) -> Option<Box<Self>> {
+        self.right.take()
+    }
+
+    fn into_elem(self) -> T {
+        self.elem
+    }
 }
 
 // ========================
-// Implementação de Set<T>
+// Busca / remoção por comparação (usadas também pelo `AvlMap`)
 // ========================
+//
+// `cmp(elem)` diz onde o alvo está em relação a `elem`:
+// `Less` → à esquerda, `Greater` → à direita, `Equal` → achou.
 
-impl<T: Ord> Set<T> for BstAvlSet<T> {
-    fn insert(&mut self, value: T) -> bool {
-        fn insert_rec<T: Ord>(link: &mut Link<T>, value: T, len: &mut usize) -> bool {
+impl<T, C> BstAvlSet<T, C> {
+    pub(crate) fn find_by(&self, cmp: impl Fn(&T) -> Ordering) -> Option<&T> {
+        let cmp = metrics::counted_cmp(cmp);
+        let mut cur = &self.root;
+
+        while let Some(node) = cur {
+            match cmp(&node.elem) {
+                Ordering::Less => cur = &node.left,
+                Ordering::Greater => cur = &node.right,
+                Ordering::Equal => return Some(&node.elem),
+            }
+        }
+
+        None
+    }
+
+    pub(crate) fn find_by_mut(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<&mut T> {
+        find_mut_in(&mut self.root, metrics::counted_cmp(cmp))
+    }
+
+    pub(crate) fn remove_by(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+        let removed = remove_in(&mut self.root, cmp);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+}
+
+// desce até o nó que `cmp` aponta; não conta comparações (quem chama decide).
+fn find_mut_in<T>(link: &mut Link<T>, cmp: impl Fn(&T) -> Ordering) -> Option<&mut T> {
+    let mut cur = link;
+
+    while let Some(node) = cur {
+        match cmp(&node.elem) {
+            Ordering::Less => cur = &mut node.left,
+            Ordering::Greater => cur = &mut node.right,
+            Ordering::Equal => return Some(&mut node.elem),
+        }
+    }
+
+    None
+}
+
+// desce até o nó que `cmp` aponta, remove e rebalanceia na volta.
+fn remove_in<T>(link: &mut Link<T>, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+    fn remove_rec<T>(link: &mut Link<T>, cmp: &impl Fn(&T) -> Ordering) -> Option<T> {
+        let node = link.as_mut()?;
+
+        let removed = match cmp(&node.elem) {
+            Ordering::Less => remove_rec(&mut node.left, cmp),
+            Ordering::Greater => remove_rec(&mut node.right, cmp),
+            // achamos o nó a remover na raiz desta subárvore
+            Ordering::Equal => return Some(remove_at_root(link)),
+        };
+
+        if removed.is_some() {
+            rebalance(link);
+        }
+        removed
+    }
+
+    remove_rec(link, &metrics::counted_cmp(cmp))
+}
+
+impl<T, C: Comparator<T>> BstAvlSet<T, C> {
+    /// Insere o elemento se ele ainda não existe.
+    /// Devolve uma referência para o elemento recém-inserido, ou `None` se já existia.
+    pub(crate) fn insert_new(&mut self, value: T) -> Option<&mut T> {
+        let mut path = InsertPath::new();
+        if !self.insert_with_path(value, &mut path) {
+            return None;
+        }
+        // as rotações mudaram o caminho: desce de novo (ver `InsertPath`)
+        find_mut_in(&mut self.root, |x| path.direction(x))
+    }
+
+    /// Insere o elemento se ele ainda não existe. Devolve `true` se inseriu.
+    fn insert_value(&mut self, value: T) -> bool {
+        self.insert_with_path(value, &mut InsertPath::untracked())
+    }
+
+    fn insert_with_path(&mut self, value: T, path: &mut InsertPath<T>) -> bool {
+        // devolve se inseriu; `path` anota as comparações da descida
+        fn insert_rec<T, C: Comparator<T>>(
+            link: &mut Link<T>,
+            value: T,
+            cmp: &C,
+            path: &mut InsertPath<T>,
+        ) -> bool {
             match link {
                 Some(node) => {
-                    let changed = if value < node.elem {
-                        insert_rec(&mut node.left, value, len)
-                    } else if value > node.elem {
-                        insert_rec(&mut node.right, value, len)
-                    } else {
+                    let ord = comparator::counted(cmp, &value, &node.elem);
+                    path.record(&node.elem, ord);
+                    let inserted = match ord {
+                        Ordering::Less => insert_rec(&mut node.left, value, cmp, path),
+                        Ordering::Greater => insert_rec(&mut node.right, value, cmp, path),
                         // já existe, não insere
-                        false
+                        Ordering::Equal => false,
                     };
 
-                    if changed {
+                    if inserted {
                         rebalance(link);
                     }
 
-                    changed
+                    inserted
                 }
                 None => {
                     *link = Some(Box::new(Node {
@@ -210,68 +329,324 @@ impl<T: Ord> Set<T> for BstAvlSet<T> {
                         right: None,
                         height: 1,
                     }));
-                    *len += 1;
                     true
                 }
             }
         }
 
-        insert_rec(&mut self.root, value, &mut self.len)
+        let inserted = insert_rec(&mut self.root, value, &self.cmp, path);
+        if inserted {
+            self.len += 1;
+        }
+        inserted
     }
 
-    fn remove(&mut self, value: &T) -> bool {
-        fn remove_rec<T: Ord>(link: &mut Link<T>, value: &T, len: &mut usize) -> bool {
-            match link {
-                None => false,
-                Some(node) => {
-                    if value < &node.elem {
-                        let removed = remove_rec(&mut node.left, value, len);
-                        if removed {
-                            rebalance(link);
-                        }
-                        removed
-                    } else if value > &node.elem {
-                        let removed = remove_rec(&mut node.right, value, len);
-                        if removed {
-                            rebalance(link);
-                        }
-                        removed
-                    } else {
-                        // achamos o nó a remover na raiz desta subárvore
-                        remove_at_root(link, len);
-                        true
-                    }
-                }
-            }
+    /// Elemento equivalente a `value` na ordem do comparador.
+    fn get_equivalent(&self, value: &T) -> Option<&T> {
+        self.find_by(|x| self.cmp.compare(value, x))
+    }
+
+    /// Remove o elemento equivalente a `value` na ordem do comparador.
+    fn take_equivalent(&mut self, value: &T) -> Option<T> {
+        let cmp = &self.cmp;
+        let removed = remove_in(&mut self.root, |x| cmp.compare(value, x));
+        if removed.is_some() {
+            self.len -= 1;
         }
+        removed
+    }
+}
+
+// ========================
+// Construção a partir de entrada ordenada
+// ========================
 
-        remove_rec(&mut self.root, value, &mut self.len)
+impl<T: Ord> BstAvlSet<T> {
+    /// Monta a árvore a partir de elementos em ordem estritamente crescente,
+    /// em O(n) e sem nenhuma rotação: a mediana vira a raiz e cada metade
+    /// vira uma subárvore, recursivamente. As duas metades diferem em no
+    /// máximo um elemento, então as alturas diferem em no máximo um.
+    ///
+    /// Devolve `UnsortedError` se a entrada estiver fora de ordem ou tiver
+    /// repetidos.
+    pub fn from_sorted_iter<I>(iter: I) -> Result<Self, UnsortedError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::from_sorted_iter_by(iter, NaturalOrder)
     }
+}
 
-    fn contains(&self, value: &T) -> bool {
-        let mut cur = &self.root;
+impl<T, C: Comparator<T>> BstAvlSet<T, C> {
+    /// `from_sorted_iter` na ordem de `cmp`: a entrada precisa ser
+    /// estritamente crescente segundo o comparador.
+    pub fn from_sorted_iter_by<I>(iter: I, cmp: C) -> Result<Self, UnsortedError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let values = bulk::collect_sorted(iter, &cmp)?;
+        let len = values.len();
+        let root = build(len, &mut values.into_iter());
+        Ok(Self { root, len, cmp })
+    }
+}
 
-        while let Some(node) = cur {
-            if *value < node.elem {
-                cur = &node.left;
-            } else if *value > node.elem {
-                cur = &node.right;
-            } else {
-                return true;
+// monta a subárvore com os próximos `n` elementos de `it`, em ordem
+fn build<T>(n: usize, it: &mut impl Iterator<Item = T>) -> Link<T> {
+    if n == 0 {
+        return None;
+    }
+    let left = build(n / 2, it);
+    let elem = it.next().expect("from_sorted_iter: faltou elemento");
+    let right = build(n - 1 - n / 2, it);
+
+    let mut node = Box::new(Node {
+        elem,
+        left,
+        right,
+        height: 0,
+    });
+    update_height(&mut *node);
+    Some(node)
+}
+
+// ========================
+// Invariantes
+// ========================
+
+impl<T, C: Comparator<T>> BstAvlSet<T, C> {
+    /// Confere a estrutura da árvore: ordem de BST (a do comparador), `len`, alturas
+    /// guardadas nos nós e fator de balanceamento em [-1, 1].
+    /// Devolve a primeira regra quebrada. Custa O(n); pensado para testes.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        // devolve a altura real da subárvore
+        fn check_rec<T>(link: &Link<T>) -> Result<i32, InvariantError> {
+            let Some(node) = link else {
+                return Ok(0);
+            };
+
+            let hl = check_rec(&node.left)?;
+            let hr = check_rec(&node.right)?;
+            let actual = 1 + hl.max(hr);
+
+            if node.height != actual {
+                return Err(InvariantError::StaleHeight {
+                    stored: node.height,
+                    actual,
+                });
             }
+            if (hl - hr).abs() > 1 {
+                return Err(InvariantError::Unbalanced { balance: hl - hr });
+            }
+
+            Ok(actual)
         }
 
-        false
+        invariants::check_order_and_len_by(self.iter(), self.len, &self.cmp)?;
+        check_rec(&self.root)?;
+        Ok(())
+    }
+}
+
+// ========================
+// Graphviz
+// ========================
+
+impl<T: Display, C> BstAvlSet<T, C> {
+    /// Desenha a árvore em DOT (Graphviz), com um ponto para cada filho vazio.
+    /// Cada nó mostra a altura guardada (`h`) e o fator de balanceamento
+    /// (`bf` = altura(esq) - altura(dir)).
+    pub fn to_dot(&self) -> String {
+        dot::to_dot(
+            "BstAvlSet",
+            self.root.as_deref(),
+            |node| format!("h={} bf={}", node.height, balance_factor(node)),
+            |_| "",
+        )
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q> Lookup<T, Q> for BstAvlSet<T>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        self.find_by(|x| value.cmp(x.borrow()))
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        self.remove_by(|x| value.cmp(x.borrow()))
+    }
+}
+
+// ========================
+// Implementação de Set<T>
+// ========================
+
+impl<T, C: Comparator<T>> Set<T> for BstAvlSet<T, C> {
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_value(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take_equivalent(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get_equivalent(value).is_some()
     }
 
     fn len(&self) -> usize {
         self.len
     }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(BstAvlSet::iter(self))
+    }
+
+    fn sorted_by(&self) -> Option<CmpFn<T>> {
+        self.cmp.natural_cmp()
+    }
+}
+
+// ========================
+// Implementação de OrderedSet<T>
+// ========================
+
+impl<T, C: Comparator<T>> OrderedSet<T> for BstAvlSet<T, C> {
+    fn min(&self) -> Option<&T> {
+        bst_nav::min(self.root.as_deref())
+    }
+
+    fn max(&self) -> Option<&T> {
+        bst_nav::max(self.root.as_deref())
+    }
+
+    fn floor(&self, value: &T) -> Option<&T> {
+        bst_nav::floor_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn ceiling(&self, value: &T) -> Option<&T> {
+        bst_nav::ceiling_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn successor(&self, value: &T) -> Option<&T> {
+        bst_nav::successor_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn predecessor(&self, value: &T) -> Option<&T> {
+        bst_nav::predecessor_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.root.as_ref()?;
+        self.len -= 1;
+        Some(take_min_node(&mut self.root).elem)
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        self.root.as_ref()?;
+        self.len -= 1;
+        Some(take_max_node(&mut self.root).elem)
+    }
+
+    fn range<R: RangeBounds<T>>(&self, range: R) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(InOrder::range_by(
+            self.root.as_deref(),
+            range.start_bound(),
+            range.end_bound(),
+            &self.cmp,
+        ))
+    }
+}
+
+// ========================
+// Drop
+// ========================
+
+impl<T, C> Drop for BstAvlSet<T, C> {
+    fn drop(&mut self) {
+        bst_nav::drop_tree(self.root.take());
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+pub struct BstAvlSetIter<'a, T> {
+    inner: InOrder<'a, T, Node<T>>,
+}
+
+impl<'a, T> Iterator for BstAvlSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Iterador em ordem que consome o conjunto.
+pub struct BstAvlSetIntoIter<T> {
+    inner: IntoInOrder<T, Node<T>>,
+}
+
+impl<T> Iterator for BstAvlSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, C> IntoIterator for BstAvlSet<T, C> {
+    type Item = T;
+    type IntoIter = BstAvlSetIntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        BstAvlSetIntoIter {
+            inner: IntoInOrder::new(self.root.take(), self.len),
+        }
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a BstAvlSet<T, C> {
+    type Item = &'a T;
+    type IntoIter = BstAvlSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> FromIterator<T> for BstAvlSet<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::with_comparator(C::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for BstAvlSet<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
 }
 
-impl<T> crate::set::SetName for BstAvlSet<T> {
-    fn name() -> &'static str {
-        "BstAvlSet"
+impl<T, C> crate::set::SetName for BstAvlSet<T, C> {
+    fn name() -> String {
+        "BstAvlSet".to_string()
     }
 }
 
@@ -0,0 +1,392 @@
+//! Navegação em árvores binárias de busca.
+//!
+//! As três árvores (`BstSet`, `BstAvlSet`, `BstRBSet`) têm nós diferentes
+//! (com altura, com cor, ...), mas as consultas de ordem só olham para
+//! `elem`, `left` e `right`. Cada árvore implementa `BstNode` para o seu nó
+//! e reaproveita as funções daqui.
+
+use crate::set::comparator::{Comparator, NaturalOrder};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::ops::Bound;
+
+pub(crate) trait BstNode<T> {
+    fn elem(&self) -> &T;
+    fn left(&self) -> Option<&Self>;
+    fn right(&self) -> Option<&Self>;
+}
+
+/// Nó que pode ser desmontado, para iteradores que consomem a árvore.
+pub(crate) trait OwnedBstNode<T>: Sized {
+    fn take_left(&mut self) -> Option<Box<Self>>;
+    fn take_right(&mut self) -> Option<Box<Self>>;
+    fn into_elem(self) -> T;
+}
+
+pub(crate) fn min<T, N: BstNode<T>>(root: Option<&N>) -> Option<&T> {
+    let mut node = root?;
+    while let Some(left) = node.left() {
+        node = left;
+    }
+    Some(node.elem())
+}
+
+pub(crate) fn max<T, N: BstNode<T>>(root: Option<&N>) -> Option<&T> {
+    let mut node = root?;
+    while let Some(right) = node.right() {
+        node = right;
+    }
+    Some(node.elem())
+}
+
+/// Maior elemento `<= value`.
+pub(crate) fn floor<'a, T: Ord, N: BstNode<T>>(root: Option<&'a N>, value: &T) -> Option<&'a T> {
+    floor_by(root, value, &NaturalOrder)
+}
+
+/// Menor elemento `>= value`.
+pub(crate) fn ceiling<'a, T: Ord, N: BstNode<T>>(root: Option<&'a N>, value: &T) -> Option<&'a T> {
+    ceiling_by(root, value, &NaturalOrder)
+}
+
+/// Menor elemento estritamente `> value`.
+pub(crate) fn successor<'a, T: Ord, N: BstNode<T>>(root: Option<&'a N>, value: &T) -> Option<&'a T> {
+    successor_by(root, value, &NaturalOrder)
+}
+
+/// Maior elemento estritamente `< value`.
+pub(crate) fn predecessor<'a, T: Ord, N: BstNode<T>>(root: Option<&'a N>, value: &T) -> Option<&'a T> {
+    predecessor_by(root, value, &NaturalOrder)
+}
+
+// As versões `_by` recebem a ordem da árvore (ver `set::comparator`).
+
+/// `floor` na ordem de `cmp`.
+pub(crate) fn floor_by<'a, T, N, C>(root: Option<&'a N>, value: &T, cmp: &C) -> Option<&'a T>
+where
+    N: BstNode<T>,
+    C: Comparator<T>,
+{
+    let mut cur = root;
+    let mut best = None;
+
+    while let Some(node) = cur {
+        match cmp.compare(value, node.elem()) {
+            Ordering::Less => cur = node.left(),
+            Ordering::Greater => {
+                // candidato; pode haver um maior (ainda <= value) à direita
+                best = Some(node.elem());
+                cur = node.right();
+            }
+            Ordering::Equal => return Some(node.elem()),
+        }
+    }
+
+    best
+}
+
+/// `ceiling` na ordem de `cmp`.
+pub(crate) fn ceiling_by<'a, T, N, C>(root: Option<&'a N>, value: &T, cmp: &C) -> Option<&'a T>
+where
+    N: BstNode<T>,
+    C: Comparator<T>,
+{
+    let mut cur = root;
+    let mut best = None;
+
+    while let Some(node) = cur {
+        match cmp.compare(value, node.elem()) {
+            Ordering::Greater => cur = node.right(),
+            Ordering::Less => {
+                best = Some(node.elem());
+                cur = node.left();
+            }
+            Ordering::Equal => return Some(node.elem()),
+        }
+    }
+
+    best
+}
+
+/// `successor` na ordem de `cmp`.
+pub(crate) fn successor_by<'a, T, N, C>(root: Option<&'a N>, value: &T, cmp: &C) -> Option<&'a T>
+where
+    N: BstNode<T>,
+    C: Comparator<T>,
+{
+    let mut cur = root;
+    let mut best = None;
+
+    while let Some(node) = cur {
+        if cmp.compare(value, node.elem()) == Ordering::Less {
+            best = Some(node.elem());
+            cur = node.left();
+        } else {
+            cur = node.right();
+        }
+    }
+
+    best
+}
+
+/// `predecessor` na ordem de `cmp`.
+pub(crate) fn predecessor_by<'a, T, N, C>(root: Option<&'a N>, value: &T, cmp: &C) -> Option<&'a T>
+where
+    N: BstNode<T>,
+    C: Comparator<T>,
+{
+    let mut cur = root;
+    let mut best = None;
+
+    while let Some(node) = cur {
+        if cmp.compare(value, node.elem()) == Ordering::Greater {
+            best = Some(node.elem());
+            cur = node.right();
+        } else {
+            cur = node.left();
+        }
+    }
+
+    best
+}
+
+// ========================
+// Reencontrar o elemento inserido
+// ========================
+
+/// Comparações feitas na descida de uma inserção.
+///
+/// As árvores balanceadas não conseguem devolver `&mut` para o elemento
+/// novo direto da recursão: o rebalanceamento precisa de `&mut` nos
+/// ancestrais enquanto a referência estaria viva. Então a inserção só
+/// anota, para cada nó visitado na descida, o resultado de
+/// `cmp(novo, nó)`, e depois a árvore desce de novo seguindo as anotações,
+/// sem chamar o comparador (nem contar comparações).
+///
+/// As rotações não mudam a ordem entre dois elementos, e só mexem em nós
+/// do caminho da inserção: os nós do caminho final até o novo são os
+/// ancestrais anotados, e o único que não aparece aqui é o próprio novo.
+///
+/// Os nós são identificados pelo endereço do elemento (o conteúdo de um
+/// `Box` não muda de endereço quando o `Box` é movido), guardado numa
+/// tabela de hash: cada passo da segunda descida é O(1). Os endereços só
+/// são comparados, nunca desreferenciados.
+///
+/// `Set::insert` não precisa da referência: usa `InsertPath::untracked`,
+/// que não anota nada.
+pub(crate) struct InsertPath<T> {
+    steps: Option<HashMap<*const T, Ordering>>,
+}
+
+impl<T> InsertPath<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            steps: Some(HashMap::new()),
+        }
+    }
+
+    /// Caminho que não anota nada, para inserções que só devolvem `bool`.
+    pub(crate) fn untracked() -> Self {
+        Self { steps: None }
+    }
+
+    /// Anota `cmp(novo, elem)` para o nó de `elem`.
+    pub(crate) fn record(&mut self, elem: &T, ord: Ordering) {
+        if let Some(steps) = &mut self.steps {
+            steps.insert(elem, ord);
+        }
+    }
+
+    /// Para usar como `cmp` da segunda descida: direção anotada, ou
+    /// `Equal` no único nó que não estava no caminho (o novo).
+    pub(crate) fn direction(&self, elem: &T) -> Ordering {
+        self.steps
+            .as_ref()
+            .and_then(|steps| steps.get(&(elem as *const T)).copied())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+// ========================
+// Destruição sem recursão
+// ========================
+
+/// Libera a árvore sem recursão.
+///
+/// O `Drop` gerado pelo compilador desce a árvore recursivamente: numa BST
+/// degenerada (inserções em ordem) isso é uma recursão de profundidade n.
+/// Aqui cada nó tem os filhos desligados antes de ser liberado, e os filhos
+/// vão para uma pilha explícita. A pilha guarda no máximo um irmão pendente
+/// por nível, então cresce com a altura da árvore, não com n.
+pub(crate) fn drop_tree<T, N: OwnedBstNode<T>>(root: Option<Box<N>>) {
+    let mut stack: Vec<Box<N>> = root.into_iter().collect();
+
+    while let Some(mut node) = stack.pop() {
+        stack.extend(node.take_left());
+        stack.extend(node.take_right());
+        // `node` sai de escopo sem filhos
+    }
+}
+
+// ========================
+// Iterador em ordem (com limites)
+// ========================
+
+/// Percurso em ordem usando uma pilha explícita.
+///
+/// A pilha guarda o "caminho à esquerda" ainda não visitado.
+/// O limite superior é resolvido na construção: guardamos uma referência
+/// para o último elemento do intervalo e paramos logo depois de devolvê-lo.
+pub(crate) struct InOrder<'a, T, N> {
+    stack: Vec<&'a N>,
+    last: Option<&'a T>,
+}
+
+impl<'a, T, N: BstNode<T>> InOrder<'a, T, N> {
+    /// Percurso completo (sem limites).
+    pub(crate) fn new(root: Option<&'a N>) -> Self {
+        let mut stack = Vec::new();
+        let mut cur = root;
+        while let Some(node) = cur {
+            stack.push(node);
+            cur = node.left();
+        }
+
+        Self { stack, last: None }
+    }
+}
+
+impl<'a, T: Ord, N: BstNode<T>> InOrder<'a, T, N> {
+    pub(crate) fn range(root: Option<&'a N>, start: Bound<&T>, end: Bound<&T>) -> Self {
+        Self::range_by(root, start, end, &NaturalOrder)
+    }
+}
+
+impl<'a, T, N: BstNode<T>> InOrder<'a, T, N> {
+    /// Percurso dos elementos entre `start` e `end`, na ordem de `cmp`.
+    pub(crate) fn range_by<C: Comparator<T>>(
+        root: Option<&'a N>,
+        start: Bound<&T>,
+        end: Bound<&T>,
+        cmp: &C,
+    ) -> Self {
+        let last = match end {
+            Bound::Included(hi) => floor_by(root, hi, cmp),
+            Bound::Excluded(hi) => predecessor_by(root, hi, cmp),
+            Bound::Unbounded => max(root),
+        };
+
+        let mut stack = Vec::new();
+        let mut cur = root;
+
+        // desce até o primeiro elemento dentro do limite inferior,
+        // empilhando os nós que ainda serão visitados
+        while let Some(node) = cur {
+            let inside = match start {
+                Bound::Included(lo) => cmp.compare(node.elem(), lo) != Ordering::Less,
+                Bound::Excluded(lo) => cmp.compare(node.elem(), lo) == Ordering::Greater,
+                Bound::Unbounded => true,
+            };
+
+            if inside {
+                stack.push(node);
+                cur = node.left();
+            } else {
+                cur = node.right();
+            }
+        }
+
+        let mut iter = Self { stack, last };
+
+        // intervalo vazio (ex.: 5..3): o primeiro elemento já passa do último
+        let empty = match (iter.stack.last(), iter.last) {
+            (Some(first), Some(last)) => cmp.compare(first.elem(), last) == Ordering::Greater,
+            _ => true,
+        };
+        if empty {
+            iter.stack.clear();
+        }
+
+        iter
+    }
+}
+
+impl<'a, T, N: BstNode<T>> Iterator for InOrder<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+
+        if self.last.is_some_and(|last| std::ptr::eq(last, node.elem())) {
+            // último elemento do intervalo: nada mais a visitar
+            self.stack.clear();
+        } else {
+            // sucessor: mínimo da subárvore direita
+            let mut cur = node.right();
+            while let Some(n) = cur {
+                self.stack.push(n);
+                cur = n.left();
+            }
+        }
+
+        Some(node.elem())
+    }
+}
+
+// ========================
+// Iterador em ordem que consome a árvore
+// ========================
+
+/// Mesmo percurso do `InOrder`, mas a pilha é dona dos nós:
+/// cada nó é desmontado quando visitado e o elemento é movido para fora.
+pub(crate) struct IntoInOrder<T, N: OwnedBstNode<T>> {
+    stack: Vec<Box<N>>,
+    remaining: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T, N: OwnedBstNode<T>> IntoInOrder<T, N> {
+    pub(crate) fn new(root: Option<Box<N>>, len: usize) -> Self {
+        let mut iter = Self {
+            stack: Vec::new(),
+            remaining: len,
+            _marker: PhantomData,
+        };
+        iter.push_left_spine(root);
+        iter
+    }
+
+    fn push_left_spine(&mut self, mut link: Option<Box<N>>) {
+        while let Some(mut node) = link {
+            link = node.take_left();
+            self.stack.push(node);
+        }
+    }
+}
+
+// iterador largado no meio: os nós da pilha ainda carregam as
+// subárvores direitas
+impl<T, N: OwnedBstNode<T>> Drop for IntoInOrder<T, N> {
+    fn drop(&mut self) {
+        while let Some(node) = self.stack.pop() {
+            drop_tree(Some(node));
+        }
+    }
+}
+
+impl<T, N: OwnedBstNode<T>> Iterator for IntoInOrder<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut node = self.stack.pop()?;
+        let right = node.take_right();
+        self.push_left_spine(right);
+        self.remaining -= 1;
+        Some(node.into_elem())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
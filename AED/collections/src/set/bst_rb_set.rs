@@ -1,4 +1,12 @@
-use crate::set::Set;
+use crate::metrics::{self, Counter};
+use crate::set::bst_nav::{self, BstNode, InOrder, InsertPath, IntoInOrder, OwnedBstNode};
+use crate::set::comparator::{self, Comparator, NaturalOrder};
+use crate::set::{bulk, dot, invariants};
+use crate::set::{CmpFn, InvariantError, Lookup, OrderedSet, Set, UnsortedError};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::RangeBounds;
 
 type Link<T> = Option<Box<Node<T>>>;
 
@@ -18,14 +26,33 @@ struct Node<T> {
 /// Conjunto implementado como red-black tree left-leaning (Sedgewick).
 /// Não permite elementos repetidos.
 /// Operações são O(log n).
-pub struct BstRBSet<T> {
+///
+/// A ordem vem de `C` (ver `set::comparator`); o padrão é o `Ord` de `T`.
+pub struct BstRBSet<T, C = NaturalOrder> {
     root: Link<T>,
     len: usize,
+    cmp: C,
 }
 
 impl<T> BstRBSet<T> {
     pub fn new() -> Self {
-        Self { root: None, len: 0 }
+        Self::with_comparator(NaturalOrder)
+    }
+}
+
+impl<T, C> BstRBSet<T, C> {
+    /// Conjunto vazio ordenado por `cmp`.
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            root: None,
+            len: 0,
+            cmp,
+        }
+    }
+
+    /// A ordem do conjunto.
+    pub fn comparator(&self) -> &C {
+        &self.cmp
     }
 
     pub fn len(&self) -> usize {
@@ -35,6 +62,13 @@ impl<T> BstRBSet<T> {
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
+
+    /// Itera em ordem crescente.
+    pub fn iter(&self) -> BstRBSetIter<'_, T> {
+        BstRBSetIter {
+            inner: InOrder::new(self.root.as_deref()),
+        }
+    }
 }
 
 // ========================
@@ -48,6 +82,7 @@ fn is_red_link<T>(link: &Link<T>) -> bool {
 // Versão em Box, mais próxima do estilo do Sedgewick (retorna nova raiz da subárvore)
 
 fn rotate_left_box<T>(mut h: Box<Node<T>>) -> Box<Node<T>> {
+    metrics::record(Counter::Rotations);
     // supõe h.right é Some
     let mut x = h.right.take().expect("rotate_left_box: sem filho direito");
     h.right = x.left.take();
@@ -58,6 +93,7 @@ fn rotate_left_box<T>(mut h: Box<Node<T>>) -> Box<Node<T>> {
 }
 
 fn rotate_right_box<T>(mut h: Box<Node<T>>) -> Box<Node<T>> {
+    metrics::record(Counter::Rotations);
     // supõe h.left é Some
     let mut x = h.left.take().expect("rotate_right_box: sem filho esquerdo");
     h.left = x.right.take();
@@ -68,6 +104,7 @@ fn rotate_right_box<T>(mut h: Box<Node<T>>) -> Box<Node<T>> {
 }
 
 fn flip_colors_box<T>(h: &mut Box<Node<T>>) {
+    metrics::record(Counter::ColorFlips);
     h.color = match h.color {
         Color::Red => Color::Black,
         Color::Black => Color::Red,
@@ -166,7 +203,7 @@ fn move_red_right_box<T>(mut h: Box<Node<T>>) -> Box<Node<T>> {
 // deleteMin (usado dentro de delete)
 // ========================
 
-fn delete_min_box<T: Ord>(mut h: Box<Node<T>>) -> (T, Link<T>) {
+fn delete_min_box<T>(mut h: Box<Node<T>>) -> (T, Link<T>) {
     if h.left.is_none() {
         let elem = h.elem;
         // a subárvore resultante é apenas o filho direito
@@ -192,30 +229,73 @@ fn delete_min_box<T: Ord>(mut h: Box<Node<T>>) -> (T, Link<T>) {
     (min, Some(h))
 }
 
+// ========================
+// deleteMax (espelho do deleteMin)
+// ========================
+
+fn delete_max_box<T>(mut h: Box<Node<T>>) -> (T, Link<T>) {
+    // traz o vermelho da esquerda para o caminho da direita
+    if is_red_link(&h.left) {
+        h = rotate_right_box(h);
+    }
+
+    if h.right.is_none() {
+        // sem filho direito, pela invariante o esquerdo também é vazio
+        let elem = h.elem;
+        return (elem, h.left.take());
+    }
+
+    if !is_red_link(&h.right) {
+        let right_left_red = if let Some(ref right) = h.right {
+            is_red_link(&right.left)
+        } else {
+            false
+        };
+
+        if !right_left_red {
+            h = move_red_right_box(h);
+        }
+    }
+
+    let right = h.right.take().unwrap();
+    let (max, new_right) = delete_max_box(right);
+    h.right = new_right;
+    let h = fix_up_box(h);
+    (max, Some(h))
+}
+
 // ========================
 // Inserção (put) estilo Sedgewick
 // ========================
 
-fn insert_rec<T: Ord>(h: Link<T>, value: T, inserted: &mut bool) -> Link<T> {
+/// `inserted` vira `true` se houve inserção; `path` anota as comparações
+/// da descida, para reencontrar o elemento novo depois do fix-up.
+fn insert_rec<T, C: Comparator<T>>(
+    h: Link<T>,
+    value: T,
+    cmp: &C,
+    path: &mut InsertPath<T>,
+    inserted: &mut bool,
+) -> Link<T> {
     match h {
         None => {
             *inserted = true;
-            return Some(Box::new(Node {
+            Some(Box::new(Node {
                 elem: value,
                 left: None,
                 right: None,
                 color: Color::Red,
-            }));
+            }))
         }
 
         Some(mut node) => {
-            if value < node.elem {
-                node.left = insert_rec(node.left, value, inserted);
-            } else if value > node.elem {
-                node.right = insert_rec(node.right, value, inserted);
-            } else {
+            let ord = comparator::counted(cmp, &value, &node.elem);
+            path.record(&node.elem, ord);
+            match ord {
+                Ordering::Less => node.left = insert_rec(node.left, value, cmp, path, inserted),
+                Ordering::Greater => node.right = insert_rec(node.right, value, cmp, path, inserted),
                 // já existe — não insere e não muda cor nem estrutura
-                return Some(node);
+                Ordering::Equal => return Some(node),
             }
 
             // === Fix-up, exatamente como o Sedgewick ===
@@ -236,7 +316,7 @@ fn insert_rec<T: Ord>(h: Link<T>, value: T, inserted: &mut bool) -> Link<T> {
                 flip_colors_box(&mut node);
             }
 
-            return Some(node);
+            Some(node)
         }
     }
 }
@@ -246,13 +326,12 @@ fn insert_rec<T: Ord>(h: Link<T>, value: T, inserted: &mut bool) -> Link<T> {
 // Remoção (delete) estilo Sedgewick
 // ========================
 
-fn delete_rec<T: Ord>(h: Link<T>, key: &T, removed: &mut bool) -> Link<T> {
-    let mut h = match h {
-        None => return None,
-        Some(node) => node,
-    };
+/// Pré-condição (como no Sedgewick): a chave está na árvore.
+/// `cmp(elem)` diz onde a chave está em relação a `elem`.
+fn delete_rec<T>(h: Link<T>, cmp: &impl Fn(&T) -> Ordering, removed: &mut Option<T>) -> Link<T> {
+    let mut h = h?;
 
-    if *key < h.elem {
+    if cmp(&h.elem) == Ordering::Less {
         // vamos descer pela esquerda; garante vermelho disponível à esquerda
         if !is_red_link(&h.left) {
             let left_left_red = if let Some(ref left) = h.left {
@@ -264,7 +343,7 @@ fn delete_rec<T: Ord>(h: Link<T>, key: &T, removed: &mut bool) -> Link<T> {
                 h = move_red_left_box(h);
             }
         }
-        h.left = delete_rec(h.left.take(), key, removed);
+        h.left = delete_rec(h.left.take(), cmp, removed);
     } else {
         // se o filho esquerdo é vermelho, rotaciona para trazer a chave para baixo
         if is_red_link(&h.left) {
@@ -272,8 +351,8 @@ fn delete_rec<T: Ord>(h: Link<T>, key: &T, removed: &mut bool) -> Link<T> {
         }
 
         // caso: achou a chave num nó sem filho direito → remover folha
-        if *key == h.elem && h.right.is_none() {
-            *removed = true;
+        if cmp(&h.elem) == Ordering::Equal && h.right.is_none() {
+            *removed = Some(h.elem);
             return None;
         }
 
@@ -289,91 +368,540 @@ fn delete_rec<T: Ord>(h: Link<T>, key: &T, removed: &mut bool) -> Link<T> {
             }
         }
 
-        if *key == h.elem {
+        if cmp(&h.elem) == Ordering::Equal {
             // substitui pela menor chave da subárvore direita
             let right = h.right.take().unwrap();
             let (min_key, new_right) = delete_min_box(right);
-            h.elem = min_key;
+            *removed = Some(std::mem::replace(&mut h.elem, min_key));
             h.right = new_right;
-            *removed = true;
         } else {
-            h.right = delete_rec(h.right.take(), key, removed);
+            h.right = delete_rec(h.right.take(), cmp, removed);
         }
     }
 
     Some(fix_up_box(h))
 }
 
+impl<T> BstNode<T> for Node<T> {
+    fn elem(&self) -> &T {
+        &self.elem
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+impl<T> OwnedBstNode<T> for Node<T> {
+    fn take_left(&mut self) -> Option<Box<Self>> {
+        self.left.take()
+    }
+
+    fn take_right(&mut self) -> Option<Box<Self>> {
+        self.right.take()
+    }
+
+    fn into_elem(self) -> T {
+        self.elem
+    }
+}
+
+/// Padrão do Sedgewick antes de remover: se ambos filhos da raiz são
+/// pretos, torna a raiz vermelha para ter um vermelho para emprestar.
+fn redden_root_if_needed<T>(root: &mut Link<T>) {
+    if let Some(root) = root {
+        if !is_red_link(&root.left) && !is_red_link(&root.right) {
+            root.color = Color::Red;
+        }
+    }
+}
+
+/// Raiz sempre preta.
+fn blacken_root<T>(root: &mut Link<T>) {
+    if let Some(root) = root {
+        root.color = Color::Black;
+    }
+}
+
+// busca na árvore inteira; `cmp` como em `find_by`.
+fn find_in<'a, T>(root: &'a Link<T>, cmp: &impl Fn(&T) -> Ordering) -> Option<&'a T> {
+    let mut cur = root;
+
+    while let Some(node) = cur {
+        match cmp(&node.elem) {
+            Ordering::Less => cur = &node.left,
+            Ordering::Greater => cur = &node.right,
+            Ordering::Equal => return Some(&node.elem),
+        }
+    }
+
+    None
+}
+
+// desce até o nó que `cmp` aponta; não conta comparações (quem chama decide).
+fn find_mut_in<T>(root: &mut Link<T>, cmp: impl Fn(&T) -> Ordering) -> Option<&mut T> {
+    let mut cur = root;
+
+    while let Some(node) = cur {
+        match cmp(&node.elem) {
+            Ordering::Less => cur = &mut node.left,
+            Ordering::Greater => cur = &mut node.right,
+            Ordering::Equal => return Some(&mut node.elem),
+        }
+    }
+
+    None
+}
+
+// remove da árvore inteira o nó que `cmp` aponta; `cmp` como em `find_by`.
+fn remove_in<T>(root: &mut Link<T>, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+    let cmp = metrics::counted_cmp(cmp);
+    // o delete do Sedgewick supõe que a chave existe
+    find_in(root, &cmp)?;
+
+    redden_root_if_needed(root);
+
+    let mut removed = None;
+    *root = delete_rec(root.take(), &cmp, &mut removed);
+
+    blacken_root(root);
+    removed
+}
+
 // ========================
-// Implementação de Set<T>
+// Busca / remoção por comparação (usadas também pelo `RbMap`)
 // ========================
+//
+// `cmp(elem)` diz onde o alvo está em relação a `elem`:
+// `Less` → à esquerda, `Greater` → à direita, `Equal` → achou.
 
-impl<T: Ord> Set<T> for BstRBSet<T> {
-    fn insert(&mut self, value: T) -> bool {
-        let mut inserted = false;
-        self.root = insert_rec(self.root.take(), value, &mut inserted);
-        if let Some(ref mut root) = self.root {
-            root.color = Color::Black; // raiz sempre preta
+impl<T, C> BstRBSet<T, C> {
+    pub(crate) fn find_by(&self, cmp: impl Fn(&T) -> Ordering) -> Option<&T> {
+        find_in(&self.root, &metrics::counted_cmp(cmp))
+    }
+
+    pub(crate) fn find_by_mut(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<&mut T> {
+        find_mut_in(&mut self.root, metrics::counted_cmp(cmp))
+    }
+
+    pub(crate) fn remove_by(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+        let removed = remove_in(&mut self.root, cmp);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+}
+
+impl<T, C: Comparator<T>> BstRBSet<T, C> {
+    /// Insere o elemento se ele ainda não existe.
+    /// Devolve uma referência para o elemento recém-inserido, ou `None` se já existia.
+    pub(crate) fn insert_new(&mut self, value: T) -> Option<&mut T> {
+        let mut path = InsertPath::new();
+        if !self.insert_with_path(value, &mut path) {
+            return None;
         }
+        // as rotações mudaram o caminho: desce de novo (ver `InsertPath`)
+        find_mut_in(&mut self.root, |x| path.direction(x))
+    }
+
+    /// Insere o elemento se ele ainda não existe. Devolve `true` se inseriu.
+    fn insert_value(&mut self, value: T) -> bool {
+        self.insert_with_path(value, &mut InsertPath::untracked())
+    }
+
+    fn insert_with_path(&mut self, value: T, path: &mut InsertPath<T>) -> bool {
+        let mut inserted = false;
+        self.root = insert_rec(self.root.take(), value, &self.cmp, path, &mut inserted);
+        blacken_root(&mut self.root); // raiz sempre preta
+
         if inserted {
             self.len += 1;
         }
         inserted
     }
 
-    fn remove(&mut self, value: &T) -> bool {
-        if self.root.is_none() {
-            return false;
+    /// Elemento equivalente a `value` na ordem do comparador.
+    fn get_equivalent(&self, value: &T) -> Option<&T> {
+        self.find_by(|x| self.cmp.compare(value, x))
+    }
+
+    /// Remove o elemento equivalente a `value` na ordem do comparador.
+    fn take_equivalent(&mut self, value: &T) -> Option<T> {
+        let cmp = &self.cmp;
+        let removed = remove_in(&mut self.root, |x| cmp.compare(value, x));
+        if removed.is_some() {
+            self.len -= 1;
         }
+        removed
+    }
+}
+
+// ========================
+// Construção a partir de entrada ordenada
+// ========================
+//
+// A left-leaning rubro-negra é uma árvore 2-3 disfarçada: cada nó preto,
+// junto com um eventual filho esquerdo vermelho, é um nó 2-3. Para montar
+// a árvore em O(n), escolhemos uma árvore 2-3 com todas as folhas na mesma
+// profundidade `b` e traduzimos cada nó:
+//
+// - nó-2 (uma chave, dois filhos): um nó preto;
+// - nó-3 (duas chaves, três filhos): um nó preto com filho esquerdo
+//   vermelho, que fica com os dois primeiros filhos.
+//
+// Uma árvore 2-3 de altura `b` guarda entre 2^b - 1 (só nós-2) e 3^b - 1
+// (só nós-3) chaves. Com b = ⌊log2(n + 1)⌋, n sempre cabe nesse intervalo;
+// cada nó usa nó-2 enquanto as chaves couberem em dois filhos, e reparte as
+// chaves o mais igualmente possível entre os filhos.
+
+/// Menor número de chaves de uma árvore 2-3 de altura `b`.
+fn min_keys(b: u32) -> usize {
+    2usize.saturating_pow(b) - 1
+}
+
+/// Maior número de chaves de uma árvore 2-3 de altura `b`.
+fn max_keys(b: u32) -> usize {
+    3usize.saturating_pow(b) - 1
+}
+
+/// Monta a subárvore de altura negra `b` com os próximos `n` elementos de
+/// `it`, em ordem. Raiz sempre preta.
+fn build_sorted<T>(n: usize, b: u32, it: &mut impl Iterator<Item = T>) -> Link<T> {
+    debug_assert!((min_keys(b)..=max_keys(b)).contains(&n));
+    if b == 0 {
+        return None;
+    }
+
+    let child_max = max_keys(b - 1);
+
+    if n - 1 <= child_max.saturating_mul(2) {
+        // nó-2: o filho esquerdo leva a chave que sobrar
+        let m = n - 1;
+        let left = build_sorted(m - m / 2, b - 1, it);
+        let elem = it_next(it);
+        let right = build_sorted(m / 2, b - 1, it);
+        return Some(Box::new(Node {
+            elem,
+            left,
+            right,
+            color: Color::Black,
+        }));
+    }
+
+    // nó-3: três filhos com m/3 chaves cada, as sobras indo para a esquerda
+    let m = n - 2;
+    let (q, r) = (m / 3, m % 3);
+    let a = build_sorted(q + usize::from(r > 0), b - 1, it);
+    let red_elem = it_next(it);
+    let middle = build_sorted(q + usize::from(r > 1), b - 1, it);
+    let elem = it_next(it);
+    let c = build_sorted(q, b - 1, it);
+
+    let red = Box::new(Node {
+        elem: red_elem,
+        left: a,
+        right: middle,
+        color: Color::Red,
+    });
+    Some(Box::new(Node {
+        elem,
+        left: Some(red),
+        right: c,
+        color: Color::Black,
+    }))
+}
+
+fn it_next<T>(it: &mut impl Iterator<Item = T>) -> T {
+    it.next().expect("from_sorted_iter: faltou elemento")
+}
 
-        // opcional: se ambos filhos da raiz são pretos, torna raiz vermelha
-        // (padrão do Sedgewick para simplificar lógica)
-        if !is_red_link(&self.root.as_ref().unwrap().left)
-            && !is_red_link(&self.root.as_ref().unwrap().right)
-        {
-            if let Some(ref mut root) = self.root {
-                root.color = Color::Red;
+impl<T: Ord> BstRBSet<T> {
+    /// Monta a árvore a partir de elementos em ordem estritamente crescente,
+    /// em O(n) e sem nenhuma rotação (ver o comentário acima). O resultado
+    /// satisfaz todas as regras da left-leaning: raiz preta, vermelhos só à
+    /// esquerda, nunca dois seguidos e a mesma altura negra em todo caminho.
+    ///
+    /// Devolve `UnsortedError` se a entrada estiver fora de ordem ou tiver
+    /// repetidos.
+    pub fn from_sorted_iter<I>(iter: I) -> Result<Self, UnsortedError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::from_sorted_iter_by(iter, NaturalOrder)
+    }
+}
+
+impl<T, C: Comparator<T>> BstRBSet<T, C> {
+    /// `from_sorted_iter` na ordem de `cmp`: a entrada precisa ser
+    /// estritamente crescente segundo o comparador.
+    pub fn from_sorted_iter_by<I>(iter: I, cmp: C) -> Result<Self, UnsortedError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let values = bulk::collect_sorted(iter, &cmp)?;
+        let len = values.len();
+        let b = (len + 1).ilog2();
+        let root = build_sorted(len, b, &mut values.into_iter());
+        Ok(Self { root, len, cmp })
+    }
+}
+
+// ========================
+// Invariantes
+// ========================
+
+impl<T, C: Comparator<T>> BstRBSet<T, C> {
+    /// Confere a estrutura da árvore: ordem de BST (a do comparador), `len`, raiz preta,
+    /// nenhum link vermelho à direita, nenhum par de vermelhos seguidos e
+    /// a mesma quantidade de links pretos em todo caminho até as folhas.
+    /// Devolve a primeira regra quebrada. Custa O(n); pensado para testes.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        // devolve a altura negra da subárvore
+        fn check_rec<T>(link: &Link<T>) -> Result<usize, InvariantError> {
+            let Some(node) = link else {
+                return Ok(0);
+            };
+
+            if is_red_link(&node.right) {
+                return Err(InvariantError::RightLeaningRed);
+            }
+            if node.color == Color::Red && is_red_link(&node.left) {
+                return Err(InvariantError::DoubleRed);
             }
-        }
 
-        let mut removed = false;
-        self.root = delete_rec(self.root.take(), value, &mut removed);
+            let left = check_rec(&node.left)?;
+            let right = check_rec(&node.right)?;
+            if left != right {
+                return Err(InvariantError::UnequalBlackHeight { left, right });
+            }
 
-        if let Some(ref mut root) = self.root {
-            root.color = Color::Black;
+            Ok(left + usize::from(node.color == Color::Black))
         }
 
-        if removed {
-            self.len -= 1;
+        invariants::check_order_and_len_by(self.iter(), self.len, &self.cmp)?;
+        if is_red_link(&self.root) {
+            return Err(InvariantError::RedRoot);
         }
+        check_rec(&self.root)?;
+        Ok(())
+    }
+}
 
-        removed
+// ========================
+// Graphviz
+// ========================
+
+impl<T: Display, C> BstRBSet<T, C> {
+    /// Desenha a árvore em DOT (Graphviz), com um ponto para cada filho vazio.
+    /// A cor fica no link: a aresta que chega num nó vermelho é desenhada
+    /// em vermelho (na left-leaning, sempre um filho esquerdo).
+    pub fn to_dot(&self) -> String {
+        dot::to_dot(
+            "BstRBSet",
+            self.root.as_deref(),
+            |_| String::new(),
+            |node| match node.color {
+                Color::Red => "color=red, penwidth=2",
+                Color::Black => "",
+            },
+        )
     }
+}
 
-    fn contains(&self, value: &T) -> bool {
-        let mut cur = &self.root;
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
 
-        while let Some(node) = cur {
-            if *value < node.elem {
-                cur = &node.left;
-            } else if *value > node.elem {
-                cur = &node.right;
-            } else {
-                return true;
-            }
-        }
+impl<T, Q> Lookup<T, Q> for BstRBSet<T>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        self.find_by(|x| value.cmp(x.borrow()))
+    }
 
-        false
+    fn take(&mut self, value: &Q) -> Option<T> {
+        self.remove_by(|x| value.cmp(x.borrow()))
+    }
+}
+
+// ========================
+// Implementação de Set<T>
+// ========================
+
+impl<T, C: Comparator<T>> Set<T> for BstRBSet<T, C> {
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_value(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take_equivalent(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get_equivalent(value).is_some()
     }
 
     fn len(&self) -> usize {
         self.len
     }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(BstRBSet::iter(self))
+    }
+
+    fn sorted_by(&self) -> Option<CmpFn<T>> {
+        self.cmp.natural_cmp()
+    }
+}
+
+// ========================
+// Implementação de OrderedSet<T>
+// ========================
+
+impl<T, C: Comparator<T>> OrderedSet<T> for BstRBSet<T, C> {
+    fn min(&self) -> Option<&T> {
+        bst_nav::min(self.root.as_deref())
+    }
+
+    fn max(&self) -> Option<&T> {
+        bst_nav::max(self.root.as_deref())
+    }
+
+    fn floor(&self, value: &T) -> Option<&T> {
+        bst_nav::floor_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn ceiling(&self, value: &T) -> Option<&T> {
+        bst_nav::ceiling_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn successor(&self, value: &T) -> Option<&T> {
+        bst_nav::successor_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn predecessor(&self, value: &T) -> Option<&T> {
+        bst_nav::predecessor_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        redden_root_if_needed(&mut self.root);
+        let root = self.root.take()?;
+        let (min, new_root) = delete_min_box(root);
+        self.root = new_root;
+        blacken_root(&mut self.root);
+        self.len -= 1;
+        Some(min)
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        redden_root_if_needed(&mut self.root);
+        let root = self.root.take()?;
+        let (max, new_root) = delete_max_box(root);
+        self.root = new_root;
+        blacken_root(&mut self.root);
+        self.len -= 1;
+        Some(max)
+    }
+
+    fn range<R: RangeBounds<T>>(&self, range: R) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(InOrder::range_by(
+            self.root.as_deref(),
+            range.start_bound(),
+            range.end_bound(),
+            &self.cmp,
+        ))
+    }
+}
+
+// ========================
+// Drop
+// ========================
+
+impl<T, C> Drop for BstRBSet<T, C> {
+    fn drop(&mut self) {
+        bst_nav::drop_tree(self.root.take());
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+pub struct BstRBSetIter<'a, T> {
+    inner: InOrder<'a, T, Node<T>>,
+}
+
+impl<'a, T> Iterator for BstRBSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Iterador em ordem que consome o conjunto.
+pub struct BstRBSetIntoIter<T> {
+    inner: IntoInOrder<T, Node<T>>,
+}
+
+impl<T> Iterator for BstRBSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, C> IntoIterator for BstRBSet<T, C> {
+    type Item = T;
+    type IntoIter = BstRBSetIntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        BstRBSetIntoIter {
+            inner: IntoInOrder::new(self.root.take(), self.len),
+        }
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a BstRBSet<T, C> {
+    type Item = &'a T;
+    type IntoIter = BstRBSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> FromIterator<T> for BstRBSet<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::with_comparator(C::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for BstRBSet<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
 }
 
-impl<T> crate::set::SetName for BstRBSet<T> {
-    fn name() -> &'static str {
-        "BstRBSet"
+impl<T, C> crate::set::SetName for BstRBSet<T, C> {
+    fn name() -> String {
+        "BstRBSet".to_string()
     }
 }
 
@@ -1,4 +1,12 @@
-use crate::set::Set;
+use crate::metrics;
+use crate::set::bst_nav::{self, BstNode, InOrder, IntoInOrder, OwnedBstNode};
+use crate::set::comparator::{self, Comparator, NaturalOrder};
+use crate::set::{dot, invariants};
+use crate::set::{CmpFn, InvariantError, Lookup, OrderedSet, Set};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::RangeBounds;
 
 type Link<T> = Option<Box<Node<T>>>;
 
@@ -11,152 +19,473 @@ struct Node<T> {
 /// Conjunto implementado como árvore binária de busca (não balanceada).
 /// Não permite elementos repetidos.
 /// Operações são O(altura).
-pub struct BstSet<T> {
+///
+/// A ordem vem de `C` (ver `set::comparator`); o padrão é o `Ord` de `T`.
+pub struct BstSet<T, C = NaturalOrder> {
     root: Link<T>,
     len: usize,
+    cmp: C,
 }
 
 impl<T> BstSet<T> {
     pub fn new() -> Self {
-        Self { root: None, len: 0 }
+        Self::with_comparator(NaturalOrder)
+    }
+}
+
+impl<T, C> BstSet<T, C> {
+    /// Conjunto vazio ordenado por `cmp`.
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            root: None,
+            len: 0,
+            cmp,
+        }
+    }
+
+    /// A ordem do conjunto.
+    pub fn comparator(&self) -> &C {
+        &self.cmp
     }
 
     pub fn len(&self) -> usize {
         self.len
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Itera em ordem crescente.
+    pub fn iter(&self) -> BstSetIter<'_, T> {
+        BstSetIter {
+            inner: InOrder::new(self.root.as_deref()),
+        }
+    }
 }
 
 // ========================
-// Implementação de Set<T>
+// Helpers
 // ========================
 
-impl<T: Ord> Set<T> for BstSet<T> {
-    fn insert(&mut self, value: T) -> bool {
-        fn insert_rec<T: Ord>(link: &mut Link<T>, value: T, len: &mut usize) -> bool {
-            match link {
-                Some(node) => {
-                    if value < node.elem {
-                        insert_rec(&mut node.left, value, len)
-                    } else if value > node.elem {
-                        insert_rec(&mut node.right, value, len)
-                    } else {
-                        // já existe, não insere
-                        false
-                    }
-                }
-                None => {
-                    *link = Some(Box::new(Node {
-                        elem: value,
-                        left: None,
-                        right: None,
-                    }));
-                    *len += 1;
-                    true
-                }
-            }
+// remove o menor elemento da subárvore apontada por `link`
+// e devolve o valor removido.
+fn take_min<T>(link: &mut Link<T>) -> T {
+    let mut cur = link;
+
+    loop {
+        // enquanto houver filho à esquerda, desce para a esquerda
+        if cur.as_ref()
+            .and_then(|node| node.left.as_ref())
+            .is_some()
+        {
+            cur = &mut cur.as_mut().unwrap().left;
+            continue;
         }
 
-        insert_rec(&mut self.root, value, &mut self.len)
+        // aqui `cur` aponta para o mínimo: não tem filho esquerdo
+        let mut boxed = cur.take().expect("take_min chamado em subárvore vazia");
+        let right = boxed.right.take(); // filho direito sobe
+        *cur = right;
+        return boxed.elem;
     }
+}
 
-    fn remove(&mut self, value: &T) -> bool {
-        // remove o menor elemento da subárvore apontada por `link`
-        // e devolve o valor removido.
-        fn take_min<T>(link: &mut Link<T>) -> T {
-            let mut cur = link;
-
-            loop {
-                // enquanto houver filho à esquerda, desce para a esquerda
-                if cur.as_ref()
-                    .and_then(|node| node.left.as_ref())
-                    .is_some()
-                {
-                    cur = &mut cur.as_mut().unwrap().left;
-                    continue;
-                }
-
-                // aqui `cur` aponta para o mínimo: não tem filho esquerdo
-                let mut boxed = cur.take().expect("take_min chamado em subárvore vazia");
-                let right = boxed.right.take(); // filho direito sobe
-                *cur = right;
-                return boxed.elem;
-            }
+// espelho de `take_min`: remove o maior elemento da subárvore.
+fn take_max<T>(link: &mut Link<T>) -> T {
+    let mut cur = link;
+
+    loop {
+        if cur.as_ref()
+            .and_then(|node| node.right.as_ref())
+            .is_some()
+        {
+            cur = &mut cur.as_mut().unwrap().right;
+            continue;
         }
 
-        fn remove_rec<T: Ord>(link: &mut Link<T>, value: &T, len: &mut usize) -> bool {
-            let node = match link {
-                Some(node) => node,
-                None => return false,
-            };
+        let mut boxed = cur.take().expect("take_max chamado em subárvore vazia");
+        let left = boxed.left.take(); // filho esquerdo sobe
+        *cur = left;
+        return boxed.elem;
+    }
+}
 
-            if value < &node.elem {
-                return remove_rec(&mut node.left, value, len);
-            } else if value > &node.elem {
-                return remove_rec(&mut node.right, value, len);
-            }
+// desce até o nó que `cmp` aponta (ver `find_by`) e o remove.
+fn remove_in<T>(link: &mut Link<T>, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+    let cmp = metrics::counted_cmp(cmp);
+    let mut cur = link;
+    loop {
+        let order = match cur.as_deref() {
+            Some(node) => cmp(&node.elem),
+            None => return None,
+        };
 
-            // encontramos o nó a ser removido
-            *len -= 1;
-
-            match (node.left.take(), node.right.take()) {
-                (None, None) => {
-                    // folha
-                    *link = None;
-                }
-                (Some(left), None) => {
-                    // só filho esquerdo
-                    *link = Some(left);
-                }
-                (None, Some(right)) => {
-                    // só filho direito
-                    *link = Some(right);
-                }
-                (Some(left), Some(right_box)) => {
-                    // dois filhos:
-                    // - pegamos o sucessor em ordem (mínimo da subárvore direita)
-                    // - esse valor vira o novo elem da raiz desta subárvore
-                    // - e o restante da subárvore direita (sem o mínimo) fica em `right`
-                    let mut right: Link<T> = Some(right_box);
-                    let succ = take_min(&mut right);
-
-                    *link = Some(Box::new(Node {
-                        elem: succ,
-                        left: Some(left),
-                        right,
-                    }));
-                }
-            }
+        match order {
+            Ordering::Less => cur = &mut cur.as_mut().unwrap().left,
+            Ordering::Greater => cur = &mut cur.as_mut().unwrap().right,
+            Ordering::Equal => break,
+        }
+    }
+
+    Some(remove_at(cur))
+}
+
+// remove o nó apontado por `link` (que EXISTE) e devolve o elemento.
+fn remove_at<T>(link: &mut Link<T>) -> T {
+    let mut node = link.take().expect("remove_at chamado em link vazio");
 
-            true
+    match (node.left.take(), node.right.take()) {
+        (None, None) => {
+            // folha: `link` já ficou vazio
         }
+        (Some(left), None) => {
+            // só filho esquerdo
+            *link = Some(left);
+        }
+        (None, Some(right)) => {
+            // só filho direito
+            *link = Some(right);
+        }
+        (Some(left), Some(right_box)) => {
+            // dois filhos:
+            // - pegamos o sucessor em ordem (mínimo da subárvore direita)
+            // - esse valor vira o novo elem da raiz desta subárvore
+            // - e o restante da subárvore direita (sem o mínimo) fica em `right`
+            let mut right: Link<T> = Some(right_box);
+            let succ = take_min(&mut right);
 
-        remove_rec(&mut self.root, value, &mut self.len)
+            *link = Some(Box::new(Node {
+                elem: succ,
+                left: Some(left),
+                right,
+            }));
+        }
     }
 
-    fn contains(&self, value: &T) -> bool {
+    node.elem
+}
+
+impl<T> BstNode<T> for Node<T> {
+    fn elem(&self) -> &T {
+        &self.elem
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+impl<T> OwnedBstNode<T> for Node<T> {
+    fn take_left(&mut self) -> Option<Box<Self>> {
+        self.left.take()
+    }
+
+    fn take_right(&mut self) -> Option<Box<Self>> {
+        self.right.take()
+    }
+
+    fn into_elem(self) -> T {
+        self.elem
+    }
+}
+
+// ========================
+// Busca / remoção por comparação (usadas também pelo `BstMap`)
+// ========================
+//
+// `cmp(elem)` diz onde o alvo está em relação a `elem`:
+// `Less` → à esquerda, `Greater` → à direita, `Equal` → achou.
+
+impl<T, C> BstSet<T, C> {
+    pub(crate) fn find_by(&self, cmp: impl Fn(&T) -> Ordering) -> Option<&T> {
+        let cmp = metrics::counted_cmp(cmp);
         let mut cur = &self.root;
 
         while let Some(node) = cur {
-            if *value < node.elem {
-                cur = &node.left;
-            } else if *value > node.elem {
-                cur = &node.right;
-            } else {
-                return true;
+            match cmp(&node.elem) {
+                Ordering::Less => cur = &node.left,
+                Ordering::Greater => cur = &node.right,
+                Ordering::Equal => return Some(&node.elem),
+            }
+        }
+
+        None
+    }
+
+    pub(crate) fn find_by_mut(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<&mut T> {
+        let cmp = metrics::counted_cmp(cmp);
+        let mut cur = &mut self.root;
+
+        while let Some(node) = cur {
+            match cmp(&node.elem) {
+                Ordering::Less => cur = &mut node.left,
+                Ordering::Greater => cur = &mut node.right,
+                Ordering::Equal => return Some(&mut node.elem),
             }
         }
 
-        false
+        None
+    }
+
+    pub(crate) fn remove_by(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+        let removed = remove_in(&mut self.root, cmp);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+}
+
+impl<T, C: Comparator<T>> BstSet<T, C> {
+    /// Insere o elemento se ele ainda não existe.
+    /// Devolve uma referência para o elemento recém-inserido, ou `None` se já existia.
+    pub(crate) fn insert_new(&mut self, value: T) -> Option<&mut T> {
+        // desce até o link vazio onde o elemento deve entrar
+        let mut cur = &mut self.root;
+        while let Some(node) = cur.as_deref() {
+            match comparator::counted(&self.cmp, &value, &node.elem) {
+                Ordering::Less => cur = &mut cur.as_mut().unwrap().left,
+                Ordering::Greater => cur = &mut cur.as_mut().unwrap().right,
+                // já existe, não insere
+                Ordering::Equal => return None,
+            }
+        }
+
+        self.len += 1;
+        let node = cur.insert(Box::new(Node {
+            elem: value,
+            left: None,
+            right: None,
+        }));
+        Some(&mut node.elem)
+    }
+
+    /// Elemento equivalente a `value` na ordem do comparador.
+    fn get_equivalent(&self, value: &T) -> Option<&T> {
+        self.find_by(|x| self.cmp.compare(value, x))
+    }
+
+    /// Remove o elemento equivalente a `value` na ordem do comparador.
+    fn take_equivalent(&mut self, value: &T) -> Option<T> {
+        let cmp = &self.cmp;
+        let removed = remove_in(&mut self.root, |x| cmp.compare(value, x));
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+}
+
+// ========================
+// Invariantes
+// ========================
+
+impl<T, C: Comparator<T>> BstSet<T, C> {
+    /// Confere a estrutura da árvore: ordem de BST (a do comparador) e `len`.
+    /// Devolve a primeira regra quebrada. Custa O(n); pensado para testes.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        invariants::check_order_and_len_by(self.iter(), self.len, &self.cmp)
+    }
+}
+
+// ========================
+// Graphviz
+// ========================
+
+impl<T: Display, C> BstSet<T, C> {
+    /// Desenha a árvore em DOT (Graphviz), com um ponto para cada filho vazio.
+    /// Ex.: `dot -Tpng arvore.dot -o arvore.png`.
+    pub fn to_dot(&self) -> String {
+        dot::to_dot("BstSet", self.root.as_deref(), |_| String::new(), |_| "")
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q> Lookup<T, Q> for BstSet<T>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        self.find_by(|x| value.cmp(x.borrow()))
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        self.remove_by(|x| value.cmp(x.borrow()))
+    }
+}
+
+// ========================
+// Implementação de Set<T>
+// ========================
+
+impl<T, C: Comparator<T>> Set<T> for BstSet<T, C> {
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_new(value).is_some()
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take_equivalent(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get_equivalent(value).is_some()
     }
 
     fn len(&self) -> usize {
         self.len
     }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(BstSet::iter(self))
+    }
+
+    fn sorted_by(&self) -> Option<CmpFn<T>> {
+        self.cmp.natural_cmp()
+    }
+}
+
+// ========================
+// Implementação de OrderedSet<T>
+// ========================
+
+impl<T, C: Comparator<T>> OrderedSet<T> for BstSet<T, C> {
+    fn min(&self) -> Option<&T> {
+        bst_nav::min(self.root.as_deref())
+    }
+
+    fn max(&self) -> Option<&T> {
+        bst_nav::max(self.root.as_deref())
+    }
+
+    fn floor(&self, value: &T) -> Option<&T> {
+        bst_nav::floor_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn ceiling(&self, value: &T) -> Option<&T> {
+        bst_nav::ceiling_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn successor(&self, value: &T) -> Option<&T> {
+        bst_nav::successor_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn predecessor(&self, value: &T) -> Option<&T> {
+        bst_nav::predecessor_by(self.root.as_deref(), value, &self.cmp)
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.root.as_ref()?;
+        self.len -= 1;
+        Some(take_min(&mut self.root))
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        self.root.as_ref()?;
+        self.len -= 1;
+        Some(take_max(&mut self.root))
+    }
+
+    fn range<R: RangeBounds<T>>(&self, range: R) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(InOrder::range_by(
+            self.root.as_deref(),
+            range.start_bound(),
+            range.end_bound(),
+            &self.cmp,
+        ))
+    }
+}
+
+// ========================
+// Drop
+// ========================
+
+impl<T, C> Drop for BstSet<T, C> {
+    fn drop(&mut self) {
+        bst_nav::drop_tree(self.root.take());
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+pub struct BstSetIter<'a, T> {
+    inner: InOrder<'a, T, Node<T>>,
+}
+
+impl<'a, T> Iterator for BstSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Iterador em ordem que consome o conjunto.
+pub struct BstSetIntoIter<T> {
+    inner: IntoInOrder<T, Node<T>>,
+}
+
+impl<T> Iterator for BstSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, C> IntoIterator for BstSet<T, C> {
+    type Item = T;
+    type IntoIter = BstSetIntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        BstSetIntoIter {
+            inner: IntoInOrder::new(self.root.take(), self.len),
+        }
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a BstSet<T, C> {
+    type Item = &'a T;
+    type IntoIter = BstSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> FromIterator<T> for BstSet<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::with_comparator(C::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for BstSet<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
 }
 
-impl<T> crate::set::SetName for BstSet<T> {
-    fn name() -> &'static str {
-        "BstSet"
+impl<T, C> crate::set::SetName for BstSet<T, C> {
+    fn name() -> String {
+        "BstSet".to_string()
     }
 }
 
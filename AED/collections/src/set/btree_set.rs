@@ -0,0 +1,674 @@
+use crate::metrics;
+use crate::set::invariants;
+use crate::set::{CmpFn, InvariantError, Lookup, OrderedSet, Set};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+use std::vec;
+
+// Nó de árvore B com grau mínimo `B` (notação do CLRS, onde se chama t):
+// - todo nó, fora a raiz, tem entre B - 1 e 2B - 1 chaves;
+// - nó interno com k chaves tem k + 1 filhos;
+// - todas as folhas estão na mesma profundidade.
+//
+// As chaves de um nó ficam num único `Vec`, lado a lado na memória: a busca
+// dentro do nó é uma busca binária num bloco contíguo, e a árvore inteira
+// tem altura ~log_B(n) em vez de ~log_2(n).
+struct Node<T> {
+    keys: Vec<T>,
+    children: Vec<Node<T>>, // vazio nas folhas
+}
+
+impl<T> Node<T> {
+    fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Posição do alvo dentro do nó: `Ok(i)` se `keys[i]` é o alvo,
+    /// `Err(i)` se ele estaria na subárvore `children[i]`.
+    fn search(&self, cmp: &impl Fn(&T) -> Ordering) -> Result<usize, usize> {
+        // `cmp` dá o alvo em relação à chave; a busca binária quer o contrário
+        self.keys.binary_search_by(|k| cmp(k).reverse())
+    }
+}
+
+/// Conjunto implementado como árvore B com grau mínimo `B`.
+/// Não permite elementos repetidos.
+/// Operações são O(B · log_B n); na prática a busca binária dentro do nó é
+/// barata e o ganho vem de visitar poucos nós (melhor uso de cache).
+///
+/// `B = 2` é a árvore 2-3-4; valores maiores deixam os nós mais largos.
+pub struct BTreeSet<T, const B: usize = 6> {
+    root: Node<T>,
+    len: usize,
+}
+
+impl<T, const B: usize> BTreeSet<T, B> {
+    /// Máximo de chaves por nó.
+    const MAX_KEYS: usize = 2 * B - 1;
+    /// Mínimo de chaves por nó (fora a raiz).
+    const MIN_KEYS: usize = B - 1;
+
+    pub fn new() -> Self {
+        const { assert!(B >= 2, "BTreeSet: grau mínimo B precisa ser >= 2") };
+
+        Self {
+            root: Node::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Itera em ordem crescente.
+    pub fn iter(&self) -> BTreeSetIter<'_, T> {
+        let mut iter = BTreeSetIter {
+            stack: Vec::new(),
+            last: None,
+        };
+        iter.seek(&self.root, |_| false);
+        iter
+    }
+}
+
+// ========================
+// Helpers de árvore B
+// ========================
+
+impl<T, const B: usize> BTreeSet<T, B> {
+    /// Divide o filho cheio `node.children[i]` (2B - 1 chaves) em dois nós
+    /// de B - 1 chaves; a chave do meio sobe para `node`.
+    fn split_child(node: &mut Node<T>, i: usize) {
+        let child = &mut node.children[i];
+
+        let right_keys = child.keys.split_off(B);
+        let median = child.keys.pop().expect("split_child: filho sem chave do meio");
+        let right_children = if child.is_leaf() {
+            Vec::new()
+        } else {
+            child.children.split_off(B)
+        };
+
+        node.keys.insert(i, median);
+        node.children.insert(
+            i + 1,
+            Node {
+                keys: right_keys,
+                children: right_children,
+            },
+        );
+    }
+
+    /// Junta `children[i]`, `keys[i]` e `children[i + 1]` num único nó.
+    fn merge_children(node: &mut Node<T>, i: usize) {
+        let right = node.children.remove(i + 1);
+        let separator = node.keys.remove(i);
+
+        let left = &mut node.children[i];
+        left.keys.push(separator);
+        left.keys.extend(right.keys);
+        left.children.extend(right.children);
+    }
+
+    /// Garante que `node.children[i]` tenha pelo menos B chaves antes de
+    /// descer nele (remoção em uma passada, como no CLRS): pega emprestado
+    /// de um irmão com folga ou funde com um irmão.
+    /// Devolve o índice do filho onde a descida deve continuar.
+    fn fill_child(node: &mut Node<T>, i: usize) -> usize {
+        if node.children[i].keys.len() > Self::MIN_KEYS {
+            return i;
+        }
+
+        if i > 0 && node.children[i - 1].keys.len() > Self::MIN_KEYS {
+            // empresta do irmão esquerdo: a maior chave dele sobe e o
+            // separador desce para o começo do filho
+            let (left, right) = node.children.split_at_mut(i);
+            let (left, child) = (&mut left[i - 1], &mut right[0]);
+
+            let up = left.keys.pop().expect("fill_child: irmão esquerdo vazio");
+            let down = std::mem::replace(&mut node.keys[i - 1], up);
+            child.keys.insert(0, down);
+            if let Some(grandchild) = left.children.pop() {
+                child.children.insert(0, grandchild);
+            }
+            i
+        } else if i + 1 < node.children.len() && node.children[i + 1].keys.len() > Self::MIN_KEYS {
+            // espelho: empresta do irmão direito
+            let (left, right) = node.children.split_at_mut(i + 1);
+            let (child, right) = (&mut left[i], &mut right[0]);
+
+            let up = right.keys.remove(0);
+            let down = std::mem::replace(&mut node.keys[i], up);
+            child.keys.push(down);
+            if !right.is_leaf() {
+                child.children.push(right.children.remove(0));
+            }
+            i
+        } else if i + 1 < node.children.len() {
+            Self::merge_children(node, i);
+            i
+        } else {
+            Self::merge_children(node, i - 1);
+            i - 1
+        }
+    }
+
+    /// Remove a menor chave da subárvore (que tem pelo menos B chaves,
+    /// ou é a raiz).
+    fn remove_min(mut node: &mut Node<T>) -> T {
+        while !node.is_leaf() {
+            let i = Self::fill_child(node, 0);
+            node = &mut node.children[i];
+        }
+        node.keys.remove(0)
+    }
+
+    /// Espelho de `remove_min`.
+    fn remove_max(mut node: &mut Node<T>) -> T {
+        while !node.is_leaf() {
+            let last = node.children.len() - 1;
+            let i = Self::fill_child(node, last);
+            node = &mut node.children[i];
+        }
+        node.keys.pop().expect("remove_max: folha vazia")
+    }
+
+    /// Raiz interna que ficou sem chaves (depois de uma fusão) dá lugar
+    /// ao seu único filho: é assim que a árvore B perde altura.
+    fn shrink_root(&mut self) {
+        if self.root.keys.is_empty() && !self.root.is_leaf() {
+            self.root = self.root.children.pop().expect("shrink_root: raiz sem filho");
+        }
+    }
+}
+
+// ========================
+// Busca / remoção por comparação
+// ========================
+//
+// Mesma convenção das outras árvores: `cmp(elem)` diz onde o alvo está em
+// relação a `elem`.
+
+impl<T, const B: usize> BTreeSet<T, B> {
+    pub(crate) fn find_by(&self, cmp: impl Fn(&T) -> Ordering) -> Option<&T> {
+        let cmp = metrics::counted_cmp(cmp);
+        let mut node = &self.root;
+
+        loop {
+            match node.search(&cmp) {
+                Ok(i) => return Some(&node.keys[i]),
+                Err(_) if node.is_leaf() => return None,
+                Err(i) => node = &node.children[i],
+            }
+        }
+    }
+
+    pub(crate) fn remove_by(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+        let cmp = metrics::counted_cmp(cmp);
+        // remoção em uma passada: antes de descer para um filho, garante que
+        // ele tenha uma chave sobrando, para a remoção não precisar subir
+        let mut node = &mut self.root;
+
+        let removed = loop {
+            match node.search(&cmp) {
+                Ok(i) if node.is_leaf() => break Some(node.keys.remove(i)),
+                Ok(i) => {
+                    // chave em nó interno: troca pelo predecessor ou sucessor,
+                    // o que vier de um filho com folga; sem folga, funde
+                    if node.children[i].keys.len() > Self::MIN_KEYS {
+                        let pred = Self::remove_max(&mut node.children[i]);
+                        break Some(std::mem::replace(&mut node.keys[i], pred));
+                    }
+                    if node.children[i + 1].keys.len() > Self::MIN_KEYS {
+                        let succ = Self::remove_min(&mut node.children[i + 1]);
+                        break Some(std::mem::replace(&mut node.keys[i], succ));
+                    }
+                    Self::merge_children(node, i);
+                    node = &mut node.children[i];
+                }
+                Err(_) if node.is_leaf() => break None,
+                Err(i) => {
+                    let i = Self::fill_child(node, i);
+                    node = &mut node.children[i];
+                }
+            }
+        };
+
+        self.shrink_root();
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+}
+
+impl<T: Ord, const B: usize> BTreeSet<T, B> {
+    /// Insere o elemento se ele ainda não existe. Retorna `true` se inseriu.
+    fn insert_value(&mut self, value: T) -> bool {
+        // raiz cheia: sobe uma raiz nova e divide a antiga
+        // (é o único jeito de a árvore B ganhar altura)
+        if self.root.keys.len() == Self::MAX_KEYS {
+            let old_root = std::mem::replace(&mut self.root, Node::new());
+            self.root.children.push(old_root);
+            Self::split_child(&mut self.root, 0);
+        }
+
+        // inserção em uma passada: filhos cheios são divididos na descida,
+        // então sempre há espaço para a chave que sobe
+        let mut node = &mut self.root;
+        loop {
+            let i = match node.keys.binary_search_by(|k| metrics::cmp(k, &value)) {
+                Ok(_) => return false,
+                Err(i) => i,
+            };
+
+            if node.is_leaf() {
+                node.keys.insert(i, value);
+                self.len += 1;
+                return true;
+            }
+
+            let mut i = i;
+            if node.children[i].keys.len() == Self::MAX_KEYS {
+                Self::split_child(node, i);
+                // a chave do meio subiu para `keys[i]`: decide o lado
+                match metrics::cmp(&value, &node.keys[i]) {
+                    Ordering::Less => {}
+                    Ordering::Greater => i += 1,
+                    Ordering::Equal => return false,
+                }
+            }
+            node = &mut node.children[i];
+        }
+    }
+}
+
+// ========================
+// Invariantes
+// ========================
+
+impl<T: Ord, const B: usize> BTreeSet<T, B> {
+    /// Confere a estrutura da árvore: ordem, `len`, número de chaves por nó,
+    /// número de filhos e folhas todas na mesma profundidade.
+    /// Devolve a primeira regra quebrada. Custa O(n); pensado para testes.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        // devolve a profundidade das folhas da subárvore
+        fn check_rec<T>(
+            node: &Node<T>,
+            is_root: bool,
+            min: usize,
+            max: usize,
+        ) -> Result<usize, InvariantError> {
+            let keys = node.keys.len();
+            if keys > max || (!is_root && keys < min) {
+                return Err(InvariantError::NodeSize { keys, min, max });
+            }
+            if node.is_leaf() {
+                return Ok(0);
+            }
+            if node.children.len() != keys + 1 {
+                return Err(InvariantError::ChildCount {
+                    keys,
+                    children: node.children.len(),
+                });
+            }
+
+            let mut depth = None;
+            for child in &node.children {
+                let d = check_rec(child, false, min, max)?;
+                if depth.is_some_and(|depth| depth != d) {
+                    return Err(InvariantError::UnevenLeaves);
+                }
+                depth = Some(d);
+            }
+            Ok(depth.unwrap_or(0) + 1)
+        }
+
+        invariants::check_order_and_len(self.iter(), self.len)?;
+        check_rec(&self.root, true, Self::MIN_KEYS, Self::MAX_KEYS)?;
+        Ok(())
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q, const B: usize> Lookup<T, Q> for BTreeSet<T, B>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        self.find_by(|x| value.cmp(x.borrow()))
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        self.remove_by(|x| value.cmp(x.borrow()))
+    }
+}
+
+// ========================
+// Implementação de Set<T>
+// ========================
+
+impl<T: Ord, const B: usize> Set<T> for BTreeSet<T, B> {
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_value(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(BTreeSet::iter(self))
+    }
+
+    fn sorted_by(&self) -> Option<CmpFn<T>> {
+        Some(T::cmp)
+    }
+}
+
+// ========================
+// Implementação de OrderedSet<T>
+// ========================
+
+impl<T, const B: usize> BTreeSet<T, B> {
+    /// Maior chave com `before(chave)` (as chaves com `before` formam um prefixo).
+    fn last_where(&self, before: impl Fn(&T) -> bool) -> Option<&T> {
+        let mut node = &self.root;
+        let mut best = None;
+
+        loop {
+            let i = node.keys.partition_point(&before);
+            if i > 0 {
+                best = Some(&node.keys[i - 1]);
+            }
+            if node.is_leaf() {
+                return best;
+            }
+            node = &node.children[i];
+        }
+    }
+
+    /// Menor chave sem `before(chave)`.
+    fn first_where_not(&self, before: impl Fn(&T) -> bool) -> Option<&T> {
+        let mut node = &self.root;
+        let mut best = None;
+
+        loop {
+            let i = node.keys.partition_point(&before);
+            if i < node.keys.len() {
+                best = Some(&node.keys[i]);
+            }
+            if node.is_leaf() {
+                return best;
+            }
+            node = &node.children[i];
+        }
+    }
+}
+
+impl<T: Ord, const B: usize> OrderedSet<T> for BTreeSet<T, B> {
+    fn min(&self) -> Option<&T> {
+        self.first_where_not(|_| false)
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.last_where(|_| true)
+    }
+
+    fn floor(&self, value: &T) -> Option<&T> {
+        self.last_where(|x| x <= value)
+    }
+
+    fn ceiling(&self, value: &T) -> Option<&T> {
+        self.first_where_not(|x| x < value)
+    }
+
+    fn successor(&self, value: &T) -> Option<&T> {
+        self.first_where_not(|x| x <= value)
+    }
+
+    fn predecessor(&self, value: &T) -> Option<&T> {
+        self.last_where(|x| x < value)
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let min = Self::remove_min(&mut self.root);
+        self.shrink_root();
+        self.len -= 1;
+        Some(min)
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let max = Self::remove_max(&mut self.root);
+        self.shrink_root();
+        self.len -= 1;
+        Some(max)
+    }
+
+    fn range<R: RangeBounds<T>>(&self, range: R) -> Box<dyn Iterator<Item = &T> + '_> {
+        let last = match range.end_bound() {
+            Bound::Included(hi) => self.floor(hi),
+            Bound::Excluded(hi) => self.predecessor(hi),
+            Bound::Unbounded => self.max(),
+        };
+
+        let mut iter = BTreeSetIter {
+            stack: Vec::new(),
+            last,
+        };
+        match range.start_bound() {
+            Bound::Included(lo) => iter.seek(&self.root, |x| x < lo),
+            Bound::Excluded(lo) => iter.seek(&self.root, |x| x <= lo),
+            Bound::Unbounded => iter.seek(&self.root, |_| false),
+        }
+
+        // intervalo vazio (ex.: 5..3): o primeiro elemento já passa do último
+        let empty = match (iter.peek(), last) {
+            (Some(first), Some(last)) => first > last,
+            _ => true,
+        };
+        if empty {
+            iter.stack.clear();
+        }
+
+        Box::new(iter)
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+/// Percurso em ordem com pilha explícita.
+///
+/// Cada entrada `(nó, i)` diz que a próxima chave a devolver desse nó é
+/// `keys[i]` (a subárvore `children[i]` já foi visitada ou está na pilha).
+pub struct BTreeSetIter<'a, T> {
+    stack: Vec<(&'a Node<T>, usize)>,
+    last: Option<&'a T>, // para depois deste (limite de `range`)
+}
+
+impl<'a, T> BTreeSetIter<'a, T> {
+    /// Desce a partir de `node` até a primeira chave sem `before(chave)`.
+    fn seek(&mut self, mut node: &'a Node<T>, before: impl Fn(&T) -> bool) {
+        loop {
+            let i = node.keys.partition_point(&before);
+            self.stack.push((node, i));
+            if node.is_leaf() {
+                break;
+            }
+            node = &node.children[i];
+        }
+        self.skip_exhausted();
+    }
+
+    /// Tira do topo os nós que já devolveram todas as chaves.
+    fn skip_exhausted(&mut self) {
+        while let Some(&(node, i)) = self.stack.last() {
+            if i < node.keys.len() {
+                break;
+            }
+            self.stack.pop();
+        }
+    }
+
+    fn peek(&self) -> Option<&'a T> {
+        self.stack.last().map(|&(node, i)| &node.keys[i])
+    }
+}
+
+impl<'a, T> Iterator for BTreeSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, i) = self.stack.pop()?;
+        let key = &node.keys[i];
+
+        if self.last.is_some_and(|last| std::ptr::eq(last, key)) {
+            // último elemento do intervalo: nada mais a visitar
+            self.stack.clear();
+            return Some(key);
+        }
+
+        self.stack.push((node, i + 1));
+        if !node.is_leaf() {
+            // a próxima chave é a menor da subárvore à direita de `key`
+            self.seek(&node.children[i + 1], |_| false);
+        } else {
+            self.skip_exhausted();
+        }
+
+        Some(key)
+    }
+}
+
+/// Iterador em ordem que consome o conjunto.
+///
+/// A pilha guarda, para cada nó aberto, as chaves e os filhos que faltam.
+pub struct BTreeSetIntoIter<T> {
+    stack: Vec<(vec::IntoIter<T>, vec::IntoIter<Node<T>>)>,
+    remaining: usize,
+}
+
+impl<T> BTreeSetIntoIter<T> {
+    fn push_left_spine(&mut self, mut node: Node<T>) {
+        loop {
+            let mut children = node.children.into_iter();
+            let first = children.next();
+            self.stack.push((node.keys.into_iter(), children));
+
+            match first {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+    }
+}
+
+impl<T> Iterator for BTreeSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (keys, children) = self.stack.last_mut()?;
+
+            if let Some(key) = keys.next() {
+                if let Some(child) = children.next() {
+                    self.push_left_spine(child);
+                }
+                self.remaining -= 1;
+                return Some(key);
+            }
+
+            self.stack.pop();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, const B: usize> IntoIterator for BTreeSet<T, B> {
+    type Item = T;
+    type IntoIter = BTreeSetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut iter = BTreeSetIntoIter {
+            stack: Vec::new(),
+            remaining: self.len,
+        };
+        iter.push_left_spine(self.root);
+        iter
+    }
+}
+
+impl<'a, T, const B: usize> IntoIterator for &'a BTreeSet<T, B> {
+    type Item = &'a T;
+    type IntoIter = BTreeSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord, const B: usize> FromIterator<T> for BTreeSet<T, B> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord, const B: usize> Extend<T> for BTreeSet<T, B> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T, const B: usize> crate::set::SetName for BTreeSet<T, B> {
+    /// `"BTreeSet<B>"`, com o grau: as curvas de graus diferentes não se
+    /// misturam no CSV nem no gráfico.
+    fn name() -> String {
+        format!("BTreeSet<{B}>")
+    }
+}
+
+impl<T, const B: usize> Default for BTreeSet<T, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
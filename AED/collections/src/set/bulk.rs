@@ -0,0 +1,49 @@
+//! Construção em lote a partir de entrada já ordenada.
+//!
+//! Inserir n chaves crescentes uma a uma numa AVL ou numa rubro-negra custa
+//! O(n log n) comparações e dispara rotações o tempo todo. Com a entrada
+//! ordenada dá para montar a árvore final direto, em O(n): `BstAvlSet` e
+//! `BstRBSet` expõem `from_sorted_iter` (e `from_sorted_iter_by`, na ordem
+//! de um comparador), que confere a ordem enquanto lê e devolve
+//! `UnsortedError` se ela não for estritamente crescente.
+
+use crate::set::comparator::{self, Comparator};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A entrada de `from_sorted_iter` não é estritamente crescente.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct UnsortedError {
+    /// Índice do primeiro elemento menor ou igual ao anterior.
+    pub position: usize,
+}
+
+impl fmt::Display for UnsortedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "entrada fora de ordem: o elemento {} não é maior que o anterior",
+            self.position
+        )
+    }
+}
+
+impl std::error::Error for UnsortedError {}
+
+/// Lê a entrada inteira, conferindo que é estritamente crescente na ordem
+/// de `cmp` (repetidos também são recusados).
+pub(crate) fn collect_sorted<T, C: Comparator<T>>(
+    iter: impl IntoIterator<Item = T>,
+    cmp: &C,
+) -> Result<Vec<T>, UnsortedError> {
+    let iter = iter.into_iter();
+    let mut values: Vec<T> = Vec::with_capacity(iter.size_hint().0);
+
+    for value in iter {
+        if values.last().is_some_and(|prev| comparator::counted(cmp, prev, &value) != Ordering::Less) {
+            return Err(UnsortedError { position: values.len() });
+        }
+        values.push(value);
+    }
+    Ok(values)
+}
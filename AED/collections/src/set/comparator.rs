@@ -0,0 +1,105 @@
+//! Ordem das árvores de busca.
+//!
+//! `BstSet`, `BstAvlSet` e `BstRBSet` recebem a ordem como parâmetro de
+//! tipo `C: Comparator<T>`, com `NaturalOrder` (o `Ord` de `T`) como padrão.
+//! Todas as decisões da árvore passam pelo comparador: descida na inserção,
+//! busca e remoção, consultas de `OrderedSet` e a conferência de ordem em
+//! `check_invariants`. Dois elementos que o comparador considera iguais são
+//! o mesmo elemento do conjunto.
+//!
+//! ```
+//! use collections::set::{BstAvlSet, ByKey, NaturalOrder, ReverseOrder, Set};
+//!
+//! // decrescente
+//! let mut s = BstAvlSet::with_comparator(ReverseOrder(NaturalOrder));
+//! s.extend([1, 3, 2]);
+//! assert_eq!(s.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+//!
+//! // sem diferenciar maiúsculas: "Ana" e "ANA" são o mesmo elemento
+//! let mut nomes = BstAvlSet::with_comparator(ByKey(|s: &String| s.to_lowercase()));
+//! assert!(nomes.insert("Ana".to_string()));
+//! assert!(!nomes.insert("ANA".to_string()));
+//!
+//! // qualquer closure `Fn(&T, &T) -> Ordering`
+//! let mut pares = BstAvlSet::with_comparator(|a: &(i32, &str), b: &(i32, &str)| a.1.cmp(b.1));
+//! pares.insert((2, "b"));
+//! pares.insert((1, "a"));
+//! assert_eq!(pares.iter().next(), Some(&(1, "a")));
+//! ```
+//!
+//! O comparador precisa ser uma ordem total consistente (como `Ord`):
+//! se ele mudar de ideia sobre dois elementos, a árvore fica inválida.
+
+use crate::metrics::{self, Counter};
+use crate::set::CmpFn;
+use std::cmp::Ordering;
+
+/// Ordem total sobre `T`.
+pub trait Comparator<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+
+    /// Se esta ordem é exatamente o `Ord` de `T`, devolve `T::cmp`.
+    ///
+    /// As operações de conjunto só intercalam dois conjuntos ordenados
+    /// quando os dois usam a mesma ordem; como não dá para comparar
+    /// comparadores quaisquer, só a ordem natural participa (ver
+    /// `Set::sorted_by`). As outras caem na busca elemento a elemento.
+    fn natural_cmp(&self) -> Option<CmpFn<T>> {
+        None
+    }
+}
+
+/// Ordem natural: o `Ord` de `T`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct NaturalOrder;
+
+impl<T: Ord> Comparator<T> for NaturalOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+
+    fn natural_cmp(&self) -> Option<CmpFn<T>> {
+        Some(T::cmp)
+    }
+}
+
+/// Inverte outra ordem (por padrão, a natural).
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ReverseOrder<C = NaturalOrder>(pub C);
+
+impl<T, C: Comparator<T>> Comparator<T> for ReverseOrder<C> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0.compare(b, a)
+    }
+}
+
+/// Compara pela chave extraída com `F` (ex.: um campo, ou a string em
+/// minúsculas). A chave é recalculada a cada comparação.
+#[derive(Clone, Copy, Debug)]
+pub struct ByKey<F>(pub F);
+
+impl<T, K, F> Comparator<T> for ByKey<F>
+where
+    F: Fn(&T) -> K,
+    K: Ord,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
+/// Closures e funções `Fn(&T, &T) -> Ordering`.
+impl<T, F> Comparator<T> for F
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// `cmp.compare(a, b)`, contando a comparação (feature `metrics`).
+pub(crate) fn counted<T, C: Comparator<T>>(cmp: &C, a: &T, b: &T) -> Ordering {
+    metrics::record(Counter::Comparisons);
+    cmp.compare(a, b)
+}
@@ -0,0 +1,102 @@
+//! Exportação das árvores binárias para Graphviz (DOT).
+//!
+//! `BstSet`, `BstAvlSet` e `BstRBSet` expõem `to_dot()`. O percurso e o
+//! formato ficam aqui; cada árvore só diz o que acrescentar ao rótulo do nó
+//! (altura e fator de balanceamento na AVL) e aos atributos da aresta que
+//! chega nele (cor do link na rubro-negra).
+//!
+//! Filhos vazios aparecem como pontos, para que esquerda e direita fiquem
+//! visíveis mesmo em nós com um filho só:
+//!
+//! ```text
+//! cargo run --bin tree_dot -- avl saida/ +5 +3 +8 -5
+//! dot -Tpng saida/step_001.dot -o passo1.png
+//! ```
+
+use crate::set::bst_nav::BstNode;
+use std::fmt::{Display, Write};
+
+/// Gera o digraph da árvore `root`.
+///
+/// - `extra_label(nó)`: linha extra no rótulo (vazia = nenhuma);
+/// - `edge_attrs(filho)`: atributos da aresta pai → filho (vazio = padrão).
+///
+/// Percorre com pilha explícita: uma `BstSet` degenerada não estoura a pilha.
+pub(crate) fn to_dot<T, N>(
+    name: &str,
+    root: Option<&N>,
+    extra_label: impl Fn(&N) -> String,
+    edge_attrs: impl Fn(&N) -> &'static str,
+) -> String
+where
+    T: Display,
+    N: BstNode<T>,
+{
+    let mut out = String::new();
+    // `write!` em String não falha
+    let _ = writeln!(out, "digraph {name} {{");
+    // `ordering=out` mantém o filho esquerdo à esquerda no desenho
+    let _ = writeln!(out, "    graph [ordering=out];");
+    let _ = writeln!(out, "    node [shape=circle, fontname=\"Helvetica\"];");
+
+    let mut next_id = 0usize;
+    let mut nulls = 0usize;
+    let mut stack: Vec<(&N, usize)> = Vec::new();
+
+    if let Some(root) = root {
+        stack.push((root, next_id));
+        next_id += 1;
+    }
+
+    while let Some((node, id)) = stack.pop() {
+        let mut label = escape(&node.elem().to_string());
+        let extra = extra_label(node);
+        if !extra.is_empty() {
+            label.push_str("\\n");
+            label.push_str(&escape(&extra));
+        }
+        let _ = writeln!(out, "    n{id} [label=\"{label}\"];");
+
+        // direita é empilhada antes para a esquerda sair primeiro no arquivo
+        let mut children = Vec::with_capacity(2);
+        for child in [node.left(), node.right()] {
+            match child {
+                Some(child) => {
+                    let attrs = edge_attrs(child);
+                    if attrs.is_empty() {
+                        let _ = writeln!(out, "    n{id} -> n{next_id};");
+                    } else {
+                        let _ = writeln!(out, "    n{id} -> n{next_id} [{attrs}];");
+                    }
+                    children.push((child, next_id));
+                    next_id += 1;
+                }
+                None => {
+                    let _ = writeln!(out, "    null{nulls} [shape=point];");
+                    let _ = writeln!(out, "    n{id} -> null{nulls};");
+                    nulls += 1;
+                }
+            }
+        }
+        stack.extend(children.into_iter().rev());
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Escapa aspas e barras para uso dentro de uma string DOT.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
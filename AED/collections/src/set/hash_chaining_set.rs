@@ -1,6 +1,11 @@
-use crate::set::Set;
+use crate::metrics::{self, Counter};
+use crate::set::{Lookup, Set};
+use std::borrow::Borrow;
 use std::collections::hash_map::RandomState;
-use std::hash::{BuildHasher, Hash, Hasher};
+use std::hash::{BuildHasher, Hash};
+use std::iter::{Chain, Flatten};
+use std::marker::PhantomData;
+use std::{slice, vec};
 
 /// Conjunto implementado com tabela de hash usando encadeamento.
 ///
@@ -8,11 +13,25 @@ use std::hash::{BuildHasher, Hash, Hasher};
 /// - Cada lista contém os elementos que caíram naquele bucket.
 /// - Não permite elementos repetidos.
 /// - `S` é o tipo responsável por construir hashers (ex.: `RandomState`, `FxBuildHasher`, etc.).
+/// - Capacidade inicial, limites de carga, fator de crescimento, função de
+///   índice e modo de rehash são configuráveis por `HashChainingSet::builder()`.
 #[derive(Debug)]
 pub struct HashChainingSet<T, S = RandomState> {
     buckets: Vec<Vec<T>>,
+    // Número de buckets da tabela atual. No começo de um rehash incremental,
+    // `buckets` ainda tem menos posições: elas são criadas aos poucos e, até
+    // ficarem todas prontas, os elementos continuam em `old`.
+    bucket_count: usize,
+    // Rehash incremental: tabela antiga ainda em migração (vazia fora dela).
+    // A migração tira buckets do fim, então `old` só tem os que faltam
+    // migrar; `old_count` é o tamanho original, usado para achar o índice.
+    old: Vec<Vec<T>>,
+    old_count: usize,
+    // Buckets criados ou migrados por operação na migração atual.
+    step: usize,
     len: usize,
     hash_builder: S,
+    policy: Policy,
 }
 
 // Construtor padrão: usa o mesmo hasher do HashMap (`RandomState`)
@@ -20,6 +39,251 @@ impl<T> HashChainingSet<T, RandomState> {
     pub fn new() -> Self {
         Self::with_hasher(RandomState::new())
     }
+
+    /// Configura a tabela antes de criar. Ex.:
+    ///
+    /// ```
+    /// use collections::set::{HashChainingSet, IndexFn};
+    ///
+    /// let s: HashChainingSet<u64> = HashChainingSet::builder()
+    ///     .initial_capacity(1000)
+    ///     .max_load_factor(1.0)
+    ///     .min_load_factor(0.1)
+    ///     .growth_factor(1.5)
+    ///     .index_fn(IndexFn::Fibonacci)
+    ///     .build();
+    /// assert!(s.stats().buckets >= 1000);
+    /// ```
+    pub fn builder() -> HashChainingSetBuilder<T, RandomState> {
+        HashChainingSetBuilder::new()
+    }
+}
+
+// ========================
+// Política de crescimento
+// ========================
+
+/// Como o hash vira índice de bucket.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum IndexFn {
+    /// `h % buckets`. Aceita qualquer número de buckets.
+    #[default]
+    Modulo,
+    /// `h & (buckets - 1)`: só os bits baixos do hash. Rápido, mas um
+    /// hasher fraco nesses bits gera aglomeração. Buckets em potência de 2.
+    Mask,
+    /// Hashing de Fibonacci (Knuth): multiplica por 2^64/φ e usa os bits
+    /// altos, que misturam todos os bits do hash. Buckets em potência de 2.
+    Fibonacci,
+}
+
+/// Como a tabela é reconstruída quando cresce ou encolhe.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Rehash {
+    /// Move todos os elementos de uma vez: a operação que dispara o
+    /// redimensionamento custa O(n).
+    #[default]
+    AllAtOnce,
+    /// Mantém as duas tabelas e espalha o trabalho pelos `insert`/`remove`
+    /// seguintes: cada um cria alguns buckets vazios da tabela nova e,
+    /// quando ela está pronta, move alguns buckets da antiga, liberando-os.
+    /// Nenhuma operação faz O(n) trabalho: a tabela nova é só reservada
+    /// (sem inicializar) no começo, e a antiga é liberada bucket a bucket.
+    ///
+    /// Cada operação cuida de pelo menos `buckets_per_op` buckets, e de
+    /// mais se for preciso para terminar antes de a tabela nova chegar à
+    /// carga máxima: um novo redimensionamento espera a migração acabar.
+    /// Esse mínimo é constante: (g + 1) / (carga máxima · (g - 1)), com g o
+    /// fator de crescimento. Em troca, buscas olham as duas tabelas
+    /// enquanto a migração dura.
+    Incremental { buckets_per_op: usize },
+}
+
+impl IndexFn {
+    fn needs_power_of_two(self) -> bool {
+        !matches!(self, IndexFn::Modulo)
+    }
+}
+
+/// Parâmetros da tabela, validados pelo builder.
+#[derive(Clone, Copy, Debug)]
+struct Policy {
+    min_buckets: usize,
+    max_load_factor: f64,
+    min_load_factor: f64,
+    growth_factor: f64,
+    index: IndexFn,
+    rehash: Rehash,
+}
+
+impl Policy {
+    /// Ajusta um número de buckets às exigências da função de índice.
+    fn bucket_count(&self, n: usize) -> usize {
+        let n = n.max(self.min_buckets).max(1);
+        if self.index.needs_power_of_two() {
+            n.next_power_of_two()
+        } else {
+            n
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy {
+            min_buckets: 16,
+            max_load_factor: 0.75,
+            min_load_factor: 0.25,
+            growth_factor: 2.0,
+            index: IndexFn::Modulo,
+            rehash: Rehash::AllAtOnce,
+        }
+    }
+}
+
+/// Builder do `HashChainingSet` (ver `HashChainingSet::builder`).
+///
+/// Os valores são conferidos em `build`, que entra em pânico se a
+/// combinação não faz sentido (ex.: crescer e logo depois encolher).
+#[derive(Clone, Debug)]
+pub struct HashChainingSetBuilder<T, S = RandomState> {
+    initial_capacity: Option<usize>,
+    policy: Policy,
+    hash_builder: S,
+    _elem: PhantomData<T>,
+}
+
+impl<T> HashChainingSetBuilder<T, RandomState> {
+    pub fn new() -> Self {
+        Self {
+            initial_capacity: None,
+            policy: Policy::default(),
+            hash_builder: RandomState::new(),
+            _elem: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for HashChainingSetBuilder<T, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S> HashChainingSetBuilder<T, S> {
+    /// Quantos elementos cabem sem rehash. A tabela nunca encolhe abaixo
+    /// disso. Padrão: 16 buckets.
+    pub fn initial_capacity(mut self, capacity: usize) -> Self {
+        self.initial_capacity = Some(capacity);
+        self
+    }
+
+    /// Carga (elementos / buckets) acima da qual a tabela cresce. Padrão: 0.75.
+    pub fn max_load_factor(mut self, load: f64) -> Self {
+        self.policy.max_load_factor = load;
+        self
+    }
+
+    /// Carga abaixo da qual a tabela encolhe após remoções
+    /// (`0.0` = nunca encolhe). Padrão: 0.25.
+    pub fn min_load_factor(mut self, load: f64) -> Self {
+        self.policy.min_load_factor = load;
+        self
+    }
+
+    /// Por quanto multiplicar (ou dividir, ao encolher) o número de buckets.
+    /// Padrão: 2.0.
+    pub fn growth_factor(mut self, factor: f64) -> Self {
+        self.policy.growth_factor = factor;
+        self
+    }
+
+    /// Função de índice. Padrão: `IndexFn::Modulo`.
+    pub fn index_fn(mut self, index: IndexFn) -> Self {
+        self.policy.index = index;
+        self
+    }
+
+    /// Modo de rehash. Padrão: `Rehash::AllAtOnce`.
+    pub fn rehash(mut self, rehash: Rehash) -> Self {
+        self.policy.rehash = rehash;
+        self
+    }
+
+    /// Troca o construtor de hasher.
+    pub fn hasher<S2>(self, hash_builder: S2) -> HashChainingSetBuilder<T, S2> {
+        HashChainingSetBuilder {
+            initial_capacity: self.initial_capacity,
+            policy: self.policy,
+            hash_builder,
+            _elem: PhantomData,
+        }
+    }
+
+    /// Cria o conjunto vazio.
+    ///
+    /// # Panics
+    ///
+    /// Se `max_load_factor <= 0`, `growth_factor <= 1`, `min_load_factor < 0`
+    /// ou se `min_load_factor * growth_factor >= max_load_factor` (uma tabela
+    /// recém-encolhida já estaria acima do limite de crescimento), ou ainda
+    /// com `Rehash::Incremental { buckets_per_op: 0 }`.
+    pub fn build(self) -> HashChainingSet<T, S>
+    where
+        S: BuildHasher,
+    {
+        let mut policy = self.policy;
+        assert!(
+            policy.max_load_factor > 0.0,
+            "HashChainingSet: max_load_factor precisa ser positivo"
+        );
+        assert!(
+            policy.growth_factor > 1.0,
+            "HashChainingSet: growth_factor precisa ser maior que 1"
+        );
+        assert!(
+            policy.min_load_factor >= 0.0,
+            "HashChainingSet: min_load_factor não pode ser negativo"
+        );
+        assert!(
+            policy.min_load_factor * policy.growth_factor < policy.max_load_factor,
+            "HashChainingSet: min_load_factor * growth_factor precisa ser menor que max_load_factor"
+        );
+        assert!(
+            policy.rehash != Rehash::Incremental { buckets_per_op: 0 },
+            "HashChainingSet: buckets_per_op precisa ser positivo"
+        );
+
+        if let Some(capacity) = self.initial_capacity {
+            policy.min_buckets = (capacity as f64 / policy.max_load_factor).ceil() as usize;
+        }
+        policy.min_buckets = policy.bucket_count(policy.min_buckets);
+
+        HashChainingSet::with_policy(self.hash_builder, policy)
+    }
+}
+
+// ========================
+// Estatísticas
+// ========================
+
+/// Retrato da distribuição dos elementos pelos buckets (ver `stats`).
+///
+/// Durante um rehash incremental, os buckets ainda não migrados da tabela
+/// antiga entram na contagem (e em `pending_migration`), e os da tabela
+/// nova ainda não criados contam como vazios.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChainingStats {
+    pub len: usize,
+    pub buckets: usize,
+    /// Buckets da tabela antiga que ainda faltam migrar (0 fora de um rehash incremental).
+    pub pending_migration: usize,
+    /// `len / buckets`.
+    pub load_factor: f64,
+    /// `histogram[k]` = quantos buckets têm exatamente `k` elementos.
+    pub histogram: Vec<usize>,
+    /// Tamanho do maior bucket (`histogram.len() - 1`).
+    pub max_chain: usize,
 }
 
 // Implementação genérica em qualquer `S: BuildHasher`
@@ -27,9 +291,6 @@ impl<T, S> HashChainingSet<T, S>
 where
     S: BuildHasher,
 {
-    const INITIAL_BUCKETS: usize = 16;
-    const MAX_LOAD_FACTOR: f64 = 0.75;
-
     /// Cria um HashSet com um construtor de hasher customizado.
     ///
     /// Exemplo:
@@ -39,17 +300,26 @@ where
     ///     HashSet::with_hasher(FxBuildHasher::default());
     /// ```
     pub fn with_hasher(hash_builder: S) -> Self {
-        let buckets = (0..Self::INITIAL_BUCKETS)
-            .map(|_| Vec::new())
-            .collect();
+        Self::with_policy(hash_builder, Policy::default())
+    }
 
+    fn with_policy(hash_builder: S, policy: Policy) -> Self {
         Self {
-            buckets,
+            buckets: Self::empty_buckets(policy.min_buckets),
+            bucket_count: policy.min_buckets,
+            old: Vec::new(),
+            old_count: 0,
+            step: 0,
             len: 0,
             hash_builder,
+            policy,
         }
     }
 
+    fn empty_buckets(n: usize) -> Vec<Vec<T>> {
+        (0..n).map(|_| Vec::new()).collect()
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
@@ -58,49 +328,308 @@ where
         self.len == 0
     }
 
-    fn bucket_index(&self, value: &T) -> usize
+    /// Itera bucket por bucket (a ordem depende dos hashes).
+    pub fn iter(&self) -> HashChainingSetIter<'_, T> {
+        HashChainingSetIter {
+            inner: self.old.iter().chain(self.buckets.iter()).flatten(),
+            remaining: self.len,
+        }
+    }
+
+    /// Carga, histograma dos tamanhos de bucket e maior cadeia. Custa O(buckets).
+    pub fn stats(&self) -> ChainingStats {
+        let pending = self.old.len();
+        // buckets da tabela nova ainda não criados contam como vazios
+        let mut histogram = vec![self.bucket_count - self.buckets.len(); 1];
+        for bucket in self.old.iter().chain(&self.buckets) {
+            if bucket.len() >= histogram.len() {
+                histogram.resize(bucket.len() + 1, 0);
+            }
+            histogram[bucket.len()] += 1;
+        }
+
+        let buckets = self.bucket_count + pending;
+        ChainingStats {
+            len: self.len,
+            buckets,
+            pending_migration: pending,
+            load_factor: self.len as f64 / buckets as f64,
+            max_chain: histogram.len() - 1,
+            histogram,
+        }
+    }
+
+    pub(crate) fn hash_of<Q: Hash + ?Sized>(&self, value: &Q) -> u64 {
+        metrics::record(Counter::Hashes);
+        self.hash_builder.hash_one(value)
+    }
+
+    fn bucket_index(&self, h: u64) -> usize {
+        index_in(self.policy.index, h, self.bucket_count)
+    }
+
+    /// Bucket de `h` na tabela antiga, se ainda não foi migrado.
+    fn old_bucket_index(&self, h: u64) -> Option<usize> {
+        if self.old.is_empty() {
+            return None;
+        }
+        let idx = index_in(self.policy.index, h, self.old_count);
+        (idx < self.old.len()).then_some(idx)
+    }
+
+    /// Todos os buckets da tabela atual já foram criados?
+    fn table_ready(&self) -> bool {
+        self.buckets.len() == self.bucket_count
+    }
+
+    fn is_migrating(&self) -> bool {
+        !self.old.is_empty()
+    }
+
+    // ========================
+    // Acesso por hash + predicado (usado também pelo `HashChainingMap`)
+    // ========================
+    //
+    // `h` precisa ser o hash do elemento procurado (ver `hash_of`)
+    // e `eq` reconhece esse elemento dentro do bucket.
+
+    pub(crate) fn find_by(&self, h: u64, eq: impl Fn(&T) -> bool) -> Option<&T> {
+        let (in_old, idx, pos) = self.position(h, eq)?;
+        let table = if in_old { &self.old } else { &self.buckets };
+        Some(&table[idx][pos])
+    }
+
+    pub(crate) fn find_by_mut(&mut self, h: u64, eq: impl Fn(&T) -> bool) -> Option<&mut T> {
+        let (in_old, idx, pos) = self.position(h, eq)?;
+        let table = if in_old { &mut self.old } else { &mut self.buckets };
+        Some(&mut table[idx][pos])
+    }
+
+    pub(crate) fn remove_by(&mut self, h: u64, eq: impl Fn(&T) -> bool) -> Option<T>
     where
         T: Hash,
     {
-        let mut hasher = self.hash_builder.build_hasher();
-        value.hash(&mut hasher);
-        let h = hasher.finish() as usize;
-        h % self.buckets.len()
+        let (in_old, idx, pos) = self.position(h, eq)?;
+        let table = if in_old { &mut self.old } else { &mut self.buckets };
+        let value = table[idx].swap_remove(pos);
+        self.len -= 1;
+        self.migrate_step();
+        self.maybe_shrink();
+        Some(value)
+    }
+
+    /// Onde está o elemento: `(na tabela antiga?, bucket, posição no bucket)`.
+    /// Durante um rehash incremental, procura também na tabela antiga.
+    fn position(&self, h: u64, eq: impl Fn(&T) -> bool) -> Option<(bool, usize, usize)> {
+        let eq = probe_eq(eq);
+        let idx = self.bucket_index(h);
+        // bucket ainda não criado: vazio
+        if let Some(pos) = self.buckets.get(idx).and_then(|b| b.iter().position(&eq)) {
+            return Some((false, idx, pos));
+        }
+        let idx = self.old_bucket_index(h)?;
+        let pos = self.old[idx].iter().position(&eq)?;
+        Some((true, idx, pos))
+    }
+
+    /// Insere o elemento se ele ainda não existe.
+    /// Devolve uma referência para o elemento recém-inserido, ou `None` se já existia.
+    pub(crate) fn insert_new(&mut self, value: T) -> Option<&mut T>
+    where
+        T: Eq + Hash,
+    {
+        let h = self.hash_of(&value);
+        self.insert_new_hashed(h, value)
+    }
+
+    /// Como `insert_new`, com o hash já calculado (usado pelo `StripedHashSet`,
+    /// que precisa do hash antes para escolher a faixa).
+    pub(crate) fn insert_new_hashed(&mut self, h: u64, value: T) -> Option<&mut T>
+    where
+        T: Eq + Hash,
+    {
+        if self.find_by(h, |x| x == &value).is_some() {
+            return None;
+        }
+
+        // redimensiona antes de colocar o elemento, para que a referência
+        // devolvida aponte para a posição definitiva dele
+        self.len += 1;
+        self.migrate_step();
+        self.maybe_grow();
+
+        // enquanto a tabela nova não está pronta, a antiga continua inteira
+        // e recebe as inserções
+        let bucket = if self.table_ready() {
+            let idx = self.bucket_index(h);
+            &mut self.buckets[idx]
+        } else {
+            let idx = index_in(self.policy.index, h, self.old_count);
+            &mut self.old[idx]
+        };
+        bucket.push(value);
+        bucket.last_mut()
     }
 
     fn load_factor(&self) -> f64 {
-        if self.buckets.is_empty() {
+        if self.bucket_count == 0 {
             0.0
         } else {
-            self.len as f64 / self.buckets.len() as f64
+            self.len as f64 / self.bucket_count as f64
         }
     }
 
-    fn maybe_resize(&mut self)
+    fn maybe_grow(&mut self)
     where
-        T: Eq + Hash,
+        T: Hash,
     {
-        if self.load_factor() <= Self::MAX_LOAD_FACTOR {
+        if self.is_migrating() || self.load_factor() <= self.policy.max_load_factor {
             return;
         }
 
-        let new_bucket_count = self.buckets.len() * 2;
-        let mut new_buckets: Vec<Vec<T>> =
-            (0..new_bucket_count).map(|_| Vec::new()).collect();
+        let current = self.bucket_count;
+        let wanted = (current as f64 * self.policy.growth_factor).ceil() as usize;
+        self.resize(self.policy.bucket_count(wanted.max(current + 1)));
+    }
 
+    fn maybe_shrink(&mut self)
+    where
+        T: Hash,
+    {
+        let current = self.bucket_count;
+        if self.is_migrating()
+            || current <= self.policy.min_buckets
+            || self.load_factor() >= self.policy.min_load_factor
+        {
+            return;
+        }
+
+        let wanted = (current as f64 / self.policy.growth_factor).floor() as usize;
+        let new_count = self.policy.bucket_count(wanted);
+        if new_count < current {
+            self.resize(new_count);
+        }
+    }
+
+    fn resize(&mut self, new_bucket_count: usize)
+    where
+        T: Hash,
+    {
+        metrics::record(Counter::Rehashes);
+
+        if let Rehash::Incremental { buckets_per_op } = self.policy.rehash {
+            // a tabela atual vira a antiga; a nova só é reservada, e
+            // `migrate_step` cria os buckets dela e esvazia a antiga aos poucos
+            let new_buckets = Vec::with_capacity(new_bucket_count);
+            self.old = std::mem::replace(&mut self.buckets, new_buckets);
+            self.old_count = self.old.len();
+            self.bucket_count = new_bucket_count;
+
+            // inserções que cabem na tabela nova antes da carga máxima: o
+            // trabalho (criar a nova e migrar a antiga) precisa caber nelas
+            let max_len = (self.policy.max_load_factor * new_bucket_count as f64).floor() as usize;
+            let ops = max_len.saturating_sub(self.len).max(1);
+            let work = new_bucket_count + self.old_count;
+            self.step = buckets_per_op.max(work.div_ceil(ops));
+            return;
+        }
+
+        let mut new_buckets = Self::empty_buckets(new_bucket_count);
         for bucket in self.buckets.iter_mut() {
             for value in bucket.drain(..) {
-                let mut hasher = self.hash_builder.build_hasher();
-                value.hash(&mut hasher);
-                let h = hasher.finish() as usize;
-                let idx = h % new_bucket_count;
+                metrics::record(Counter::Hashes);
+                let h = self.hash_builder.hash_one(&value);
+                let idx = index_in(self.policy.index, h, new_bucket_count);
                 new_buckets[idx].push(value);
             }
         }
 
         self.buckets = new_buckets;
+        self.bucket_count = new_bucket_count;
         // self.len continua o mesmo
     }
+
+    /// Rehash incremental: cria os próximos buckets vazios da tabela nova
+    /// ou, com ela pronta, move os últimos buckets da antiga; `step` no
+    /// total. Chamado a cada `insert`/`remove`.
+    fn migrate_step(&mut self)
+    where
+        T: Hash,
+    {
+        if !self.is_migrating() {
+            return;
+        }
+
+        let created = self.step.min(self.bucket_count - self.buckets.len());
+        self.buckets.extend((0..created).map(|_| Vec::new()));
+
+        for _ in created..self.step {
+            // cada bucket sai da antiga e é liberado logo em seguida
+            let Some(bucket) = self.old.pop() else {
+                break;
+            };
+            for value in bucket {
+                metrics::record(Counter::Hashes);
+                let h = self.hash_builder.hash_one(&value);
+                let idx = self.bucket_index(h);
+                self.buckets[idx].push(value);
+            }
+        }
+
+        if self.old.is_empty() {
+            // vazio: liberar é O(1)
+            self.old = Vec::new();
+        }
+    }
+}
+
+/// Bucket de `h` numa tabela com `n` buckets (potência de 2 para `Mask`
+/// e `Fibonacci`).
+fn index_in(index: IndexFn, h: u64, n: usize) -> usize {
+    match index {
+        IndexFn::Modulo => (h % n as u64) as usize,
+        IndexFn::Mask => h as usize & (n - 1),
+        IndexFn::Fibonacci => {
+            // 2^64 / φ
+            const PHI: u64 = 0x9E37_79B9_7F4A_7C15;
+            let bits = n.trailing_zeros();
+            if bits == 0 {
+                0
+            } else {
+                (h.wrapping_mul(PHI) >> (64 - bits)) as usize
+            }
+        }
+    }
+}
+
+/// Cada elemento do bucket visitado conta um passo e uma comparação.
+fn probe_eq<T>(eq: impl Fn(&T) -> bool) -> impl Fn(&T) -> bool {
+    let eq = metrics::counted_eq(eq);
+    move |x| {
+        metrics::record(Counter::Probes);
+        eq(x)
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q, S> Lookup<T, Q> for HashChainingSet<T, S>
+where
+    T: Eq + Hash + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        let h = self.hash_of(value);
+        self.find_by(h, |x| x.borrow() == value)
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        let h = self.hash_of(value);
+        self.remove_by(h, |x| x.borrow() == value)
+    }
 }
 
 // ========================
@@ -113,47 +642,120 @@ where
     S: BuildHasher,
 {
     fn insert(&mut self, value: T) -> bool {
-        let idx = self.bucket_index(&value);
-        let bucket = &mut self.buckets[idx];
+        self.insert_new(value).is_some()
+    }
 
-        if bucket.iter().any(|x| x == &value) {
-            return false;
-        }
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
 
-        bucket.push(value);
-        self.len += 1;
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
 
-        self.maybe_resize();
+    fn len(&self) -> usize {
+        self.len
+    }
 
-        true
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(HashChainingSet::iter(self))
     }
+}
 
-    fn remove(&mut self, value: &T) -> bool {
-        let idx = self.bucket_index(value);
-        let bucket = &mut self.buckets[idx];
+// ========================
+// Iteradores
+// ========================
 
-        if let Some(pos) = bucket.iter().position(|x| x == value) {
-            bucket.swap_remove(pos);
-            self.len -= 1;
-            true
-        } else {
-            false
+// Tabela antiga (restante da migração) seguida da atual.
+type Both<I> = Flatten<Chain<I, I>>;
+
+pub struct HashChainingSetIter<'a, T> {
+    inner: Both<slice::Iter<'a, Vec<T>>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for HashChainingSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.inner.next()?;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Iterador que consome o conjunto, esvaziando os buckets em ordem.
+pub struct HashChainingSetIntoIter<T> {
+    inner: Both<vec::IntoIter<Vec<T>>>,
+    remaining: usize,
+}
+
+impl<T> Iterator for HashChainingSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.inner.next()?;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, S> IntoIterator for HashChainingSet<T, S> {
+    type Item = T;
+    type IntoIter = HashChainingSetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        HashChainingSetIntoIter {
+            inner: self.old.into_iter().chain(self.buckets).flatten(),
+            remaining: self.len,
         }
     }
+}
 
-    fn contains(&self, value: &T) -> bool {
-        let idx = self.bucket_index(value);
-        self.buckets[idx].iter().any(|x| x == value)
+impl<'a, T, S: BuildHasher> IntoIterator for &'a HashChainingSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = HashChainingSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
+}
 
-    fn len(&self) -> usize {
-        self.len
+impl<T, S> FromIterator<T> for HashChainingSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::with_hasher(S::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for HashChainingSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
     }
 }
 
 impl<T> crate::set::SetName for HashChainingSet<T> {
-    fn name() -> &'static str {
-        "HashChainingSet"
+    fn name() -> String {
+        "HashChainingSet".to_string()
     }
 }
 
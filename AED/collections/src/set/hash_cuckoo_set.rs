@@ -0,0 +1,433 @@
+use crate::metrics::{self, Counter};
+use crate::set::{Lookup, Set};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::iter::{Chain, Flatten};
+use std::{slice, vec};
+
+type Table<T> = Vec<Option<T>>;
+
+/// As duas tabelas em sequência, pulando posições vazias, e depois o estoque.
+type Both<I, J> = Chain<Flatten<Chain<I, I>>, J>;
+
+// ========================
+// Estrutura principal
+// ========================
+
+/// Conjunto com hashing cuco.
+///
+/// - Duas tabelas e duas funções de hash: cada elemento só pode morar em
+///   uma de duas posições, uma em cada tabela. Busca e remoção olham no
+///   máximo essas duas posições, acertando ou não.
+/// - Na inserção, se as posições estão ocupadas, o novo elemento expulsa o
+///   ocupante da primeira tabela, que vai para a sua posição na outra
+///   tabela, expulsando quem estiver lá, e assim por diante.
+/// - O caminho de expulsões é limitado: se passar do limite (provável
+///   ciclo), quem ficou sem lugar vai para um estoque (stash) pequeno,
+///   percorrido por busca linear. Quando o estoque enche, a tabela é
+///   reconstruída com novas funções de hash, crescendo se necessário.
+/// - Se nem as reconstruções separam as chaves (ex.: hash constante), as
+///   que sobram ficam no estoque, que passa a crescer: o conjunto continua
+///   correto, só que com busca linear para essas chaves.
+/// - Não permite elementos repetidos.
+/// - `S` é o tipo responsável por construir hashers (ex.: `RandomState`, `FxBuildHasher`, etc.).
+///
+/// As duas funções saem de um único hash de 64 bits, calculado com uma
+/// semente (`seed`) misturada à chave: os bits baixos indexam a primeira
+/// tabela e os altos a segunda. Trocar a semente troca as duas funções.
+#[derive(Debug)]
+pub struct HashCuckooSet<T, S = RandomState> {
+    tables: [Table<T>; 2],
+    /// Elementos sem lugar nas tabelas.
+    stash: Vec<T>,
+    /// Tamanho do estoque que dispara uma reconstrução.
+    stash_limit: usize,
+    len: usize,
+    seed: u64,
+    hash_builder: S,
+}
+
+// Construtor padrão: usa o mesmo hasher do HashMap (`RandomState`)
+impl<T> HashCuckooSet<T, RandomState> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+// Implementação genérica em qualquer `S: BuildHasher`
+impl<T, S> HashCuckooSet<T, S>
+where
+    S: BuildHasher,
+{
+    /// Posições por tabela no início.
+    const INITIAL_SLOTS: usize = 8;
+
+    /// Com duas tabelas e uma posição por bucket, a inserção começa a falhar
+    /// com frequência perto de 50% de ocupação total.
+    const MAX_LOAD_FACTOR: f64 = 0.45;
+
+    /// Tamanho máximo do caminho de expulsões antes de reconstruir.
+    const MAX_EVICTIONS: usize = 32;
+
+    /// Reconstruções seguidas que falham antes de desistir e deixar as
+    /// chaves que sobraram no estoque.
+    const MAX_REBUILDS: usize = 16;
+
+    /// Tamanho normal do estoque. Com funções de hash razoáveis, um
+    /// estoque de poucas posições já torna as reconstruções raras.
+    const STASH_SLOTS: usize = 4;
+
+    /// Cria um conjunto com um construtor de hasher customizado.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            tables: [Self::empty_table(Self::INITIAL_SLOTS), Self::empty_table(Self::INITIAL_SLOTS)],
+            stash: Vec::new(),
+            stash_limit: Self::STASH_SLOTS,
+            len: 0,
+            seed: 0,
+            hash_builder,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Número total de posições (as duas tabelas somadas).
+    pub fn capacity(&self) -> usize {
+        2 * self.tables[0].len()
+    }
+
+    /// Itera pela primeira tabela, pela segunda e pelo estoque (a ordem
+    /// depende dos hashes).
+    pub fn iter(&self) -> HashCuckooSetIter<'_, T> {
+        HashCuckooSetIter {
+            inner: self.tables[0].iter().chain(self.tables[1].iter()).flatten().chain(self.stash.iter()),
+            remaining: self.len,
+        }
+    }
+
+    fn empty_table(n: usize) -> Table<T> {
+        (0..n).map(|_| None).collect()
+    }
+
+    pub(crate) fn hash_of<Q: Hash + ?Sized>(&self, value: &Q) -> u64 {
+        metrics::record(Counter::Hashes);
+        let mut hasher = self.hash_builder.build_hasher();
+        self.seed.hash(&mut hasher);
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Posição do hash `h` na tabela `t`.
+    fn index(&self, t: usize, h: u64) -> usize {
+        let bits = if t == 0 { h } else { h.rotate_left(32) };
+        bits as usize & (self.tables[t].len() - 1)
+    }
+
+    /// Onde está o elemento com hash `h` reconhecido por `eq`: nas suas duas
+    /// posições ou, se não, no estoque.
+    fn find_slot(&self, h: u64, eq: impl Fn(&T) -> bool) -> Option<Slot> {
+        let eq = metrics::counted_eq(eq);
+        let in_table = (0..2).map(|t| (t, self.index(t, h))).find(|&(t, i)| {
+            metrics::record(Counter::Probes);
+            self.tables[t][i].as_ref().is_some_and(&eq)
+        });
+        match in_table {
+            Some((t, i)) => Some(Slot::Table(t, i)),
+            None => self.stash.iter().position(eq).map(Slot::Stash),
+        }
+    }
+
+    // ========================
+    // Acesso por hash + predicado
+    // ========================
+    //
+    // `h` precisa ser o hash do elemento procurado (ver `hash_of`)
+    // e `eq` reconhece esse elemento nas suas duas posições.
+
+    pub(crate) fn find_by(&self, h: u64, eq: impl Fn(&T) -> bool) -> Option<&T> {
+        match self.find_slot(h, eq)? {
+            Slot::Table(t, i) => self.tables[t][i].as_ref(),
+            Slot::Stash(i) => Some(&self.stash[i]),
+        }
+    }
+
+    pub(crate) fn remove_by(&mut self, h: u64, eq: impl Fn(&T) -> bool) -> Option<T> {
+        let slot = self.find_slot(h, eq)?;
+        self.len -= 1;
+        match slot {
+            Slot::Table(t, i) => self.tables[t][i].take(),
+            Slot::Stash(i) => Some(self.stash.swap_remove(i)),
+        }
+    }
+}
+
+/// Posição de um elemento: (tabela, índice) ou índice no estoque.
+#[derive(Clone, Copy)]
+enum Slot {
+    Table(usize, usize),
+    Stash(usize),
+}
+
+impl<T, S> HashCuckooSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Insere o elemento se ele ainda não existe. Retorna `true` se inseriu.
+    fn insert_value(&mut self, value: T) -> bool {
+        let h = self.hash_of(&value);
+        if self.find_slot(h, |x| x == &value).is_some() {
+            return false;
+        }
+
+        self.len += 1;
+        let slots = self.tables[0].len();
+        if self.len as f64 > self.capacity() as f64 * Self::MAX_LOAD_FACTOR {
+            self.rebuild(2 * slots, Some(value));
+        } else if let Err(homeless) = self.place(value) {
+            if self.stash.len() < self.stash_limit {
+                self.stash.push(homeless);
+            } else {
+                self.rebuild(slots, Some(homeless));
+            }
+        }
+
+        true
+    }
+
+    /// Coloca o elemento, expulsando ocupantes de uma tabela para a outra.
+    /// Se o caminho passa de `MAX_EVICTIONS`, devolve quem ficou sem lugar
+    /// (não necessariamente o elemento original).
+    fn place(&mut self, mut value: T) -> Result<(), T> {
+        let mut t = 0;
+
+        for _ in 0..Self::MAX_EVICTIONS {
+            metrics::record(Counter::Probes);
+            let i = self.index(t, self.hash_of(&value));
+            match self.tables[t][i].replace(value) {
+                None => return Ok(()),
+                Some(evicted) => value = evicted,
+            }
+            // o expulso tenta a sua posição na outra tabela
+            t = 1 - t;
+        }
+
+        Err(value)
+    }
+
+    /// Reconstrói as tabelas com `slots` posições cada e uma semente nova,
+    /// reinserindo todos os elementos (os do estoque e `pending` também).
+    /// Tenta de novo enquanto sobrarem mais elementos do que cabem no
+    /// estoque, dobrando o tamanho a cada poucas tentativas.
+    ///
+    /// Se nenhuma tentativa der certo, as que sobraram ficam no estoque, e
+    /// o limite dele passa a ser o dobro disso: a próxima reconstrução só
+    /// acontece depois de outras tantas inserções, e o custo continua
+    /// amortizado mesmo com um hasher que não separa as chaves.
+    fn rebuild(&mut self, mut slots: usize, pending: Option<T>) {
+        let mut items: Vec<T> = pending.into_iter().collect();
+
+        for attempt in 1..=Self::MAX_REBUILDS {
+            metrics::record(Counter::Rehashes);
+            items.extend(self.drain());
+            self.tables = [Self::empty_table(slots), Self::empty_table(slots)];
+            // semente nova = duas funções de hash novas
+            self.seed = self.seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+            let mut homeless = Vec::new();
+            while let Some(value) = items.pop() {
+                if let Err(h) = self.place(value) {
+                    homeless.push(h);
+                    if homeless.len() > Self::STASH_SLOTS {
+                        break;
+                    }
+                }
+            }
+
+            if homeless.len() <= Self::STASH_SLOTS {
+                self.stash = homeless;
+                self.stash_limit = Self::STASH_SLOTS;
+                return;
+            }
+            items.extend(homeless);
+
+            if attempt < Self::MAX_REBUILDS {
+                if attempt % 4 == 0 {
+                    slots *= 2;
+                }
+                continue;
+            }
+
+            // desiste: o que não coube fica no estoque
+            self.stash_limit = Self::STASH_SLOTS.max(2 * items.len());
+            self.stash = items;
+            return;
+        }
+    }
+
+    /// Esvazia as duas tabelas e o estoque, devolvendo os elementos.
+    fn drain(&mut self) -> impl Iterator<Item = T> {
+        let [a, b] = std::mem::take(&mut self.tables);
+        let stash = std::mem::take(&mut self.stash);
+        a.into_iter().chain(b).flatten().chain(stash)
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q, S> Lookup<T, Q> for HashCuckooSet<T, S>
+where
+    T: Eq + Hash + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        let h = self.hash_of(value);
+        self.find_by(h, |x| x.borrow() == value)
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        let h = self.hash_of(value);
+        self.remove_by(h, |x| x.borrow() == value)
+    }
+}
+
+// ========================
+// impl Set<T> for HashCuckooSet<T, S>
+// ========================
+
+impl<T, S> Set<T> for HashCuckooSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_value(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(HashCuckooSet::iter(self))
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+pub struct HashCuckooSetIter<'a, T> {
+    inner: Both<slice::Iter<'a, Option<T>>, slice::Iter<'a, T>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for HashCuckooSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.inner.next()?;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Iterador que consome o conjunto, pulando posições vazias.
+pub struct HashCuckooSetIntoIter<T> {
+    inner: Both<vec::IntoIter<Option<T>>, vec::IntoIter<T>>,
+    remaining: usize,
+}
+
+impl<T> Iterator for HashCuckooSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.inner.next()?;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, S> IntoIterator for HashCuckooSet<T, S> {
+    type Item = T;
+    type IntoIter = HashCuckooSetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let [a, b] = self.tables;
+        HashCuckooSetIntoIter {
+            inner: a.into_iter().chain(b).flatten().chain(self.stash),
+            remaining: self.len,
+        }
+    }
+}
+
+impl<'a, T, S: BuildHasher> IntoIterator for &'a HashCuckooSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = HashCuckooSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, S> FromIterator<T> for HashCuckooSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::with_hasher(S::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for HashCuckooSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> crate::set::SetName for HashCuckooSet<T> {
+    fn name() -> String {
+        "HashCuckooSet".to_string()
+    }
+}
+
+impl<T> Default for HashCuckooSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,52 +1,126 @@
-use crate::set::Set;
+use crate::metrics::{self, Counter};
+use crate::set::{Lookup, Set};
+use std::borrow::Borrow;
 use std::collections::hash_map::RandomState;
-use std::hash::{BuildHasher, Hash, Hasher};
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::{slice, vec};
 
-/// Conjunto implementado com tabela de hash usando encadeamento.
+// ========================
+// Sequências de sondagem
+// ========================
+
+/// Estratégia de sondagem usada pelo `HashProbingSet`.
+///
+/// Dado o hash `h` da chave, `index(h, i, mask)` devolve a posição
+/// visitada na `i`-ésima tentativa (i = 0, 1, 2, ...).
+/// A capacidade da tabela é sempre potência de 2, então `mask = cap - 1`.
+/// Toda estratégia precisa visitar todas as posições em no máximo `cap` tentativas.
+pub trait ProbeSequence {
+    /// Nome usado nos benchmarks (`SetName`).
+    const NAME: &'static str;
+
+    fn index(h: u64, i: usize, mask: usize) -> usize;
+}
+
+/// Sondagem linear: h, h+1, h+2, ...
+pub struct LinearProbing;
+
+/// Sondagem quadrática com números triangulares: h, h+1, h+3, h+6, ...
+/// Em tabelas de tamanho potência de 2 essa sequência visita todas as posições.
+pub struct QuadraticProbing;
+
+/// Hash duplo: h1, h1+h2, h1+2·h2, ...
+/// `h2` é derivado dos bits altos do hash e forçado a ser ímpar,
+/// o que garante que ele é coprimo com a capacidade (potência de 2).
+pub struct DoubleHashing;
+
+impl ProbeSequence for LinearProbing {
+    const NAME: &'static str = "HashLinearProbingSet";
+
+    fn index(h: u64, i: usize, mask: usize) -> usize {
+        (h as usize).wrapping_add(i) & mask
+    }
+}
+
+impl ProbeSequence for QuadraticProbing {
+    const NAME: &'static str = "HashQuadraticProbingSet";
+
+    fn index(h: u64, i: usize, mask: usize) -> usize {
+        let offset = i.wrapping_mul(i.wrapping_add(1)) / 2;
+        (h as usize).wrapping_add(offset) & mask
+    }
+}
+
+impl ProbeSequence for DoubleHashing {
+    const NAME: &'static str = "HashDoubleHashingSet";
+
+    fn index(h: u64, i: usize, mask: usize) -> usize {
+        let step = (h.rotate_left(32) as usize) | 1;
+        (h as usize).wrapping_add(i.wrapping_mul(step)) & mask
+    }
+}
+
+// ========================
+// Estrutura principal
+// ========================
+
+/// Posição da tabela.
+/// `Deleted` é a "lápide": a posição está livre para inserção,
+/// mas uma busca não pode parar nela (a chave procurada pode estar depois).
+#[derive(Debug)]
+enum Slot<T> {
+    Empty,
+    Deleted,
+    Occupied(T),
+}
+
+/// Conjunto implementado com tabela de hash de endereçamento aberto.
 ///
-/// - `buckets` é um vetor de listas.
-/// - Cada lista contém os elementos que caíram naquele bucket.
+/// - `slots` é um vetor plano de posições (sem listas por bucket).
+/// - Colisões são resolvidas sondando outras posições, na ordem dada por `P`
+///   (`LinearProbing`, `QuadraticProbing` ou `DoubleHashing`).
+/// - Remoção deixa uma lápide (`Deleted`); as lápides contam na carga
+///   e são descartadas no próximo rehash.
 /// - Não permite elementos repetidos.
 /// - `S` é o tipo responsável por construir hashers (ex.: `RandomState`, `FxBuildHasher`, etc.).
 #[derive(Debug)]
-pub struct HashProbingSet<T, S = RandomState> {
-    buckets: Vec<Vec<T>>,
+pub struct HashProbingSet<T, S = RandomState, P = LinearProbing> {
+    slots: Vec<Slot<T>>,
     len: usize,
+    tombstones: usize,
     hash_builder: S,
+    _probe: PhantomData<P>,
 }
 
 // Construtor padrão: usa o mesmo hasher do HashMap (`RandomState`)
-impl<T> HashProbingSet<T, RandomState> {
+impl<T, P: ProbeSequence> HashProbingSet<T, RandomState, P> {
     pub fn new() -> Self {
         Self::with_hasher(RandomState::new())
     }
 }
 
 // Implementação genérica em qualquer `S: BuildHasher`
-impl<T, S> HashProbingSet<T, S>
+impl<T, S, P> HashProbingSet<T, S, P>
 where
     S: BuildHasher,
+    P: ProbeSequence,
 {
-    const INITIAL_BUCKETS: usize = 16;
-    const MAX_LOAD_FACTOR: f64 = 0.75;
-
-    /// Cria um HashSet com um construtor de hasher customizado.
-    ///
-    /// Exemplo:
-    /// ```ignore
-    /// use rustc_hash::FxBuildHasher;
-    /// let mut s: HashSet<i32, FxBuildHasher> =
-    ///     HashSet::with_hasher(FxBuildHasher::default());
-    /// ```
-    pub fn with_hasher(hash_builder: S) -> Self {
-        let buckets = (0..Self::INITIAL_BUCKETS)
-            .map(|_| Vec::new())
-            .collect();
+    const INITIAL_SLOTS: usize = 16;
+
+    /// Endereçamento aberto degrada rápido com a carga
+    /// (o número esperado de sondagens cresce como 1/(1-α)),
+    /// então usamos um limite menor que o do `HashChainingSet`.
+    const MAX_LOAD_FACTOR: f64 = 0.5;
 
+    /// Cria um conjunto com um construtor de hasher customizado.
+    pub fn with_hasher(hash_builder: S) -> Self {
         Self {
-            buckets,
+            slots: Self::empty_slots(Self::INITIAL_SLOTS),
             len: 0,
+            tombstones: 0,
             hash_builder,
+            _probe: PhantomData,
         }
     }
 
@@ -58,106 +132,336 @@ where
         self.len == 0
     }
 
-    fn bucket_index(&self, value: &T) -> usize
+    /// Número de posições da tabela (sempre potência de 2).
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Itera pelas posições ocupadas da tabela (a ordem depende dos hashes).
+    pub fn iter(&self) -> HashProbingSetIter<'_, T> {
+        HashProbingSetIter {
+            slots: self.slots.iter(),
+            remaining: self.len,
+        }
+    }
+
+    fn empty_slots(n: usize) -> Vec<Slot<T>> {
+        (0..n).map(|_| Slot::Empty).collect()
+    }
+
+    pub(crate) fn hash_of<Q: Hash + ?Sized>(&self, value: &Q) -> u64 {
+        metrics::record(Counter::Hashes);
+        self.hash_builder.hash_one(value)
+    }
+
+    /// Procura o elemento com hash `h` reconhecido por `eq`.
+    /// Devolve `Ok(i)` se está na posição `i`; senão `Err(i)` com a posição
+    /// onde ele deveria ser inserido (a primeira lápide encontrada no
+    /// caminho, ou a posição vazia final).
+    fn find_slot(&self, h: u64, eq: impl Fn(&T) -> bool) -> Result<usize, usize> {
+        let eq = metrics::counted_eq(eq);
+        let mask = self.slots.len() - 1;
+        let mut first_free = None;
+
+        for i in 0..self.slots.len() {
+            metrics::record(Counter::Probes);
+            let idx = P::index(h, i, mask);
+            match &self.slots[idx] {
+                Slot::Empty => return Err(first_free.unwrap_or(idx)),
+                Slot::Deleted => {
+                    if first_free.is_none() {
+                        first_free = Some(idx);
+                    }
+                }
+                Slot::Occupied(x) => {
+                    if eq(x) {
+                        return Ok(idx);
+                    }
+                }
+            }
+        }
+
+        // tabela sem nenhum `Empty`: só acontece se estiver cheia de lápides
+        Err(first_free.expect("HashProbingSet: tabela cheia"))
+    }
+
+    /// Garante espaço para mais um elemento sem passar da carga máxima.
+    /// Lápides contam na carga: também ocupam posições e alongam as sondagens.
+    /// Devolve `true` se a tabela foi reconstruída (as posições mudaram).
+    fn reserve_one(&mut self) -> bool
     where
         T: Hash,
     {
-        let mut hasher = self.hash_builder.build_hasher();
-        value.hash(&mut hasher);
-        let h = hasher.finish() as usize;
-        h % self.buckets.len()
-    }
+        let cap = self.slots.len() as f64;
+        if (self.len + self.tombstones + 1) as f64 <= cap * Self::MAX_LOAD_FACTOR {
+            return false;
+        }
 
-    fn load_factor(&self) -> f64 {
-        if self.buckets.is_empty() {
-            0.0
+        // Se boa parte da carga é lápide, basta reconstruir com o mesmo tamanho.
+        let new_cap = if ((self.len + 1) as f64) < cap * Self::MAX_LOAD_FACTOR / 2.0 {
+            self.slots.len()
         } else {
-            self.len as f64 / self.buckets.len() as f64
+            self.slots.len() * 2
+        };
+
+        self.rehash(new_cap);
+        true
+    }
+
+    // ========================
+    // Acesso por hash + predicado (usado também pelo `HashProbingMap`)
+    // ========================
+    //
+    // `h` precisa ser o hash do elemento procurado (ver `hash_of`)
+    // e `eq` reconhece esse elemento na sequência de sondagem.
+
+    pub(crate) fn find_by(&self, h: u64, eq: impl Fn(&T) -> bool) -> Option<&T> {
+        match self.find_slot(h, eq) {
+            Ok(idx) => match &self.slots[idx] {
+                Slot::Occupied(x) => Some(x),
+                _ => None,
+            },
+            Err(_) => None,
+        }
+    }
+
+    pub(crate) fn find_by_mut(&mut self, h: u64, eq: impl Fn(&T) -> bool) -> Option<&mut T> {
+        match self.find_slot(h, eq) {
+            Ok(idx) => match &mut self.slots[idx] {
+                Slot::Occupied(x) => Some(x),
+                _ => None,
+            },
+            Err(_) => None,
+        }
+    }
+
+    pub(crate) fn remove_by(&mut self, h: u64, eq: impl Fn(&T) -> bool) -> Option<T> {
+        let idx = self.find_slot(h, eq).ok()?;
+        self.len -= 1;
+        self.tombstones += 1;
+        match std::mem::replace(&mut self.slots[idx], Slot::Deleted) {
+            Slot::Occupied(x) => Some(x),
+            _ => None,
         }
     }
 
-    fn maybe_resize(&mut self)
+    /// Insere o elemento se ele ainda não existe.
+    /// Devolve uma referência para o elemento recém-inserido, ou `None` se já existia.
+    pub(crate) fn insert_new(&mut self, value: T) -> Option<&mut T>
     where
         T: Eq + Hash,
     {
-        if self.load_factor() <= Self::MAX_LOAD_FACTOR {
-            return;
+        let h = self.hash_of(&value);
+        let mut idx = match self.find_slot(h, |x| x == &value) {
+            Ok(_) => return None,
+            Err(idx) => idx,
+        };
+
+        // redimensiona antes de colocar o elemento, para que a referência
+        // devolvida aponte para a posição definitiva dele
+        if self.reserve_one() {
+            idx = self.find_slot(h, |x| x == &value).unwrap_err();
         }
 
-        let new_bucket_count = self.buckets.len() * 2;
-        let mut new_buckets: Vec<Vec<T>> =
-            (0..new_bucket_count).map(|_| Vec::new()).collect();
-
-        for bucket in self.buckets.iter_mut() {
-            for value in bucket.drain(..) {
-                let mut hasher = self.hash_builder.build_hasher();
-                value.hash(&mut hasher);
-                let h = hasher.finish() as usize;
-                let idx = h % new_bucket_count;
-                new_buckets[idx].push(value);
-            }
+        if let Slot::Deleted = self.slots[idx] {
+            self.tombstones -= 1;
+        }
+        self.len += 1;
+
+        self.slots[idx] = Slot::Occupied(value);
+        match &mut self.slots[idx] {
+            Slot::Occupied(x) => Some(x),
+            _ => None,
         }
+    }
+
+    fn rehash(&mut self, new_cap: usize)
+    where
+        T: Hash,
+    {
+        metrics::record(Counter::Rehashes);
+        let old = std::mem::replace(&mut self.slots, Self::empty_slots(new_cap));
+        let mask = new_cap - 1;
+        self.tombstones = 0;
 
-        self.buckets = new_buckets;
+        for slot in old {
+            if let Slot::Occupied(value) = slot {
+                let h = self.hash_of(&value);
+                // na tabela nova não há lápides nem repetidos: basta achar um `Empty`
+                let mut i = 0;
+                loop {
+                    metrics::record(Counter::Probes);
+                    let idx = P::index(h, i, mask);
+                    if let Slot::Empty = self.slots[idx] {
+                        self.slots[idx] = Slot::Occupied(value);
+                        break;
+                    }
+                    i += 1;
+                }
+            }
+        }
         // self.len continua o mesmo
     }
 }
 
 // ========================
-// impl Set<T> for HashSet<T, S>
+// Consultas por empréstimo (ver `Lookup`)
 // ========================
 
-impl<T, S> Set<T> for HashProbingSet<T, S>
+impl<T, Q, S, P> Lookup<T, Q> for HashProbingSet<T, S, P>
+where
+    T: Eq + Hash + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+    P: ProbeSequence,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        let h = self.hash_of(value);
+        self.find_by(h, |x| x.borrow() == value)
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        let h = self.hash_of(value);
+        self.remove_by(h, |x| x.borrow() == value)
+    }
+}
+
+// ========================
+// impl Set<T> for HashProbingSet<T, S, P>
+// ========================
+
+impl<T, S, P> Set<T> for HashProbingSet<T, S, P>
 where
     T: Eq + Hash,
     S: BuildHasher,
+    P: ProbeSequence,
 {
     fn insert(&mut self, value: T) -> bool {
-        let idx = self.bucket_index(&value);
-        let bucket = &mut self.buckets[idx];
+        self.insert_new(value).is_some()
+    }
 
-        if bucket.iter().any(|x| x == &value) {
-            return false;
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(HashProbingSet::iter(self))
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+pub struct HashProbingSetIter<'a, T> {
+    slots: slice::Iter<'a, Slot<T>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for HashProbingSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.slots.by_ref() {
+            if let Slot::Occupied(value) = slot {
+                self.remaining -= 1;
+                return Some(value);
+            }
         }
+        None
+    }
 
-        bucket.push(value);
-        self.len += 1;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
 
-        self.maybe_resize();
+/// Iterador que consome o conjunto, pulando posições vazias e lápides.
+pub struct HashProbingSetIntoIter<T> {
+    slots: vec::IntoIter<Slot<T>>,
+    remaining: usize,
+}
 
-        true
+impl<T> Iterator for HashProbingSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.slots.by_ref() {
+            if let Slot::Occupied(value) = slot {
+                self.remaining -= 1;
+                return Some(value);
+            }
+        }
+        None
     }
 
-    fn remove(&mut self, value: &T) -> bool {
-        let idx = self.bucket_index(value);
-        let bucket = &mut self.buckets[idx];
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
 
-        if let Some(pos) = bucket.iter().position(|x| x == value) {
-            bucket.swap_remove(pos);
-            self.len -= 1;
-            true
-        } else {
-            false
+impl<T, S, P> IntoIterator for HashProbingSet<T, S, P> {
+    type Item = T;
+    type IntoIter = HashProbingSetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        HashProbingSetIntoIter {
+            slots: self.slots.into_iter(),
+            remaining: self.len,
         }
     }
+}
 
-    fn contains(&self, value: &T) -> bool {
-        let idx = self.bucket_index(value);
-        self.buckets[idx].iter().any(|x| x == value)
+impl<'a, T, S: BuildHasher, P: ProbeSequence> IntoIterator for &'a HashProbingSet<T, S, P> {
+    type Item = &'a T;
+    type IntoIter = HashProbingSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
+}
 
-    fn len(&self) -> usize {
-        self.len
+impl<T, S, P> FromIterator<T> for HashProbingSet<T, S, P>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    P: ProbeSequence,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::with_hasher(S::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S, P> Extend<T> for HashProbingSet<T, S, P>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    P: ProbeSequence,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
     }
 }
 
-impl<T> crate::set::SetName for HashProbingSet<T> {
-    fn name() -> &'static str {
-        "HashProbingSet"
+impl<T, P: ProbeSequence> crate::set::SetName for HashProbingSet<T, RandomState, P> {
+    fn name() -> String {
+        P::NAME.to_string()
     }
 }
 
-impl<T> Default for HashProbingSet<T> {
+impl<T, P: ProbeSequence> Default for HashProbingSet<T, RandomState, P> {
     fn default() -> Self {
         Self::new()
     }
@@ -0,0 +1,376 @@
+use crate::metrics::{self, Counter};
+use crate::set::{Lookup, Set};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::{slice, vec};
+
+// ========================
+// Estrutura principal
+// ========================
+
+/// Elemento guardado junto com o seu hash: o hash dá a posição "de casa"
+/// e, com ela, a distância de sondagem sem recalcular nada.
+#[derive(Debug)]
+struct Entry<T> {
+    hash: u64,
+    value: T,
+}
+
+/// Conjunto com endereçamento aberto e hashing Robin Hood.
+///
+/// - Sondagem linear, mas na inserção quem está mais perto de casa cede a
+///   posição para quem está mais longe ("tira dos ricos"). Isso nivela as
+///   distâncias de sondagem: a variância cai e a carga pode ir bem mais
+///   alto que no `HashProbingSet`.
+/// - As distâncias ficam em ordem ao longo de cada sequência, então uma
+///   busca malsucedida para assim que encontra alguém mais perto de casa
+///   do que ela já andou.
+/// - Remoção por deslocamento para trás (backward shift): os elementos
+///   seguintes voltam uma posição, sem lápides.
+/// - Não permite elementos repetidos.
+/// - `S` é o tipo responsável por construir hashers (ex.: `RandomState`, `FxBuildHasher`, etc.).
+#[derive(Debug)]
+pub struct HashRobinHoodSet<T, S = RandomState> {
+    slots: Vec<Option<Entry<T>>>,
+    len: usize,
+    hash_builder: S,
+}
+
+// Construtor padrão: usa o mesmo hasher do HashMap (`RandomState`)
+impl<T> HashRobinHoodSet<T, RandomState> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+// Implementação genérica em qualquer `S: BuildHasher`
+impl<T, S> HashRobinHoodSet<T, S>
+where
+    S: BuildHasher,
+{
+    const INITIAL_SLOTS: usize = 16;
+
+    /// As distâncias niveladas aguentam uma carga alta antes de as
+    /// sondagens ficarem longas.
+    const MAX_LOAD_FACTOR: f64 = 0.9;
+
+    /// Cria um conjunto com um construtor de hasher customizado.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            slots: Self::empty_slots(Self::INITIAL_SLOTS),
+            len: 0,
+            hash_builder,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Número de posições da tabela (sempre potência de 2).
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Itera pelas posições ocupadas da tabela (a ordem depende dos hashes).
+    pub fn iter(&self) -> HashRobinHoodSetIter<'_, T> {
+        HashRobinHoodSetIter {
+            slots: self.slots.iter(),
+            remaining: self.len,
+        }
+    }
+
+    fn empty_slots(n: usize) -> Vec<Option<Entry<T>>> {
+        (0..n).map(|_| None).collect()
+    }
+
+    pub(crate) fn hash_of<Q: Hash + ?Sized>(&self, value: &Q) -> u64 {
+        metrics::record(Counter::Hashes);
+        self.hash_builder.hash_one(value)
+    }
+
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    /// Distância entre a posição `idx` e a casa do hash `h`.
+    fn distance(&self, h: u64, idx: usize) -> usize {
+        idx.wrapping_sub(h as usize) & self.mask()
+    }
+
+    /// Procura o elemento com hash `h` reconhecido por `eq`.
+    fn find_slot(&self, h: u64, eq: impl Fn(&T) -> bool) -> Option<usize> {
+        let eq = metrics::counted_eq(eq);
+        let mask = self.mask();
+        let mut idx = h as usize & mask;
+
+        for dist in 0..self.slots.len() {
+            metrics::record(Counter::Probes);
+            let entry = self.slots[idx].as_ref()?;
+
+            // alguém mais perto de casa: o alvo estaria antes dele
+            if self.distance(entry.hash, idx) < dist {
+                return None;
+            }
+            if entry.hash == h && eq(&entry.value) {
+                return Some(idx);
+            }
+
+            idx = (idx + 1) & mask;
+        }
+
+        None
+    }
+
+    // ========================
+    // Acesso por hash + predicado
+    // ========================
+    //
+    // `h` precisa ser o hash do elemento procurado (ver `hash_of`)
+    // e `eq` reconhece esse elemento na sequência de sondagem.
+
+    pub(crate) fn find_by(&self, h: u64, eq: impl Fn(&T) -> bool) -> Option<&T> {
+        let idx = self.find_slot(h, eq)?;
+        self.slots[idx].as_ref().map(|e| &e.value)
+    }
+
+    pub(crate) fn remove_by(&mut self, h: u64, eq: impl Fn(&T) -> bool) -> Option<T> {
+        let mask = self.mask();
+        let mut idx = self.find_slot(h, eq)?;
+        let removed = self.slots[idx].take()?;
+
+        // backward shift: puxa os seguintes uma posição para trás até achar
+        // um vazio ou alguém que já está em casa
+        loop {
+            let next = (idx + 1) & mask;
+            match &self.slots[next] {
+                Some(entry) if self.distance(entry.hash, next) > 0 => {
+                    self.slots[idx] = self.slots[next].take();
+                    idx = next;
+                }
+                _ => break,
+            }
+        }
+
+        self.len -= 1;
+        Some(removed.value)
+    }
+
+    /// Insere o elemento se ele ainda não existe.
+    /// Devolve uma referência para o elemento recém-inserido, ou `None` se já existia.
+    pub(crate) fn insert_new(&mut self, value: T) -> Option<&mut T>
+    where
+        T: Eq + Hash,
+    {
+        let h = self.hash_of(&value);
+        if self.find_slot(h, |x| x == &value).is_some() {
+            return None;
+        }
+
+        if (self.len + 1) as f64 > self.slots.len() as f64 * Self::MAX_LOAD_FACTOR {
+            self.rehash(self.slots.len() * 2);
+        }
+
+        let idx = self.place(Entry { hash: h, value });
+        self.len += 1;
+        self.slots[idx].as_mut().map(|e| &mut e.value)
+    }
+
+    /// Coloca a entrada (que não pode estar na tabela) deslocando quem
+    /// estiver mais perto de casa. Devolve a posição onde ela ficou.
+    fn place(&mut self, mut entry: Entry<T>) -> usize {
+        let mask = self.mask();
+        let mut idx = entry.hash as usize & mask;
+        let mut dist = 0;
+        let mut placed = None;
+
+        loop {
+            metrics::record(Counter::Probes);
+            match &mut self.slots[idx] {
+                slot @ None => {
+                    *slot = Some(entry);
+                    return placed.unwrap_or(idx);
+                }
+                Some(other) => {
+                    let other_dist = idx.wrapping_sub(other.hash as usize) & mask;
+                    if other_dist < dist {
+                        // o "rico" cede a posição e segue procurando outra
+                        std::mem::swap(other, &mut entry);
+                        placed.get_or_insert(idx);
+                        dist = other_dist;
+                    }
+                }
+            }
+
+            idx = (idx + 1) & mask;
+            dist += 1;
+        }
+    }
+
+    fn rehash(&mut self, new_cap: usize) {
+        metrics::record(Counter::Rehashes);
+        let old = std::mem::replace(&mut self.slots, Self::empty_slots(new_cap));
+
+        // o hash guardado evita recalcular
+        for entry in old.into_iter().flatten() {
+            self.place(entry);
+        }
+        // self.len continua o mesmo
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q, S> Lookup<T, Q> for HashRobinHoodSet<T, S>
+where
+    T: Eq + Hash + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        let h = self.hash_of(value);
+        self.find_by(h, |x| x.borrow() == value)
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        let h = self.hash_of(value);
+        self.remove_by(h, |x| x.borrow() == value)
+    }
+}
+
+// ========================
+// impl Set<T> for HashRobinHoodSet<T, S>
+// ========================
+
+impl<T, S> Set<T> for HashRobinHoodSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_new(value).is_some()
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(HashRobinHoodSet::iter(self))
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+pub struct HashRobinHoodSetIter<'a, T> {
+    slots: slice::Iter<'a, Option<Entry<T>>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for HashRobinHoodSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.slots.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        Some(&entry.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Iterador que consome o conjunto, pulando posições vazias.
+pub struct HashRobinHoodSetIntoIter<T> {
+    slots: vec::IntoIter<Option<Entry<T>>>,
+    remaining: usize,
+}
+
+impl<T> Iterator for HashRobinHoodSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.slots.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        Some(entry.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, S> IntoIterator for HashRobinHoodSet<T, S> {
+    type Item = T;
+    type IntoIter = HashRobinHoodSetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        HashRobinHoodSetIntoIter {
+            slots: self.slots.into_iter(),
+            remaining: self.len,
+        }
+    }
+}
+
+impl<'a, T, S: BuildHasher> IntoIterator for &'a HashRobinHoodSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = HashRobinHoodSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, S> FromIterator<T> for HashRobinHoodSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::with_hasher(S::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for HashRobinHoodSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> crate::set::SetName for HashRobinHoodSet<T> {
+    fn name() -> String {
+        "HashRobinHoodSet".to_string()
+    }
+}
+
+impl<T> Default for HashRobinHoodSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,128 @@
+//! Verificação das invariantes estruturais das árvores.
+//!
+//! `BstSet`, `BstAvlSet`, `BstRBSet`, `BTreeSet`, `TreapSet`, `SplaySet` e
+//! `PersistentAvlSet` expõem `check_invariants()`, que percorre a árvore
+//! inteira e devolve a primeira regra quebrada. Serve para testes: uma
+//! rotação errada pode manter todos os elementos no lugar (e passar nos
+//! testes de pertinência) e ainda assim deixar a árvore inválida.
+
+use crate::set::comparator::{Comparator, NaturalOrder};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Regra estrutural quebrada em uma árvore de busca.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvariantError {
+    /// O percurso em ordem não é estritamente crescente.
+    /// `position` é o índice (em ordem) do primeiro elemento fora do lugar.
+    Unordered { position: usize },
+
+    /// O campo `len` não bate com o número de nós da árvore.
+    WrongLen { stored: usize, counted: usize },
+
+    /// AVL: altura guardada no nó diferente da altura real da subárvore.
+    StaleHeight { stored: i32, actual: i32 },
+
+    /// AVL: |altura(esq) - altura(dir)| > 1.
+    Unbalanced { balance: i32 },
+
+    /// Rubro-negra: a raiz é vermelha.
+    RedRoot,
+
+    /// Rubro-negra left-leaning: link vermelho à direita.
+    RightLeaningRed,
+
+    /// Rubro-negra: dois links vermelhos seguidos.
+    DoubleRed,
+
+    /// Rubro-negra: caminhos até as folhas com número diferente de pretos.
+    UnequalBlackHeight { left: usize, right: usize },
+
+    /// Árvore B: nó (fora a raiz) com chaves fora de [min, max].
+    NodeSize { keys: usize, min: usize, max: usize },
+
+    /// Árvore B: nó interno com número de filhos diferente de chaves + 1.
+    ChildCount { keys: usize, children: usize },
+
+    /// Árvore B: folhas em profundidades diferentes.
+    UnevenLeaves,
+
+    /// Treap: filho com prioridade maior que a do pai.
+    HeapOrder,
+
+    /// Treap: tamanho guardado no nó diferente do número de nós da subárvore.
+    StaleSize { stored: usize, actual: usize },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantError::Unordered { position } => {
+                write!(f, "ordem de BST violada no elemento {position} do percurso em ordem")
+            }
+            InvariantError::WrongLen { stored, counted } => {
+                write!(f, "len = {stored}, mas a árvore tem {counted} nós")
+            }
+            InvariantError::StaleHeight { stored, actual } => {
+                write!(f, "altura guardada {stored}, altura real {actual}")
+            }
+            InvariantError::Unbalanced { balance } => {
+                write!(f, "fator de balanceamento {balance} fora de [-1, 1]")
+            }
+            InvariantError::RedRoot => write!(f, "raiz vermelha"),
+            InvariantError::RightLeaningRed => write!(f, "link vermelho à direita"),
+            InvariantError::DoubleRed => write!(f, "dois links vermelhos seguidos"),
+            InvariantError::UnequalBlackHeight { left, right } => {
+                write!(f, "altura negra diferente: {left} à esquerda, {right} à direita")
+            }
+            InvariantError::NodeSize { keys, min, max } => {
+                write!(f, "nó com {keys} chaves, fora de [{min}, {max}]")
+            }
+            InvariantError::ChildCount { keys, children } => {
+                write!(f, "nó interno com {keys} chaves e {children} filhos")
+            }
+            InvariantError::UnevenLeaves => write!(f, "folhas em profundidades diferentes"),
+            InvariantError::HeapOrder => write!(f, "filho com prioridade maior que a do pai"),
+            InvariantError::StaleSize { stored, actual } => {
+                write!(f, "tamanho guardado {stored}, subárvore com {actual} nós")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvariantError {}
+
+/// Invariantes comuns a todas as árvores: percurso em ordem estritamente
+/// crescente e `len` igual ao número de elementos.
+///
+/// Recebe o percurso iterativo, então funciona mesmo em BSTs degeneradas.
+pub(crate) fn check_order_and_len<'a, T: Ord + 'a>(
+    in_order: impl Iterator<Item = &'a T>,
+    len: usize,
+) -> Result<(), InvariantError> {
+    check_order_and_len_by(in_order, len, &NaturalOrder)
+}
+
+/// `check_order_and_len` na ordem de `cmp`, para as árvores com comparador.
+pub(crate) fn check_order_and_len_by<'a, T: 'a, C: Comparator<T>>(
+    in_order: impl Iterator<Item = &'a T>,
+    len: usize,
+    cmp: &C,
+) -> Result<(), InvariantError> {
+    let mut prev: Option<&T> = None;
+    let mut counted = 0;
+
+    for elem in in_order {
+        if prev.is_some_and(|p| cmp.compare(p, elem) != Ordering::Less) {
+            return Err(InvariantError::Unordered { position: counted });
+        }
+        prev = Some(elem);
+        counted += 1;
+    }
+
+    if counted != len {
+        return Err(InvariantError::WrongLen { stored: len, counted });
+    }
+
+    Ok(())
+}
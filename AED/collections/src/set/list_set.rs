@@ -1,4 +1,6 @@
-use crate::set::Set;
+use crate::metrics::{self, Counter};
+use crate::set::{Lookup, Set};
+use std::borrow::Borrow;
 
 // Nó da lista ligada
 struct Node<T> {
@@ -22,38 +24,119 @@ impl<T> ListSet<T> {
     pub fn len(&self) -> usize {
         self.len
     }
-}
 
-impl<T: PartialEq> Set<T> for ListSet<T> {
-    fn insert(&mut self, value: T) -> bool {
-        // se já contém, não insere
-        if self.contains(&value) {
-            return false;
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Itera do elemento inserido por último até o primeiro.
+    pub fn iter(&self) -> ListSetIter<'_, T> {
+        ListSetIter {
+            next: self.head.as_deref(),
         }
+    }
+}
 
-        // insere no começo da lista (mais simples)
-        let new_node = Box::new(Node {
-            elem: value,
-            next: self.head.take(),
-        });
+// ========================
+// Iteradores
+// ========================
 
-        self.head = Some(new_node);
-        self.len += 1;
-        true
+pub struct ListSetIter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for ListSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        Some(&node.elem)
+    }
+}
+
+/// Iterador que consome o conjunto, desligando um nó de cada vez.
+pub struct ListSetIntoIter<T> {
+    set: ListSet<T>,
+}
+
+impl<T> Iterator for ListSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.set.head.take().map(|boxed_node| {
+            let Node { elem, next } = *boxed_node;
+            self.set.head = next;
+            self.set.len -= 1;
+            elem
+        })
     }
 
-    fn remove(&mut self, value: &T) -> bool {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.set.len, Some(self.set.len))
+    }
+}
+
+impl<T> IntoIterator for ListSet<T> {
+    type Item = T;
+    type IntoIter = ListSetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ListSetIntoIter { set: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ListSet<T> {
+    type Item = &'a T;
+    type IntoIter = ListSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ========================
+// Acesso por predicado (usado também pelo `ListMap`)
+// ========================
+
+impl<T> ListSet<T> {
+    pub(crate) fn find_by(&self, mut pred: impl FnMut(&T) -> bool) -> Option<&T> {
+        let mut cur = &self.head;
+        while let Some(node) = cur {
+            metrics::record(Counter::Comparisons);
+            if pred(&node.elem) {
+                return Some(&node.elem);
+            }
+            cur = &node.next;
+        }
+        None
+    }
+
+    pub(crate) fn find_by_mut(&mut self, mut pred: impl FnMut(&T) -> bool) -> Option<&mut T> {
+        let mut cur = &mut self.head;
+        while let Some(node) = cur {
+            metrics::record(Counter::Comparisons);
+            if pred(&node.elem) {
+                return Some(&mut node.elem);
+            }
+            cur = &mut node.next;
+        }
+        None
+    }
+
+    pub(crate) fn remove_by(&mut self, mut pred: impl FnMut(&T) -> bool) -> Option<T> {
         // `cur` é um ponteiro cru para o *link* atual: &mut Option<Box<Node<T>>>
         let mut cur: *mut Option<Box<Node<T>>> = &mut self.head;
 
         unsafe {
             while let Some(ref mut node) = *cur {
-                if node.elem == *value {
+                metrics::record(Counter::Comparisons);
+                if pred(&node.elem) {
                     // pula este nó ligando direto no próximo
                     let next = node.next.take();
-                    *cur = next;
+                    let removed = std::mem::replace(&mut *cur, next)?;
                     self.len -= 1;
-                    return true;
+                    return Some(removed.elem);
                 } else {
                     // avança: agora o link atual passa a ser o `next`
                     cur = &mut node.next;
@@ -61,28 +144,104 @@ impl<T: PartialEq> Set<T> for ListSet<T> {
             }
         }
 
-        false
+        None
     }
+}
 
-    fn contains(&self, value: &T) -> bool {
-        let mut cur = &self.head;
-        while let Some(node) = cur {
-            if &node.elem == value {
-                return true;
-            }
-            cur = &node.next;
+impl<T: PartialEq> ListSet<T> {
+    /// Insere o elemento se ele ainda não existe.
+    /// Devolve uma referência para o elemento recém-inserido, ou `None` se já existia.
+    pub(crate) fn insert_new(&mut self, value: T) -> Option<&mut T> {
+        // se já contém, não insere
+        if self.find_by(|x| *x == value).is_some() {
+            return None;
         }
-        false
+
+        // insere no começo da lista (mais simples)
+        let new_node = Box::new(Node {
+            elem: value,
+            next: self.head.take(),
+        });
+
+        self.len += 1;
+        Some(&mut self.head.insert(new_node).elem)
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q> Lookup<T, Q> for ListSet<T>
+where
+    T: PartialEq + Borrow<Q>,
+    Q: PartialEq + ?Sized,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        self.find_by(|x| x.borrow() == value)
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        self.remove_by(|x| x.borrow() == value)
+    }
+}
+
+// ========================
+// Implementação de Set<T>
+// ========================
+
+impl<T: PartialEq> Set<T> for ListSet<T> {
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_new(value).is_some()
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
     }
 
     fn len(&self) -> usize {
         self.len
     }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(ListSet::iter(self))
+    }
+}
+
+impl<T: PartialEq> FromIterator<T> for ListSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: PartialEq> Extend<T> for ListSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
 }
 
 impl<T> crate::set::SetName for ListSet<T> {
-    fn name() -> &'static str {
-        "ListSet"
+    fn name() -> String {
+        "ListSet".to_string()
+    }
+}
+
+// Drop iterativo, como nas outras listas ligadas: evita uma recursão
+// por nó ao destruir conjuntos grandes.
+impl<T> Drop for ListSet<T> {
+    fn drop(&mut self) {
+        let mut cur = self.head.take();
+        while let Some(mut node) = cur {
+            cur = node.next.take();
+        }
     }
 }
 
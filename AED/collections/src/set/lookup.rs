@@ -0,0 +1,89 @@
+//! Consultas por empréstimo (`Borrow`).
+//!
+//! Como no `std`: a busca aceita qualquer forma emprestada do elemento, e
+//! um `BstAvlSet<String>` responde a `&str` sem alocar uma `String`.
+//!
+//! Os métodos de `Set<T>` recebem `&T` porque o limite de `Q` depende do
+//! backend: `Ord` nas árvores, `Hash + Eq` nas tabelas, `PartialEq` na
+//! lista. O Rust não deixa um método de trait genérico escolher o limite
+//! por implementação, então `Q` vira parâmetro de um trait à parte,
+//! `Lookup<T, Q>`, que cada backend implementa com o seu limite. Código
+//! genérico pede os dois:
+//!
+//! ```
+//! use collections::set::{BstAvlSet, HashChainingSet, Lookup, Set};
+//!
+//! fn tem_fruta<S: Set<String> + Lookup<String, str>>(s: &S) -> bool {
+//!     s.get("uva").is_some()
+//! }
+//!
+//! let mut arvore = BstAvlSet::new();
+//! arvore.insert("uva".to_string());
+//! let tabela: HashChainingSet<String> = ["pera".to_string()].into_iter().collect();
+//! assert!(tem_fruta(&arvore));
+//! assert!(!tem_fruta(&tabela));
+//! ```
+//!
+//! Os nomes de `Lookup` (`get`, `take`) não colidem com os de `Set`: com os
+//! dois traits no escopo, `s.contains(x)` continuaria ambíguo mesmo com
+//! tipos diferentes. Para os tipos concretos, `contains` e `remove` com
+//! `&Q` existem como métodos próprios (gerados abaixo) e têm prioridade
+//! sobre os do trait.
+//!
+//! Nas tabelas de hash, o contrato de `Borrow` garante que `Q` e `T` têm o
+//! mesmo hash: `hash_of("uva")` cai no mesmo bucket que a `String` guardada.
+//!
+//! Nas árvores com comparador (`BstSet`, `BstAvlSet`, `BstRBSet`), só a
+//! ordem natural implementa `Lookup`: `Q: Ord` precisa concordar com a ordem
+//! da árvore, e um comparador qualquer não diz nada sobre `Q`.
+
+use crate::set::{BTreeSet, BstAvlSet, BstRBSet, BstSet, ListSet, PersistentAvlSet, Set, SkipListSet, SplaySet, TreapSet};
+use crate::set::{HashChainingSet, HashCuckooSet, HashProbingSet, HashRobinHoodSet};
+
+/// Busca e remoção por qualquer `Q` que `T` empresta (`T: Borrow<Q>`).
+pub trait Lookup<T, Q: ?Sized>: Set<T> {
+    /// Referência para o elemento guardado igual a `value`.
+    fn get(&self, value: &Q) -> Option<&T>;
+
+    /// Remove o elemento igual a `value` e o devolve.
+    fn take(&mut self, value: &Q) -> Option<T>;
+}
+
+/// `contains`/`remove` com `&Q` nos tipos concretos, por cima de `Lookup`.
+macro_rules! borrowed_contains_remove {
+    ($([$($gen:tt)*] $ty:ty;)*) => {$(
+        impl<$($gen)*> $ty {
+            /// O elemento está no conjunto? Aceita formas emprestadas (ver `Lookup`).
+            pub fn contains<Q: ?Sized>(&self, value: &Q) -> bool
+            where
+                Self: Lookup<T, Q>,
+            {
+                Lookup::get(self, value).is_some()
+            }
+
+            /// Remove o elemento. Retorna `true` se removeu.
+            pub fn remove<Q: ?Sized>(&mut self, value: &Q) -> bool
+            where
+                Self: Lookup<T, Q>,
+            {
+                Lookup::take(self, value).is_some()
+            }
+        }
+    )*};
+}
+
+borrowed_contains_remove! {
+    [T] ListSet<T>;
+    [T] BstSet<T>;
+    [T] BstAvlSet<T>;
+    [T] BstRBSet<T>;
+    [T, R] SkipListSet<T, R>;
+    [T, const B: usize] BTreeSet<T, B>;
+    [T, R] TreapSet<T, R>;
+    [T] SplaySet<T>;
+    [T] PersistentAvlSet<T>;
+    [T, S] HashChainingSet<T, S>;
+    [T, S, P] HashProbingSet<T, S, P>;
+    [T, S] HashRobinHoodSet<T, S>;
+    [T, S] HashCuckooSet<T, S>;
+}
@@ -1,3 +1,9 @@
+use std::ops::RangeBounds;
+
+use algebra::{Merge, Op};
+
+pub use algebra::CmpFn;
+
 pub trait Set<T> {
     /// Insere o elemento. Retorna `true` se de fato inseriu (não existia),
     /// `false` se o elemento já estava no conjunto.
@@ -7,6 +13,15 @@ pub trait Set<T> {
     fn remove(&mut self, value: &T) -> bool;
 
     /// Verifica se o elemento está no conjunto.
+    ///
+    /// Para consultar com uma forma emprestada (`&str` num conjunto de
+    /// `String`), veja `Lookup`.
+    ///
+    /// Recebe `&self`, mas estruturas autoajustáveis podem se reorganizar
+    /// por dentro (mutabilidade interior): o `SplaySet` traz o elemento
+    /// buscado para a raiz. O conteúdo observável não muda, e referências
+    /// já devolvidas pelo conjunto continuam válidas. Por isso esses tipos
+    /// não são `Sync`.
     fn contains(&self, value: &T) -> bool;
 
     /// Número de elementos no conjunto.
@@ -16,25 +31,195 @@ pub trait Set<T> {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Percorre os elementos. Árvores devolvem em ordem crescente;
+    /// tabelas de hash devolvem na ordem dos buckets.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a>;
+
+    /// Se `iter` devolve os elementos em ordem crescente de `T: Ord`,
+    /// devolve essa comparação. As operações de conjunto usam isso para
+    /// trocar a busca elemento a elemento por uma intercalação linear
+    /// quando os dois lados são ordenados.
+    fn sorted_by(&self) -> Option<CmpFn<T>> {
+        None
+    }
+
+    // ========================
+    // Operações de conjunto
+    // ========================
+    //
+    // Aceitam qualquer outro backend (`BstAvlSet` ∩ `HashChainingSet`, etc.).
+    // Devolvem iteradores preguiçosos sobre referências, como no std.
+
+    /// Elementos em `self` ou em `other`.
+    fn union<'a, O>(&'a self, other: &'a O) -> Box<dyn Iterator<Item = &'a T> + 'a>
+    where
+        Self: Sized,
+        O: Set<T> + ?Sized,
+        T: 'a,
+    {
+        combine(self, other, Op::Union)
+    }
+
+    /// Elementos em `self` e em `other`.
+    fn intersection<'a, O>(&'a self, other: &'a O) -> Box<dyn Iterator<Item = &'a T> + 'a>
+    where
+        Self: Sized,
+        O: Set<T> + ?Sized,
+        T: 'a,
+    {
+        combine(self, other, Op::Intersection)
+    }
+
+    /// Elementos em `self` que não estão em `other`.
+    fn difference<'a, O>(&'a self, other: &'a O) -> Box<dyn Iterator<Item = &'a T> + 'a>
+    where
+        Self: Sized,
+        O: Set<T> + ?Sized,
+        T: 'a,
+    {
+        combine(self, other, Op::Difference)
+    }
+
+    /// Elementos em exatamente um dos dois conjuntos.
+    fn symmetric_difference<'a, O>(&'a self, other: &'a O) -> Box<dyn Iterator<Item = &'a T> + 'a>
+    where
+        Self: Sized,
+        O: Set<T> + ?Sized,
+        T: 'a,
+    {
+        combine(self, other, Op::SymmetricDifference)
+    }
+
+    /// Todo elemento de `self` está em `other`?
+    fn is_subset<O>(&self, other: &O) -> bool
+    where
+        Self: Sized,
+        O: Set<T> + ?Sized,
+    {
+        if self.len() > other.len() {
+            return false;
+        }
+        match (self.sorted_by(), other.sorted_by()) {
+            (Some(cmp), Some(_)) => algebra::merge_is_subset(self.iter(), other.iter(), cmp),
+            _ => algebra::probe_is_subset(self, other),
+        }
+    }
+
+    /// Todo elemento de `other` está em `self`?
+    fn is_superset<O>(&self, other: &O) -> bool
+    where
+        Self: Sized,
+        O: Set<T>,
+    {
+        other.is_subset(self)
+    }
+
+    /// Nenhum elemento em comum?
+    fn is_disjoint<O>(&self, other: &O) -> bool
+    where
+        Self: Sized,
+        O: Set<T> + ?Sized,
+    {
+        match (self.sorted_by(), other.sorted_by()) {
+            (Some(cmp), Some(_)) => algebra::merge_is_disjoint(self.iter(), other.iter(), cmp),
+            _ => algebra::probe_is_disjoint(self, other),
+        }
+    }
+}
+
+/// Escolhe entre intercalação (dois lados ordenados) e busca.
+fn combine<'a, T, A, B>(a: &'a A, b: &'a B, op: Op) -> Box<dyn Iterator<Item = &'a T> + 'a>
+where
+    T: 'a,
+    A: Set<T> + ?Sized,
+    B: Set<T> + ?Sized,
+{
+    match (a.sorted_by(), b.sorted_by()) {
+        (Some(cmp), Some(_)) => Box::new(Merge::new(a.iter(), b.iter(), cmp, op)),
+        _ => algebra::probe(a, b, op),
+    }
+}
+
+/// Conjunto que mantém os elementos ordenados (árvores de busca).
+pub trait OrderedSet<T>: Set<T> {
+    /// Menor elemento.
+    fn min(&self) -> Option<&T>;
+
+    /// Maior elemento.
+    fn max(&self) -> Option<&T>;
+
+    /// Maior elemento `<= value`.
+    fn floor(&self, value: &T) -> Option<&T>;
+
+    /// Menor elemento `>= value`.
+    fn ceiling(&self, value: &T) -> Option<&T>;
+
+    /// Menor elemento estritamente maior que `value`.
+    fn successor(&self, value: &T) -> Option<&T>;
+
+    /// Maior elemento estritamente menor que `value`.
+    fn predecessor(&self, value: &T) -> Option<&T>;
+
+    /// Remove e devolve o menor elemento.
+    fn pop_min(&mut self) -> Option<T>;
+
+    /// Remove e devolve o maior elemento.
+    fn pop_max(&mut self) -> Option<T>;
+
+    /// Elementos dentro do intervalo, em ordem crescente.
+    /// Um intervalo vazio ou invertido (ex.: `5..3`) não devolve nada.
+    fn range<R: RangeBounds<T>>(&self, range: R) -> Box<dyn Iterator<Item = &T> + '_>;
 }
 
 pub trait SetName {
-    fn name() -> &'static str;
+    fn name() -> String;
 }
 
 pub mod list_set;
 
+mod algebra;
+mod avl;
+mod bst_nav;
+mod bulk;
+pub mod comparator;
+mod dot;
+mod invariants;
+pub mod lookup;
+#[cfg(feature = "serde")]
+mod serde_impl;
+
 pub mod bst_set;
 pub mod bst_avl_set;
 pub mod bst_rb_set;
+pub mod skip_list_set;
+pub mod btree_set;
+pub mod treap_set;
+pub mod splay_set;
+pub mod persistent_avl_set;
 
 pub mod hash_chaining_set;
 pub mod hash_probing_set;
+pub mod hash_robin_hood_set;
+pub mod hash_cuckoo_set;
+pub mod striped_hash_set;
 
 pub use list_set::ListSet;
 pub use bst_set::BstSet;
 pub use bst_avl_set::BstAvlSet;
 pub use bst_rb_set::BstRBSet;
+pub use skip_list_set::SkipListSet;
+pub use btree_set::BTreeSet;
+pub use treap_set::TreapSet;
+pub use splay_set::SplaySet;
+pub use persistent_avl_set::PersistentAvlSet;
+pub use invariants::InvariantError;
+pub use bulk::UnsortedError;
+pub use comparator::{ByKey, Comparator, NaturalOrder, ReverseOrder};
+pub use lookup::Lookup;
 
-pub use hash_chaining_set::HashChainingSet;
-pub use hash_probing_set::HashProbingSet;
+pub use hash_chaining_set::{ChainingStats, HashChainingSet, HashChainingSetBuilder, IndexFn, Rehash};
+pub use hash_probing_set::{HashProbingSet, ProbeSequence, LinearProbing, QuadraticProbing, DoubleHashing};
+pub use hash_robin_hood_set::HashRobinHoodSet;
+pub use hash_cuckoo_set::HashCuckooSet;
+pub use striped_hash_set::{ConcurrentSet, StripedHashSet};
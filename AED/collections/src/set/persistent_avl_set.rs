@@ -0,0 +1,512 @@
+use crate::metrics;
+use crate::set::avl::{rebalance, update_height, AvlNode};
+use crate::set::bst_nav::{self, BstNode, InOrder};
+use crate::set::invariants;
+use crate::set::{CmpFn, InvariantError, Lookup, OrderedSet, Set};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::RangeBounds;
+use std::rc::Rc;
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+#[derive(Clone)]
+struct Node<T> {
+    elem: T,
+    left: Link<T>,
+    right: Link<T>,
+    height: i32,
+}
+
+/// Conjunto ordenado persistente: árvore AVL com nós em `Rc`.
+/// Não permite elementos repetidos.
+///
+/// `clone()` é O(1) e devolve uma versão independente: as duas passam a
+/// compartilhar todos os nós. Uma modificação copia só o caminho da raiz
+/// até o ponto alterado (O(log n) nós, via `Rc::make_mut`); o resto
+/// continua compartilhado, e as versões antigas não mudam. Um nó que só
+/// uma versão enxerga é alterado no lugar, sem cópia.
+///
+/// `with`/`without` devolvem a versão nova e deixam `self` intacto;
+/// `insert`/`remove` (de `Set`) trocam `self` pela versão nova.
+///
+/// As rotações e o rebalanceamento são os mesmos do `BstAvlSet`. Os
+/// elementos precisam ser `Clone` para serem copiados junto com o caminho.
+/// Não é `Send`/`Sync` (`Rc`).
+pub struct PersistentAvlSet<T> {
+    root: Link<T>,
+    len: usize,
+}
+
+impl<T> PersistentAvlSet<T> {
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Itera em ordem crescente.
+    pub fn iter(&self) -> PersistentAvlSetIter<'_, T> {
+        PersistentAvlSetIter {
+            inner: InOrder::new(self.root.as_deref()),
+        }
+    }
+
+    /// As duas versões são a mesma árvore (mesma raiz)?
+    /// Duas versões vazias também contam como a mesma.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T> Clone for PersistentAvlSet<T> {
+    /// O(1): a cópia compartilha todos os nós.
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+// ========================
+// Helpers de AVL
+// ========================
+//
+// Rotações e rebalanceamento são os de `set::avl`, os mesmos do
+// `BstAvlSet`: pelo `Rc`, `node_mut` passa por `Rc::make_mut`, que copia o
+// nó antes de alterá-lo se ele for compartilhado com outra versão.
+
+impl<T: Clone> AvlNode for Node<T> {
+    type Link = Rc<Node<T>>;
+
+    fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn set_height(&mut self, height: i32) {
+        self.height = height;
+    }
+
+    fn left_link(&self) -> &Link<T> {
+        &self.left
+    }
+
+    fn right_link(&self) -> &Link<T> {
+        &self.right
+    }
+
+    fn left_link_mut(&mut self) -> &mut Link<T> {
+        &mut self.left
+    }
+
+    fn right_link_mut(&mut self) -> &mut Link<T> {
+        &mut self.right
+    }
+}
+
+/// Tira o nó do `Rc`: sem cópia se esta versão é a única dona,
+/// clonando (elemento e ponteiros dos filhos) se é compartilhado.
+fn unwrap_node<T: Clone>(node: Rc<Node<T>>) -> Node<T> {
+    Rc::unwrap_or_clone(node)
+}
+
+// Remove o nó com menor elemento da subárvore `link` e o devolve.
+fn take_min_node<T: Clone>(link: &mut Link<T>) -> Node<T> {
+    // pré-condição: link é Some
+    if link.as_ref().unwrap().left.is_none() {
+        // este é o mínimo; o filho direito sobe
+        let mut node = unwrap_node(link.take().unwrap());
+        *link = node.right.take();
+        node
+    } else {
+        let min_node = take_min_node(&mut Rc::make_mut(link.as_mut().unwrap()).left);
+        rebalance(link);
+        min_node
+    }
+}
+
+// Espelho de `take_min_node`: remove o nó com maior elemento.
+fn take_max_node<T: Clone>(link: &mut Link<T>) -> Node<T> {
+    if link.as_ref().unwrap().right.is_none() {
+        let mut node = unwrap_node(link.take().unwrap());
+        *link = node.left.take();
+        node
+    } else {
+        let max_node = take_max_node(&mut Rc::make_mut(link.as_mut().unwrap()).right);
+        rebalance(link);
+        max_node
+    }
+}
+
+// Remove a raiz da subárvore apontada por `link`
+// (sabendo que ela EXISTE) e devolve o elemento removido.
+fn remove_at_root<T: Clone>(link: &mut Link<T>) -> T {
+    let mut node = unwrap_node(link.take().unwrap());
+
+    *link = match (node.left.take(), node.right.take()) {
+        (None, None) => None,
+        (Some(left), None) => Some(left),
+        (None, Some(right)) => Some(right),
+        (Some(left), Some(right_root)) => {
+            // dois filhos: o menor da subárvore direita vira a raiz
+            let mut right_link = Some(right_root);
+            let mut min_node = take_min_node(&mut right_link);
+
+            min_node.left = Some(left);
+            min_node.right = right_link;
+            update_height(&mut min_node);
+            Some(Rc::new(min_node))
+        }
+    };
+
+    if link.is_some() {
+        rebalance(link);
+    }
+
+    node.elem
+}
+
+impl<T> BstNode<T> for Node<T> {
+    fn elem(&self) -> &T {
+        &self.elem
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+// ========================
+// Busca / remoção por comparação
+// ========================
+//
+// `cmp(elem)` diz onde o alvo está em relação a `elem`:
+// `Less` → à esquerda, `Greater` → à direita, `Equal` → achou.
+
+impl<T> PersistentAvlSet<T> {
+    pub(crate) fn find_by(&self, cmp: impl Fn(&T) -> Ordering) -> Option<&T> {
+        let cmp = metrics::counted_cmp(cmp);
+        let mut cur = &self.root;
+
+        while let Some(node) = cur {
+            match cmp(&node.elem) {
+                Ordering::Less => cur = &node.left,
+                Ordering::Greater => cur = &node.right,
+                Ordering::Equal => return Some(&node.elem),
+            }
+        }
+
+        None
+    }
+
+    /// Remove o elemento que `cmp` reconhece. Só copia o caminho se o
+    /// elemento existe: uma remoção sem efeito não toca na árvore.
+    pub(crate) fn remove_by(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<T>
+    where
+        T: Clone,
+    {
+        fn remove_rec<T: Clone>(link: &mut Link<T>, cmp: &impl Fn(&T) -> Ordering) -> T {
+            let node = Rc::make_mut(link.as_mut().expect("remove_rec: elemento sumiu"));
+
+            let removed = match cmp(&node.elem) {
+                Ordering::Less => remove_rec(&mut node.left, cmp),
+                Ordering::Greater => remove_rec(&mut node.right, cmp),
+                Ordering::Equal => return remove_at_root(link),
+            };
+
+            rebalance(link);
+            removed
+        }
+
+        self.find_by(&cmp)?;
+        let removed = remove_rec(&mut self.root, &metrics::counted_cmp(cmp));
+        self.len -= 1;
+        Some(removed)
+    }
+}
+
+impl<T: Ord + Clone> PersistentAvlSet<T> {
+    /// Insere o elemento se ele ainda não existe. Devolve `true` se inseriu.
+    fn insert_value(&mut self, value: T) -> bool {
+        fn insert_rec<T: Ord + Clone>(link: &mut Link<T>, value: T) {
+            match link {
+                Some(node) => {
+                    let node = Rc::make_mut(node);
+                    match metrics::cmp(&value, &node.elem) {
+                        Ordering::Less => insert_rec(&mut node.left, value),
+                        Ordering::Greater => insert_rec(&mut node.right, value),
+                        Ordering::Equal => unreachable!("insert_rec: elemento já existe"),
+                    }
+                    rebalance(link);
+                }
+                None => {
+                    *link = Some(Rc::new(Node {
+                        elem: value,
+                        left: None,
+                        right: None,
+                        height: 1,
+                    }));
+                }
+            }
+        }
+
+        // busca antes: se já existe, nenhum nó é copiado
+        if self.find_by(|x| value.cmp(x)).is_some() {
+            return false;
+        }
+        insert_rec(&mut self.root, value);
+        self.len += 1;
+        true
+    }
+
+    /// Versão nova com `value`; `self` não muda.
+    /// Se `value` já está presente, devolve a mesma árvore (`ptr_eq`).
+    pub fn with(&self, value: T) -> Self {
+        let mut next = self.clone();
+        next.insert_value(value);
+        next
+    }
+
+    /// Versão nova sem `value`; `self` não muda.
+    /// Se `value` não está presente, devolve a mesma árvore (`ptr_eq`).
+    pub fn without<Q>(&self, value: &Q) -> Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut next = self.clone();
+        next.remove(value);
+        next
+    }
+}
+
+// ========================
+// Invariantes
+// ========================
+
+impl<T: Ord> PersistentAvlSet<T> {
+    /// Confere a estrutura da árvore: ordem de BST, `len`, alturas
+    /// guardadas nos nós e fator de balanceamento em [-1, 1].
+    /// Devolve a primeira regra quebrada. Custa O(n); pensado para testes.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        // devolve a altura real da subárvore
+        fn check_rec<T>(link: &Link<T>) -> Result<i32, InvariantError> {
+            let Some(node) = link else {
+                return Ok(0);
+            };
+
+            let hl = check_rec(&node.left)?;
+            let hr = check_rec(&node.right)?;
+            let actual = 1 + hl.max(hr);
+
+            if node.height != actual {
+                return Err(InvariantError::StaleHeight {
+                    stored: node.height,
+                    actual,
+                });
+            }
+            if (hl - hr).abs() > 1 {
+                return Err(InvariantError::Unbalanced { balance: hl - hr });
+            }
+
+            Ok(actual)
+        }
+
+        invariants::check_order_and_len(self.iter(), self.len)?;
+        check_rec(&self.root)?;
+        Ok(())
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q> Lookup<T, Q> for PersistentAvlSet<T>
+where
+    T: Ord + Clone + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        self.find_by(|x| value.cmp(x.borrow()))
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        self.remove_by(|x| value.cmp(x.borrow()))
+    }
+}
+
+// ========================
+// Implementação de Set<T>
+// ========================
+
+impl<T: Ord + Clone> Set<T> for PersistentAvlSet<T> {
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_value(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(PersistentAvlSet::iter(self))
+    }
+
+    fn sorted_by(&self) -> Option<CmpFn<T>> {
+        Some(T::cmp)
+    }
+}
+
+// ========================
+// Implementação de OrderedSet<T>
+// ========================
+
+impl<T: Ord + Clone> OrderedSet<T> for PersistentAvlSet<T> {
+    fn min(&self) -> Option<&T> {
+        bst_nav::min(self.root.as_deref())
+    }
+
+    fn max(&self) -> Option<&T> {
+        bst_nav::max(self.root.as_deref())
+    }
+
+    fn floor(&self, value: &T) -> Option<&T> {
+        bst_nav::floor(self.root.as_deref(), value)
+    }
+
+    fn ceiling(&self, value: &T) -> Option<&T> {
+        bst_nav::ceiling(self.root.as_deref(), value)
+    }
+
+    fn successor(&self, value: &T) -> Option<&T> {
+        bst_nav::successor(self.root.as_deref(), value)
+    }
+
+    fn predecessor(&self, value: &T) -> Option<&T> {
+        bst_nav::predecessor(self.root.as_deref(), value)
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.root.as_ref()?;
+        self.len -= 1;
+        Some(take_min_node(&mut self.root).elem)
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        self.root.as_ref()?;
+        self.len -= 1;
+        Some(take_max_node(&mut self.root).elem)
+    }
+
+    fn range<R: RangeBounds<T>>(&self, range: R) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(InOrder::range(
+            self.root.as_deref(),
+            range.start_bound(),
+            range.end_bound(),
+        ))
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+pub struct PersistentAvlSetIter<'a, T> {
+    inner: InOrder<'a, T, Node<T>>,
+}
+
+impl<'a, T> Iterator for PersistentAvlSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Iterador em ordem que consome a versão. Elementos ainda compartilhados
+/// com outras versões saem clonados.
+pub struct PersistentAvlSetIntoIter<T: Ord + Clone> {
+    set: PersistentAvlSet<T>,
+}
+
+impl<T: Ord + Clone> Iterator for PersistentAvlSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.set.pop_min()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.set.len, Some(self.set.len))
+    }
+}
+
+impl<T: Ord + Clone> IntoIterator for PersistentAvlSet<T> {
+    type Item = T;
+    type IntoIter = PersistentAvlSetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PersistentAvlSetIntoIter { set: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentAvlSet<T> {
+    type Item = &'a T;
+    type IntoIter = PersistentAvlSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<T> for PersistentAvlSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord + Clone> Extend<T> for PersistentAvlSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> crate::set::SetName for PersistentAvlSet<T> {
+    fn name() -> String {
+        "PersistentAvlSet".to_string()
+    }
+}
+
+impl<T> Default for PersistentAvlSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
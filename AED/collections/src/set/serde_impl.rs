@@ -0,0 +1,126 @@
+//! `Serialize`/`Deserialize` dos conjuntos (feature `serde`).
+//!
+//! Um conjunto vira a sequência dos seus elementos, na ordem de `iter`:
+//! crescente nas árvores, a dos buckets nas tabelas de hash (que muda de
+//! uma execução para outra com `RandomState`).
+//!
+//! Na volta, qualquer ordem é aceita e repetidos são descartados, como em
+//! `FromIterator`. As árvores ordenam e deduplicam a entrada; `BstAvlSet` e
+//! `BstRBSet` são montadas direto com `from_sorted_iter`, e as outras
+//! recebem os elementos do meio para as pontas (em largura: a mediana,
+//! depois as medianas de cada metade, ...). Assim até a `BstSet`, que não
+//! se rebalanceia, sai com altura mínima, e a `SplaySet` não começa como
+//! uma lista, como aconteceria com a entrada já ordenada.
+
+use std::collections::VecDeque;
+use std::hash::{BuildHasher, Hash};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{BTreeSet, BstAvlSet, BstRBSet, BstSet, PersistentAvlSet, Set, SkipListSet, SplaySet, TreapSet};
+use super::{HashChainingSet, HashCuckooSet, HashProbingSet, HashRobinHoodSet, ListSet, ProbeSequence};
+use crate::serde_util::serialize_iter;
+
+/// Insere os elementos de `sorted` (crescente, sem repetidos) na ordem
+/// "meio primeiro".
+fn from_balanced_order<T, C: FromIterator<T>>(sorted: Vec<T>) -> C {
+    let mut out = Vec::with_capacity(sorted.len());
+    let mut slots: Vec<Option<T>> = sorted.into_iter().map(Some).collect();
+    let mut pending = VecDeque::from([(0, slots.len())]);
+    while let Some((lo, hi)) = pending.pop_front() {
+        if lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            out.extend(slots[mid].take());
+            pending.push_back((lo, mid));
+            pending.push_back((mid + 1, hi));
+        }
+    }
+    out.into_iter().collect()
+}
+
+// ========================
+// Conjuntos ordenados
+// ========================
+
+macro_rules! serde_sorted_set {
+    ($set:ty, [$($gen:tt)*], [$($bound:tt)+], $build:expr) => {
+        impl<$($gen)*> Serialize for $set
+        where
+            T: Serialize + $($bound)+,
+        {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_iter(Set::len(self), Set::iter(self), serializer)
+            }
+        }
+
+        impl<'de, $($gen)*> Deserialize<'de> for $set
+        where
+            T: Deserialize<'de> + $($bound)+,
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let mut values = Vec::<T>::deserialize(deserializer)?;
+                values.sort_unstable();
+                values.dedup();
+                Ok($build(values))
+            }
+        }
+    };
+}
+
+// `values` já ordenado e sem repetidos: `from_sorted_iter` não falha
+fn avl_from_sorted<T: Ord>(values: Vec<T>) -> BstAvlSet<T> {
+    BstAvlSet::from_sorted_iter(values).expect("entrada ordenada")
+}
+
+fn rb_from_sorted<T: Ord>(values: Vec<T>) -> BstRBSet<T> {
+    BstRBSet::from_sorted_iter(values).expect("entrada ordenada")
+}
+
+serde_sorted_set!(BstAvlSet<T>, [T], [Ord], avl_from_sorted);
+serde_sorted_set!(BstRBSet<T>, [T], [Ord], rb_from_sorted);
+serde_sorted_set!(BstSet<T>, [T], [Ord], from_balanced_order);
+serde_sorted_set!(SplaySet<T>, [T], [Ord], from_balanced_order);
+serde_sorted_set!(TreapSet<T>, [T], [Ord], from_balanced_order);
+serde_sorted_set!(SkipListSet<T>, [T], [Ord], from_balanced_order);
+serde_sorted_set!(BTreeSet<T, B>, [T, const B: usize], [Ord], from_balanced_order);
+serde_sorted_set!(PersistentAvlSet<T>, [T], [Ord + Clone], from_balanced_order);
+
+// ========================
+// Tabelas de hash e lista
+// ========================
+
+macro_rules! serde_unordered_set {
+    ($set:ty, [$($gen:tt)*], [$($where:tt)*], $($bound:tt)+) => {
+        impl<$($gen)*> Serialize for $set
+        where
+            T: Serialize + $($bound)+,
+            $($where)*
+        {
+            fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+                serialize_iter(Set::len(self), Set::iter(self), serializer)
+            }
+        }
+
+        impl<'de, $($gen)*> Deserialize<'de> for $set
+        where
+            T: Deserialize<'de> + $($bound)+,
+            $($where)*
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let values = Vec::<T>::deserialize(deserializer)?;
+                Ok(values.into_iter().collect())
+            }
+        }
+    };
+}
+
+serde_unordered_set!(ListSet<T>, [T], [], PartialEq);
+serde_unordered_set!(HashChainingSet<T, S>, [T, S], [S: BuildHasher + Default,], Eq + Hash);
+serde_unordered_set!(HashRobinHoodSet<T, S>, [T, S], [S: BuildHasher + Default,], Eq + Hash);
+serde_unordered_set!(HashCuckooSet<T, S>, [T, S], [S: BuildHasher + Default,], Eq + Hash);
+serde_unordered_set!(
+    HashProbingSet<T, S, P>,
+    [T, S, P],
+    [S: BuildHasher + Default, P: ProbeSequence,],
+    Eq + Hash
+);
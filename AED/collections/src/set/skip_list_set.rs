@@ -0,0 +1,443 @@
+use crate::metrics;
+use crate::set::{CmpFn, Lookup, OrderedSet, Set};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+/// Número máximo de níveis. Com p = 1/2, dá folga para muito mais
+/// elementos do que cabem na memória.
+pub(crate) const MAX_LEVEL: usize = 32;
+
+/// Sorteia a altura de um nó novo: cada nível extra com probabilidade 1/2.
+///
+/// Contar os bits 1 seguidos de um inteiro sorteado é o mesmo que jogar
+/// uma moeda até dar coroa, com um único sorteio.
+pub(crate) fn random_level<R: RngCore>(rng: &mut R) -> usize {
+    (rng.next_u32().trailing_ones() as usize + 1).min(MAX_LEVEL)
+}
+
+// Os nós ficam num vetor (arena) e os links são índices nesse vetor.
+// Assim não há `Box` encadeado (nem drop recursivo), e as posições livres
+// deixadas pelas remoções são reaproveitadas.
+//
+// Posição `None` em um caminho significa "cabeça da lista".
+
+struct Node<T> {
+    elem: T,
+    next: Vec<Option<usize>>, // um link por nível do nó
+}
+
+/// Caminho de uma busca: em cada nível, o último nó antes do alvo.
+type Path = [Option<usize>; MAX_LEVEL];
+
+/// Conjunto implementado como skip list.
+/// Não permite elementos repetidos.
+/// Operações são O(log n) esperado; o balanceamento é feito por sorteio,
+/// então `R` é o gerador usado (semeável, para experimentos reprodutíveis).
+pub struct SkipListSet<T, R = StdRng> {
+    head: [Option<usize>; MAX_LEVEL],
+    nodes: Vec<Option<Node<T>>>,
+    free: Vec<usize>,
+    level: usize, // níveis em uso
+    len: usize,
+    rng: R,
+}
+
+impl<T> SkipListSet<T> {
+    /// Conjunto vazio com gerador semeado pela entropia do sistema.
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Conjunto vazio com gerador de semente fixa: mesma semente e mesmas
+    /// operações dão a mesma estrutura.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<T, R> SkipListSet<T, R> {
+    pub fn with_rng(rng: R) -> Self {
+        Self {
+            head: [None; MAX_LEVEL],
+            nodes: Vec::new(),
+            free: Vec::new(),
+            level: 0,
+            len: 0,
+            rng,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Itera em ordem crescente.
+    pub fn iter(&self) -> SkipListSetIter<'_, T> {
+        SkipListSetIter {
+            nodes: &self.nodes,
+            next: self.head[0],
+            last: None,
+        }
+    }
+}
+
+// ========================
+// Helpers
+// ========================
+
+impl<T, R> SkipListSet<T, R> {
+    fn node(&self, i: usize) -> &Node<T> {
+        self.nodes[i].as_ref().expect("SkipListSet: link para posição livre")
+    }
+
+    /// Link do nível `lvl` que sai de `pos` (`None` = cabeça).
+    fn link(&self, pos: Option<usize>, lvl: usize) -> Option<usize> {
+        match pos {
+            Some(i) => self.node(i).next[lvl],
+            None => self.head[lvl],
+        }
+    }
+
+    fn link_mut(&mut self, pos: Option<usize>, lvl: usize) -> &mut Option<usize> {
+        match pos {
+            Some(i) => {
+                let node = self.nodes[i].as_mut().expect("SkipListSet: link para posição livre");
+                &mut node.next[lvl]
+            }
+            None => &mut self.head[lvl],
+        }
+    }
+
+    /// Desce do nível mais alto ao 0 avançando enquanto `before(elem)`.
+    /// `path[0]` é o último nó de toda a lista com `before(elem)`.
+    fn path_by(&self, mut before: impl FnMut(&T) -> bool) -> Path {
+        let mut path = [None; MAX_LEVEL];
+        let mut pos = None;
+
+        for lvl in (0..self.level).rev() {
+            while let Some(next) = self.link(pos, lvl) {
+                if !before(&self.node(next).elem) {
+                    break;
+                }
+                pos = Some(next);
+            }
+            path[lvl] = pos;
+        }
+
+        path
+    }
+
+    /// Tira da lista o nó `target`, que é o sucessor de `path` em todos
+    /// os níveis dele.
+    fn unlink(&mut self, path: &Path, target: usize) -> T {
+        let node = self.nodes[target].take().expect("SkipListSet: nó já removido");
+
+        for (lvl, next) in node.next.iter().enumerate() {
+            *self.link_mut(path[lvl], lvl) = *next;
+        }
+
+        // níveis do topo que ficaram vazios deixam de ser usados
+        while self.level > 0 && self.head[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+
+        self.free.push(target);
+        self.len -= 1;
+        node.elem
+    }
+
+    /// Posição do último elemento (`None` se vazio).
+    fn last_pos(&self) -> Option<usize> {
+        self.path_by(|_| true)[0]
+    }
+
+    fn elem_at(&self, pos: Option<usize>) -> Option<&T> {
+        pos.map(|i| &self.node(i).elem)
+    }
+}
+
+// ========================
+// Busca / remoção por comparação
+// ========================
+//
+// Mesma convenção das árvores: `cmp(elem)` diz onde o alvo está em relação
+// a `elem` (`Greater` → o alvo vem depois, então a busca avança).
+
+impl<T, R> SkipListSet<T, R> {
+    pub(crate) fn find_by(&self, cmp: impl Fn(&T) -> Ordering) -> Option<&T> {
+        let cmp = metrics::counted_cmp(cmp);
+        let path = self.path_by(|x| cmp(x) == Ordering::Greater);
+        let candidate = self.link(path[0], 0)?;
+        let elem = &self.node(candidate).elem;
+        (cmp(elem) == Ordering::Equal).then_some(elem)
+    }
+
+    pub(crate) fn remove_by(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+        let cmp = metrics::counted_cmp(cmp);
+        let path = self.path_by(|x| cmp(x) == Ordering::Greater);
+        let candidate = self.link(path[0], 0)?;
+        if cmp(&self.node(candidate).elem) != Ordering::Equal {
+            return None;
+        }
+        Some(self.unlink(&path, candidate))
+    }
+}
+
+impl<T: Ord, R: RngCore> SkipListSet<T, R> {
+    /// Insere o elemento se ele ainda não existe.
+    /// Devolve uma referência para o elemento recém-inserido, ou `None` se já existia.
+    pub(crate) fn insert_new(&mut self, value: T) -> Option<&mut T> {
+        let path = self.path_by(|x| metrics::cmp(x, &value) == Ordering::Less);
+        let next = self.elem_at(self.link(path[0], 0));
+        if next.is_some_and(|x| metrics::cmp(x, &value) == Ordering::Equal) {
+            return None;
+        }
+
+        let height = random_level(&mut self.rng);
+        if height > self.level {
+            // níveis novos começam na cabeça (`path` já tem `None` neles)
+            self.level = height;
+        }
+
+        let next = (0..height).map(|lvl| self.link(path[lvl], lvl)).collect();
+        let node = Node { elem: value, next };
+
+        let idx = match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = Some(node);
+                i
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+
+        for (lvl, pos) in path.iter().enumerate().take(height) {
+            *self.link_mut(*pos, lvl) = Some(idx);
+        }
+
+        self.len += 1;
+        self.nodes[idx].as_mut().map(|node| &mut node.elem)
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q, R> Lookup<T, Q> for SkipListSet<T, R>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+    R: RngCore,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        self.find_by(|x| value.cmp(x.borrow()))
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        self.remove_by(|x| value.cmp(x.borrow()))
+    }
+}
+
+// ========================
+// Implementação de Set<T>
+// ========================
+
+impl<T: Ord, R: RngCore> Set<T> for SkipListSet<T, R> {
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_new(value).is_some()
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(SkipListSet::iter(self))
+    }
+
+    fn sorted_by(&self) -> Option<CmpFn<T>> {
+        Some(T::cmp)
+    }
+}
+
+// ========================
+// Implementação de OrderedSet<T>
+// ========================
+
+impl<T: Ord, R: RngCore> OrderedSet<T> for SkipListSet<T, R> {
+    fn min(&self) -> Option<&T> {
+        self.elem_at(self.head[0])
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.elem_at(self.last_pos())
+    }
+
+    fn floor(&self, value: &T) -> Option<&T> {
+        self.elem_at(self.path_by(|x| x <= value)[0])
+    }
+
+    fn ceiling(&self, value: &T) -> Option<&T> {
+        let path = self.path_by(|x| x < value);
+        self.elem_at(self.link(path[0], 0))
+    }
+
+    fn successor(&self, value: &T) -> Option<&T> {
+        let path = self.path_by(|x| x <= value);
+        self.elem_at(self.link(path[0], 0))
+    }
+
+    fn predecessor(&self, value: &T) -> Option<&T> {
+        self.elem_at(self.path_by(|x| x < value)[0])
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        // o mínimo vem logo depois da cabeça em todos os níveis dele
+        let first = self.head[0]?;
+        Some(self.unlink(&[None; MAX_LEVEL], first))
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        let last = self.last_pos()?;
+        let path = self.path_by(|x| *x < self.node(last).elem);
+        Some(self.unlink(&path, last))
+    }
+
+    fn range<B: RangeBounds<T>>(&self, range: B) -> Box<dyn Iterator<Item = &T> + '_> {
+        let first = match range.start_bound() {
+            Bound::Included(lo) => self.link(self.path_by(|x| x < lo)[0], 0),
+            Bound::Excluded(lo) => self.link(self.path_by(|x| x <= lo)[0], 0),
+            Bound::Unbounded => self.head[0],
+        };
+        let last = match range.end_bound() {
+            Bound::Included(hi) => self.path_by(|x| x <= hi)[0],
+            Bound::Excluded(hi) => self.path_by(|x| x < hi)[0],
+            Bound::Unbounded => self.last_pos(),
+        };
+
+        // intervalo vazio (ex.: 5..3): o primeiro já passa do último
+        let next = match (self.elem_at(first), self.elem_at(last)) {
+            (Some(a), Some(b)) if a <= b => first,
+            _ => None,
+        };
+
+        Box::new(SkipListSetIter {
+            nodes: &self.nodes,
+            next,
+            last,
+        })
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+/// Percorre o nível 0 (a lista completa) em ordem crescente.
+pub struct SkipListSetIter<'a, T> {
+    nodes: &'a [Option<Node<T>>],
+    next: Option<usize>,
+    last: Option<usize>, // para depois deste (limite de `range`)
+}
+
+impl<'a, T> Iterator for SkipListSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.next?;
+        let node = self.nodes[i].as_ref()?;
+
+        self.next = if self.last == Some(i) { None } else { node.next[0] };
+        Some(&node.elem)
+    }
+}
+
+/// Iterador em ordem que consome o conjunto.
+pub struct SkipListSetIntoIter<T> {
+    nodes: Vec<Option<Node<T>>>,
+    next: Option<usize>,
+    remaining: usize,
+}
+
+impl<T> Iterator for SkipListSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes[self.next?].take()?;
+        self.next = node.next[0];
+        self.remaining -= 1;
+        Some(node.elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, R> IntoIterator for SkipListSet<T, R> {
+    type Item = T;
+    type IntoIter = SkipListSetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SkipListSetIntoIter {
+            nodes: self.nodes,
+            next: self.head[0],
+            remaining: self.len,
+        }
+    }
+}
+
+impl<'a, T, R> IntoIterator for &'a SkipListSet<T, R> {
+    type Item = &'a T;
+    type IntoIter = SkipListSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SkipListSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord, R: RngCore> Extend<T> for SkipListSet<T, R> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T, R> crate::set::SetName for SkipListSet<T, R> {
+    fn name() -> String {
+        "SkipListSet".to_string()
+    }
+}
+
+impl<T> Default for SkipListSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,587 @@
+use crate::metrics::{self, Counter};
+use crate::set::invariants;
+use crate::set::{CmpFn, InvariantError, Lookup, OrderedSet, Set};
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+// ========================
+// Nó da árvore splay
+// ========================
+//
+// A árvore se reorganiza a cada busca, e `Set::contains` só recebe `&self`.
+// Por isso os nós ficam numa arena (como no `SkipListSet`) e os links são
+// índices dentro de `Cell`: uma rotação só troca links, nunca move um
+// elemento. Referências `&T` devolvidas por buscas e iteradores continuam
+// válidas depois de qualquer splay; elementos só saem da arena com `&mut self`.
+
+struct Node<T> {
+    elem: T,
+    left: Cell<Option<usize>>,
+    right: Cell<Option<usize>>,
+}
+
+/// Conjunto implementado como árvore splay.
+/// Não permite elementos repetidos.
+/// Operações são O(log n) amortizado. Cada busca traz o elemento procurado
+/// (ou o último nó visitado) para a raiz, então chaves acessadas com
+/// frequência ficam perto do topo: acessos concentrados ou sequenciais
+/// saem mais baratos que numa árvore balanceada.
+///
+/// `contains` e `get` reorganizam a árvore mesmo recebendo `&self`
+/// (mutabilidade interior). Enquanto houver um iterador vivo sobre o
+/// conjunto, as buscas não reorganizam nada, para não bagunçar o percurso.
+pub struct SplaySet<T> {
+    nodes: Vec<Option<Node<T>>>,
+    free: Vec<usize>,
+    root: Cell<Option<usize>>,
+    len: usize,
+    readers: Cell<usize>, // iteradores vivos
+}
+
+impl<T> SplaySet<T> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: Cell::new(None),
+            len: 0,
+            readers: Cell::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Itera em ordem crescente. Não reorganiza a árvore.
+    pub fn iter(&self) -> SplaySetIter<'_, T> {
+        let mut iter = SplaySetIter::new(self, None);
+        iter.push_left(self.root.get());
+        iter
+    }
+
+    fn node(&self, i: usize) -> &Node<T> {
+        self.nodes[i].as_ref().expect("SplaySet: link para posição livre")
+    }
+
+    fn elem(&self, i: usize) -> &T {
+        &self.node(i).elem
+    }
+
+    fn left(&self, i: usize) -> Option<usize> {
+        self.node(i).left.get()
+    }
+
+    fn right(&self, i: usize) -> Option<usize> {
+        self.node(i).right.get()
+    }
+
+    fn set_left(&self, i: usize, child: Option<usize>) {
+        self.node(i).left.set(child);
+    }
+
+    fn set_right(&self, i: usize, child: Option<usize>) {
+        self.node(i).right.set(child);
+    }
+
+    fn alloc(&mut self, node: Node<T>) -> usize {
+        match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = Some(node);
+                i
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Tira o nó da arena. Ele já deve estar fora da árvore.
+    fn release(&mut self, i: usize) -> T {
+        let node = self.nodes[i].take().expect("SplaySet: nó já removido");
+        self.free.push(i);
+        self.len -= 1;
+        node.elem
+    }
+}
+
+// ========================
+// Splay
+// ========================
+//
+// `cmp(elem)` diz onde o alvo está em relação a `elem`:
+// `Less` → à esquerda, `Greater` → à direita, `Equal` → achou.
+
+impl<T> SplaySet<T> {
+    /// Splay de cima para baixo (Sleator–Tarjan): desce pelo caminho do
+    /// alvo desmontando a árvore em L (tudo menor) e R (tudo maior), com
+    /// uma rotação extra nos passos zig-zig. No fim, o último nó visitado
+    /// vira raiz com L e R pendurados. Iterativo: não usa a pilha de chamadas.
+    ///
+    /// Devolve a nova raiz e a posição do alvo em relação a ela.
+    fn splay(&self, cmp: impl Fn(&T) -> Ordering) -> Option<(usize, Ordering)> {
+        let cmp = metrics::counted_cmp(cmp);
+        let mut t = self.root.get()?;
+
+        // L cresce pela direita (l_max) e R pela esquerda (r_min)
+        let (mut l_root, mut l_max) = (None, None);
+        let (mut r_root, mut r_min) = (None, None);
+
+        let order = loop {
+            match cmp(self.elem(t)) {
+                Ordering::Less => {
+                    let Some(mut c) = self.left(t) else {
+                        break Ordering::Less;
+                    };
+                    if cmp(self.elem(c)) == Ordering::Less {
+                        // zig-zig: rotação à direita antes de descer
+                        metrics::record(Counter::Rotations);
+                        self.set_left(t, self.right(c));
+                        self.set_right(c, Some(t));
+                        t = c;
+                        match self.left(t) {
+                            Some(next) => c = next,
+                            None => break Ordering::Less,
+                        }
+                    }
+                    // t e a sua direita passam para R
+                    match r_min {
+                        Some(r) => self.set_left(r, Some(t)),
+                        None => r_root = Some(t),
+                    }
+                    r_min = Some(t);
+                    t = c;
+                }
+                Ordering::Greater => {
+                    let Some(mut c) = self.right(t) else {
+                        break Ordering::Greater;
+                    };
+                    if cmp(self.elem(c)) == Ordering::Greater {
+                        // zag-zag: rotação à esquerda antes de descer
+                        metrics::record(Counter::Rotations);
+                        self.set_right(t, self.left(c));
+                        self.set_left(c, Some(t));
+                        t = c;
+                        match self.right(t) {
+                            Some(next) => c = next,
+                            None => break Ordering::Greater,
+                        }
+                    }
+                    // t e a sua esquerda passam para L
+                    match l_max {
+                        Some(l) => self.set_right(l, Some(t)),
+                        None => l_root = Some(t),
+                    }
+                    l_max = Some(t);
+                    t = c;
+                }
+                Ordering::Equal => break Ordering::Equal,
+            }
+        };
+
+        // remonta: filhos de t vão para as pontas de L e R
+        if let Some(l) = l_max {
+            self.set_right(l, self.left(t));
+            self.set_left(t, l_root);
+        }
+        if let Some(r) = r_min {
+            self.set_left(r, self.right(t));
+            self.set_right(t, r_root);
+        }
+
+        self.root.set(Some(t));
+        Some((t, order))
+    }
+
+    /// Busca sem reorganizar a árvore.
+    fn find_plain(&self, cmp: impl Fn(&T) -> Ordering) -> Option<usize> {
+        let cmp = metrics::counted_cmp(cmp);
+        let mut cur = self.root.get();
+
+        while let Some(i) = cur {
+            match cmp(self.elem(i)) {
+                Ordering::Less => cur = self.left(i),
+                Ordering::Greater => cur = self.right(i),
+                Ordering::Equal => return Some(i),
+            }
+        }
+
+        None
+    }
+
+    pub(crate) fn find_by(&self, cmp: impl Fn(&T) -> Ordering) -> Option<&T> {
+        if self.readers.get() > 0 {
+            // algum iterador depende da forma atual da árvore
+            return self.find_plain(cmp).map(|i| self.elem(i));
+        }
+
+        match self.splay(cmp)? {
+            (root, Ordering::Equal) => Some(self.elem(root)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn remove_by(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+        let (root, order) = self.splay(cmp)?;
+        if order != Ordering::Equal {
+            return None;
+        }
+
+        // o maior da esquerda sobe (e fica sem filho direito);
+        // a direita original pendura nele
+        let right = self.right(root);
+        let new_root = match self.left(root) {
+            None => right,
+            Some(left) => {
+                self.root.set(Some(left));
+                let (max, _) = self.splay(|_| Ordering::Greater).unwrap();
+                self.set_right(max, right);
+                Some(max)
+            }
+        };
+
+        self.root.set(new_root);
+        Some(self.release(root))
+    }
+
+    /// Remove o menor (`go_left`) ou o maior elemento: splay até a ponta,
+    /// que vira raiz com um filho só.
+    fn take_extreme(&mut self, go_left: bool) -> Option<T> {
+        let toward = if go_left { Ordering::Less } else { Ordering::Greater };
+        let (root, _) = self.splay(|_| toward)?;
+
+        let rest = if go_left { self.right(root) } else { self.left(root) };
+        self.root.set(rest);
+        Some(self.release(root))
+    }
+
+    /// Maior elemento com `before(elem)`. Não reorganiza a árvore.
+    fn last_where(&self, before: impl Fn(&T) -> bool) -> Option<usize> {
+        let mut cur = self.root.get();
+        let mut best = None;
+
+        while let Some(i) = cur {
+            if before(self.elem(i)) {
+                best = Some(i);
+                cur = self.right(i);
+            } else {
+                cur = self.left(i);
+            }
+        }
+
+        best
+    }
+
+    /// Menor elemento sem `before(elem)`. Não reorganiza a árvore.
+    fn first_where_not(&self, before: impl Fn(&T) -> bool) -> Option<usize> {
+        let mut cur = self.root.get();
+        let mut best = None;
+
+        while let Some(i) = cur {
+            if before(self.elem(i)) {
+                cur = self.right(i);
+            } else {
+                best = Some(i);
+                cur = self.left(i);
+            }
+        }
+
+        best
+    }
+}
+
+impl<T: Ord> SplaySet<T> {
+    /// Insere o elemento se ele ainda não existe. Retorna `true` se inseriu.
+    fn insert_value(&mut self, value: T) -> bool {
+        // depois do splay, a raiz é vizinha de `value` na ordem:
+        // o nó novo entra por cima dela
+        let (left, right) = match self.splay(|x| value.cmp(x)) {
+            None => (None, None),
+            Some((_, Ordering::Equal)) => return false,
+            Some((root, Ordering::Less)) => {
+                let left = self.left(root);
+                self.set_left(root, None);
+                (left, Some(root))
+            }
+            Some((root, Ordering::Greater)) => {
+                let right = self.right(root);
+                self.set_right(root, None);
+                (Some(root), right)
+            }
+        };
+
+        let i = self.alloc(Node {
+            elem: value,
+            left: Cell::new(left),
+            right: Cell::new(right),
+        });
+        self.root.set(Some(i));
+
+        self.len += 1;
+        true
+    }
+}
+
+// ========================
+// Invariantes
+// ========================
+
+impl<T: Ord> SplaySet<T> {
+    /// Confere a estrutura da árvore: ordem de BST e `len`. Não há regra de
+    /// balanceamento a conferir. Custa O(n); pensado para testes.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        invariants::check_order_and_len(self.iter(), self.len)
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q> Lookup<T, Q> for SplaySet<T>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        self.find_by(|x| value.cmp(x.borrow()))
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        self.remove_by(|x| value.cmp(x.borrow()))
+    }
+}
+
+// ========================
+// Implementação de Set<T>
+// ========================
+
+impl<T: Ord> Set<T> for SplaySet<T> {
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_value(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(SplaySet::iter(self))
+    }
+
+    fn sorted_by(&self) -> Option<CmpFn<T>> {
+        Some(T::cmp)
+    }
+}
+
+// ========================
+// Implementação de OrderedSet<T>
+// ========================
+//
+// As consultas de vizinhança não reorganizam a árvore; `pop_min` e
+// `pop_max` sim (são `&mut self`).
+
+impl<T: Ord> OrderedSet<T> for SplaySet<T> {
+    fn min(&self) -> Option<&T> {
+        self.first_where_not(|_| false).map(|i| self.elem(i))
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.last_where(|_| true).map(|i| self.elem(i))
+    }
+
+    fn floor(&self, value: &T) -> Option<&T> {
+        self.last_where(|x| x <= value).map(|i| self.elem(i))
+    }
+
+    fn ceiling(&self, value: &T) -> Option<&T> {
+        self.first_where_not(|x| x < value).map(|i| self.elem(i))
+    }
+
+    fn successor(&self, value: &T) -> Option<&T> {
+        self.first_where_not(|x| x <= value).map(|i| self.elem(i))
+    }
+
+    fn predecessor(&self, value: &T) -> Option<&T> {
+        self.last_where(|x| x < value).map(|i| self.elem(i))
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.take_extreme(true)
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        self.take_extreme(false)
+    }
+
+    fn range<B: RangeBounds<T>>(&self, range: B) -> Box<dyn Iterator<Item = &T> + '_> {
+        let last = match range.end_bound() {
+            Bound::Included(hi) => self.last_where(|x| x <= hi),
+            Bound::Excluded(hi) => self.last_where(|x| x < hi),
+            Bound::Unbounded => self.last_where(|_| true),
+        };
+
+        let mut iter = SplaySetIter::new(self, last);
+
+        // desce até o primeiro elemento dentro do limite inferior,
+        // empilhando os nós que ainda serão visitados
+        let mut cur = self.root.get();
+        while let Some(i) = cur {
+            let inside = match range.start_bound() {
+                Bound::Included(lo) => self.elem(i) >= lo,
+                Bound::Excluded(lo) => self.elem(i) > lo,
+                Bound::Unbounded => true,
+            };
+
+            if inside {
+                iter.stack.push(i);
+                cur = self.left(i);
+            } else {
+                cur = self.right(i);
+            }
+        }
+
+        // intervalo vazio (ex.: 5..3): o primeiro elemento já passa do último
+        let empty = match (iter.stack.last(), last) {
+            (Some(&first), Some(last)) => self.elem(first) > self.elem(last),
+            _ => true,
+        };
+        if empty {
+            iter.stack.clear();
+        }
+
+        Box::new(iter)
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+/// Percurso em ordem com pilha explícita de índices.
+/// Enquanto existir, as buscas no conjunto não fazem splay.
+pub struct SplaySetIter<'a, T> {
+    set: &'a SplaySet<T>,
+    stack: Vec<usize>,
+    last: Option<usize>, // último índice do intervalo (em `range`)
+}
+
+impl<'a, T> SplaySetIter<'a, T> {
+    fn new(set: &'a SplaySet<T>, last: Option<usize>) -> Self {
+        set.readers.set(set.readers.get() + 1);
+        Self {
+            set,
+            stack: Vec::new(),
+            last,
+        }
+    }
+
+    fn push_left(&mut self, mut cur: Option<usize>) {
+        while let Some(i) = cur {
+            self.stack.push(i);
+            cur = self.set.left(i);
+        }
+    }
+}
+
+impl<'a, T> Iterator for SplaySetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.stack.pop()?;
+
+        if self.last == Some(i) {
+            // último elemento do intervalo: nada mais a visitar
+            self.stack.clear();
+        } else {
+            self.push_left(self.set.right(i));
+        }
+
+        Some(self.set.elem(i))
+    }
+}
+
+impl<T> Drop for SplaySetIter<'_, T> {
+    fn drop(&mut self) {
+        self.set.readers.set(self.set.readers.get() - 1);
+    }
+}
+
+/// Iterador em ordem que consome o conjunto: `pop_min` repetido. Pelo
+/// teorema do acesso sequencial das árvores splay, o total é O(n).
+pub struct SplaySetIntoIter<T> {
+    set: SplaySet<T>,
+}
+
+impl<T: Ord> Iterator for SplaySetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.set.pop_min()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.set.len, Some(self.set.len))
+    }
+}
+
+impl<T: Ord> IntoIterator for SplaySet<T> {
+    type Item = T;
+    type IntoIter = SplaySetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SplaySetIntoIter { set: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SplaySet<T> {
+    type Item = &'a T;
+    type IntoIter = SplaySetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SplaySet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord> Extend<T> for SplaySet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> crate::set::SetName for SplaySet<T> {
+    fn name() -> String {
+        "SplaySet".to_string()
+    }
+}
+
+impl<T> Default for SplaySet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
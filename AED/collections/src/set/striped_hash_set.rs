@@ -0,0 +1,322 @@
+use crate::metrics::{self, Counter};
+use crate::set::{HashChainingSet, Set};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Conjunto de hash compartilhável entre threads, com travas por faixa
+/// (lock striping).
+///
+/// - A tabela é dividida em `stripes` faixas; cada faixa é um
+///   `HashChainingSet` atrás do seu próprio `RwLock`.
+/// - O hash do elemento é calculado uma vez: os 32 bits altos escolhem a
+///   faixa e o hash inteiro segue para a faixa, que escolhe o bucket.
+/// - `contains` trava a faixa para leitura; `insert`/`remove`, para escrita.
+///   Operações em faixas diferentes não se bloqueiam.
+/// - Redimensionamento: cada faixa cresce (e encolhe) sozinha, com a trava
+///   de escrita que a operação já segura. Não há pausa global: enquanto uma
+///   faixa re-hasheia, as outras seguem atendendo. Como o hash espalha os
+///   elementos por igual, as faixas crescem mais ou menos juntas.
+/// - Todos os métodos recebem `&self`; basta um `&StripedHashSet` para
+///   compartilhar entre as threads de um `std::thread::scope`.
+/// - `S` precisa ser `Clone`: cada faixa recebe uma cópia do construtor de
+///   hasher, e todas precisam calcular o mesmo hash.
+pub struct StripedHashSet<T, S = RandomState> {
+    stripes: Box<[Stripe<T, S>]>,
+    len: AtomicUsize,
+    hash_builder: S,
+}
+
+/// Uma faixa por linha de cache: travas vizinhas não disputam a mesma linha
+/// (false sharing).
+#[repr(align(64))]
+struct Stripe<T, S> {
+    lock: RwLock<HashChainingSet<T, S>>,
+}
+
+impl<T> StripedHashSet<T, RandomState> {
+    /// Número de faixas de `new()`.
+    pub const DEFAULT_STRIPES: usize = 64;
+
+    pub fn new() -> Self {
+        Self::with_stripes(Self::DEFAULT_STRIPES)
+    }
+
+    /// Cria o conjunto com `stripes` faixas (mais faixas, menos disputa).
+    pub fn with_stripes(stripes: usize) -> Self {
+        Self::with_stripes_and_hasher(stripes, RandomState::new())
+    }
+}
+
+impl<T, S> StripedHashSet<T, S>
+where
+    S: BuildHasher + Clone,
+{
+    /// Cria o conjunto com `stripes` faixas e um construtor de hasher customizado.
+    pub fn with_stripes_and_hasher(stripes: usize, hash_builder: S) -> Self {
+        assert!(stripes > 0, "StripedHashSet: precisa de pelo menos uma faixa");
+        let stripes = (0..stripes)
+            .map(|_| Stripe {
+                lock: RwLock::new(HashChainingSet::with_hasher(hash_builder.clone())),
+            })
+            .collect();
+
+        Self {
+            stripes,
+            len: AtomicUsize::new(0),
+            hash_builder,
+        }
+    }
+
+    /// Número de elementos. Com outras threads escrevendo, é só um retrato:
+    /// pode mudar logo depois.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Número de faixas (travas).
+    pub fn stripes(&self) -> usize {
+        self.stripes.len()
+    }
+
+    fn hash_of<Q: Hash + ?Sized>(&self, value: &Q) -> u64 {
+        metrics::record(Counter::Hashes);
+        self.hash_builder.hash_one(value)
+    }
+
+    fn stripe_of(&self, h: u64) -> &Stripe<T, S> {
+        &self.stripes[(h >> 32) as usize % self.stripes.len()]
+    }
+}
+
+impl<T, S> Stripe<T, S> {
+    // Uma thread que entrou em pânico no meio de uma operação pode ter
+    // deixado a faixa pela metade: não há como seguir com segurança.
+    fn read(&self) -> RwLockReadGuard<'_, HashChainingSet<T, S>> {
+        self.lock
+            .read()
+            .expect("StripedHashSet: outra thread entrou em pânico segurando a trava")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashChainingSet<T, S>> {
+        self.lock
+            .write()
+            .expect("StripedHashSet: outra thread entrou em pânico segurando a trava")
+    }
+}
+
+// ========================
+// Operações (todas por `&self`)
+// ========================
+
+impl<T, S> StripedHashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Clone,
+{
+    /// Insere o elemento. Retorna `true` se inseriu. Se várias threads
+    /// inserem o mesmo elemento ao mesmo tempo, exatamente uma recebe `true`.
+    pub fn insert(&self, value: T) -> bool {
+        let h = self.hash_of(&value);
+        // o contador é atualizado ainda com a trava: quem travar a faixa
+        // depois vê o elemento e o contador juntos
+        let mut stripe = self.stripe_of(h).write();
+        let inserted = stripe.insert_new_hashed(h, value).is_some();
+        if inserted {
+            self.len.fetch_add(1, Ordering::Relaxed);
+        }
+        drop(stripe);
+        inserted
+    }
+
+    /// O elemento está no conjunto?
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let h = self.hash_of(value);
+        self.stripe_of(h).read().find_by(h, |x| x.borrow() == value).is_some()
+    }
+
+    /// Remove o elemento. Retorna `true` se removeu.
+    pub fn remove<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.take(value).is_some()
+    }
+
+    /// Remove o elemento e o devolve.
+    pub fn take<Q>(&self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let h = self.hash_of(value);
+        // como em `insert`, o contador muda antes de soltar a trava
+        let mut stripe = self.stripe_of(h).write();
+        let removed = stripe.remove_by(h, |x| x.borrow() == value);
+        if removed.is_some() {
+            self.len.fetch_sub(1, Ordering::Relaxed);
+        }
+        drop(stripe);
+        removed
+    }
+
+    /// Visita todos os elementos, uma faixa por vez (cada uma travada para
+    /// leitura durante a visita). Não é um retrato atômico do conjunto:
+    /// escritas em faixas já visitadas ou ainda não visitadas podem ou não
+    /// aparecer.
+    pub fn for_each(&self, mut f: impl FnMut(&T)) {
+        for stripe in self.stripes.iter() {
+            for value in stripe.read().iter() {
+                f(value);
+            }
+        }
+    }
+}
+
+// ========================
+// Interface comum para benchmarks/testes concorrentes
+// ========================
+
+/// Conjunto que aceita `insert`/`remove`/`contains` por `&self`, de várias
+/// threads ao mesmo tempo.
+pub trait ConcurrentSet<T>: Sync {
+    fn insert(&self, value: T) -> bool;
+    fn remove(&self, value: &T) -> bool;
+    fn contains(&self, value: &T) -> bool;
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T, S> ConcurrentSet<T> for StripedHashSet<T, S>
+where
+    T: Eq + Hash + Send + Sync,
+    S: BuildHasher + Clone + Send + Sync,
+{
+    fn insert(&self, value: T) -> bool {
+        StripedHashSet::insert(self, value)
+    }
+
+    fn remove(&self, value: &T) -> bool {
+        StripedHashSet::remove(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        StripedHashSet::contains(self, value)
+    }
+
+    fn len(&self) -> usize {
+        StripedHashSet::len(self)
+    }
+}
+
+/// Qualquer `Set` atrás de um único `Mutex`: a referência com que o
+/// `StripedHashSet` é comparado.
+impl<T, C> ConcurrentSet<T> for Mutex<C>
+where
+    C: Set<T> + Send,
+{
+    fn insert(&self, value: T) -> bool {
+        self.lock().expect("Mutex envenenado").insert(value)
+    }
+
+    fn remove(&self, value: &T) -> bool {
+        self.lock().expect("Mutex envenenado").remove(value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.lock().expect("Mutex envenenado").contains(value)
+    }
+
+    fn len(&self) -> usize {
+        self.lock().expect("Mutex envenenado").len()
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+/// Iterador que consome o conjunto, faixa por faixa.
+pub struct StripedHashSetIntoIter<T> {
+    inner: std::vec::IntoIter<T>,
+}
+
+impl<T> Iterator for StripedHashSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, S> IntoIterator for StripedHashSet<T, S> {
+    type Item = T;
+    type IntoIter = StripedHashSetIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut all = Vec::with_capacity(self.len.into_inner());
+        for stripe in self.stripes.into_vec() {
+            let set = stripe
+                .lock
+                .into_inner()
+                .expect("StripedHashSet: outra thread entrou em pânico segurando a trava");
+            all.extend(set);
+        }
+        StripedHashSetIntoIter {
+            inner: all.into_iter(),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for StripedHashSet<T, RandomState>
+where
+    T: Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for StripedHashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Clone,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> crate::set::SetName for StripedHashSet<T> {
+    fn name() -> String {
+        "StripedHashSet".to_string()
+    }
+}
+
+impl<T> Default for StripedHashSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
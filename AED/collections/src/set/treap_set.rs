@@ -0,0 +1,655 @@
+use crate::metrics;
+use crate::set::bst_nav::{self, BstNode, InOrder, IntoInOrder, OwnedBstNode};
+use crate::set::invariants;
+use crate::set::{CmpFn, InvariantError, Lookup, OrderedSet, Set};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::RangeBounds;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+// Treap = árvore de busca nos elementos + heap (de máximo) nas prioridades.
+// Como as prioridades são sorteadas, a forma da árvore é a de uma BST
+// construída com os elementos em ordem aleatória: altura O(log n) esperada,
+// não importa a ordem das inserções.
+//
+// Cada nó guarda o tamanho da sua subárvore: `split` sabe o tamanho das
+// duas partes olhando só para as raízes.
+struct Node<T> {
+    elem: T,
+    priority: u64,
+    size: usize,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// Conjunto implementado como treap.
+/// Não permite elementos repetidos.
+/// Operações são O(log n) esperado. Além das operações pontuais, expõe
+/// `split` e `join`, e as operações em lote (`union_with`,
+/// `intersection_with`, `difference_with`) construídas em cima deles.
+pub struct TreapSet<T, R = StdRng> {
+    root: Link<T>,
+    len: usize,
+    rng: R,
+}
+
+impl<T> TreapSet<T> {
+    /// Conjunto vazio com gerador semeado pela entropia do sistema.
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Conjunto vazio com gerador de semente fixa (estrutura reprodutível).
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<T, R> TreapSet<T, R> {
+    pub fn with_rng(rng: R) -> Self {
+        Self {
+            root: None,
+            len: 0,
+            rng,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Itera em ordem crescente.
+    pub fn iter(&self) -> TreapSetIter<'_, T> {
+        TreapSetIter {
+            inner: InOrder::new(self.root.as_deref()),
+        }
+    }
+}
+
+// ========================
+// split / merge
+// ========================
+//
+// As duas operações primitivas do treap. Todo o resto (inserção, remoção,
+// união, interseção, diferença) é escrito com elas.
+// `cmp(elem)` diz onde a chave de corte está em relação a `elem`.
+// Quem troca um filho de lugar atualiza o tamanho do nó com `update_size`.
+
+fn size<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+fn update_size<T>(node: &mut Node<T>) {
+    node.size = 1 + size(&node.left) + size(&node.right);
+}
+
+/// Separa a árvore em (elementos antes da chave, nó igual à chave, elementos depois).
+fn split<T>(link: Link<T>, cmp: &impl Fn(&T) -> Ordering) -> (Link<T>, Link<T>, Link<T>) {
+    let Some(mut node) = link else {
+        return (None, None, None);
+    };
+
+    match cmp(&node.elem) {
+        Ordering::Less => {
+            // corte à esquerda deste nó: ele fica do lado direito
+            let (l, mid, r) = split(node.left.take(), cmp);
+            node.left = r;
+            update_size(&mut node);
+            (l, mid, Some(node))
+        }
+        Ordering::Greater => {
+            let (l, mid, r) = split(node.right.take(), cmp);
+            node.right = l;
+            update_size(&mut node);
+            (Some(node), mid, r)
+        }
+        Ordering::Equal => {
+            let l = node.left.take();
+            let r = node.right.take();
+            node.size = 1;
+            (l, Some(node), r)
+        }
+    }
+}
+
+/// Junta duas árvores com todo elemento de `a` menor que todo elemento de `b`.
+/// A raiz de maior prioridade fica por cima.
+fn merge<T>(a: Link<T>, b: Link<T>) -> Link<T> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.priority > b.priority {
+                a.right = merge(a.right.take(), Some(b));
+                update_size(&mut a);
+                Some(a)
+            } else {
+                b.left = merge(Some(a), b.left.take());
+                update_size(&mut b);
+                Some(b)
+            }
+        }
+    }
+}
+
+// ========================
+// Operações em lote
+// ========================
+//
+// Recursão sobre a raiz de maior prioridade: ela continua sendo raiz do
+// resultado, e a outra árvore é cortada no elemento dela. Para conjuntos de
+// tamanhos m <= n custa O(m log(n/m + 1)) esperado — bem menos que
+// percorrer os dois quando um deles é pequeno.
+
+/// `dups` conta os elementos que estavam nos dois lados.
+fn union<T: Ord>(a: Link<T>, b: Link<T>, dups: &mut usize) -> Link<T> {
+    let (mut a, b) = match (a, b) {
+        (None, b) => return b,
+        (a, None) => return a,
+        (Some(a), Some(b)) if a.priority < b.priority => (b, Some(a)),
+        (Some(a), b) => (a, b),
+    };
+
+    let (l, mid, r) = split(b, &|x| metrics::cmp(&a.elem, x));
+    if mid.is_some() {
+        *dups += 1;
+    }
+
+    a.left = union(a.left.take(), l, dups);
+    a.right = union(a.right.take(), r, dups);
+    update_size(&mut a);
+    Some(a)
+}
+
+/// `kept` conta os elementos do resultado.
+fn intersection<T: Ord>(a: Link<T>, b: Link<T>, kept: &mut usize) -> Link<T> {
+    let (mut a, b) = match (a, b) {
+        (None, b) => {
+            bst_nav::drop_tree(b);
+            return None;
+        }
+        (a, None) => {
+            bst_nav::drop_tree(a);
+            return None;
+        }
+        (Some(a), Some(b)) if a.priority < b.priority => (b, Some(a)),
+        (Some(a), b) => (a, b),
+    };
+
+    let (l, mid, r) = split(b, &|x| metrics::cmp(&a.elem, x));
+    let left = intersection(a.left.take(), l, kept);
+    let right = intersection(a.right.take(), r, kept);
+
+    if mid.is_some() {
+        // está nos dois: a raiz fica
+        *kept += 1;
+        a.left = left;
+        a.right = right;
+        update_size(&mut a);
+        Some(a)
+    } else {
+        merge(left, right)
+    }
+}
+
+/// Elementos de `a` fora de `b`. `removed` conta os que saíram de `a`.
+fn difference<T: Ord>(a: Link<T>, b: Link<T>, removed: &mut usize) -> Link<T> {
+    let (a, mut b) = match (a, b) {
+        (None, b) => {
+            bst_nav::drop_tree(b);
+            return None;
+        }
+        (a, None) => return a,
+        (Some(a), Some(b)) => (Some(a), b),
+    };
+
+    // aqui a raiz não pode trocar de lado: corta `a` no elemento de `b`
+    let (l, mid, r) = split(a, &|x| metrics::cmp(&b.elem, x));
+    if mid.is_some() {
+        *removed += 1;
+    }
+
+    let left = difference(l, b.left.take(), removed);
+    let right = difference(r, b.right.take(), removed);
+    merge(left, right)
+}
+
+impl<T> BstNode<T> for Node<T> {
+    fn elem(&self) -> &T {
+        &self.elem
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+impl<T> OwnedBstNode<T> for Node<T> {
+    fn take_left(&mut self) -> Option<Box<Self>> {
+        self.left.take()
+    }
+
+    fn take_right(&mut self) -> Option<Box<Self>> {
+        self.right.take()
+    }
+
+    fn into_elem(self) -> T {
+        self.elem
+    }
+}
+
+// ========================
+// split / join públicos
+// ========================
+
+impl<T: Ord, R: RngCore + SeedableRng> TreapSet<T, R> {
+    /// Corta o conjunto em `key`: devolve (elementos `< key`, `key` se
+    /// estava no conjunto, elementos `> key`). O(log n) esperado.
+    ///
+    /// O lado esquerdo fica com o gerador deste conjunto; o direito recebe
+    /// um gerador novo, semeado a partir dele.
+    pub fn split(mut self, key: &T) -> (Self, Option<T>, Self) {
+        let right_rng = R::from_rng(&mut self.rng).expect("TreapSet::split: falha ao semear gerador");
+
+        let (l, mid, r) = split(self.root.take(), &|x| metrics::cmp(key, x));
+        let mid = mid.map(|node| node.elem);
+
+        let right = Self {
+            len: size(&r),
+            root: r,
+            rng: right_rng,
+        };
+        self.len = size(&l);
+        self.root = l;
+
+        (self, mid, right)
+    }
+}
+
+impl<T: Ord, R> TreapSet<T, R> {
+    /// Junta dois conjuntos em que todo elemento de `left` é menor que todo
+    /// elemento de `right`. O(log n) esperado. O resultado fica com o
+    /// gerador de `left`.
+    ///
+    /// Entra em pânico se os conjuntos se sobrepõem.
+    pub fn join(mut left: Self, mut right: Self) -> Self {
+        if let (Some(max), Some(min)) = (bst_nav::max(left.root.as_deref()), bst_nav::min(right.root.as_deref())) {
+            assert!(max < min, "TreapSet::join: os conjuntos se sobrepõem");
+        }
+
+        left.root = merge(left.root.take(), right.root.take());
+        left.len += right.len;
+        left
+    }
+
+    /// União consumindo os dois conjuntos. O resultado fica com o gerador
+    /// de `self`.
+    pub fn union_with(mut self, mut other: Self) -> Self {
+        let mut dups = 0;
+        self.root = union(self.root.take(), other.root.take(), &mut dups);
+        self.len = self.len + other.len - dups;
+        self
+    }
+
+    /// Interseção consumindo os dois conjuntos.
+    pub fn intersection_with(mut self, mut other: Self) -> Self {
+        let mut kept = 0;
+        self.root = intersection(self.root.take(), other.root.take(), &mut kept);
+        self.len = kept;
+        self
+    }
+
+    /// Elementos de `self` que não estão em `other`, consumindo os dois.
+    pub fn difference_with(mut self, mut other: Self) -> Self {
+        let mut removed = 0;
+        self.root = difference(self.root.take(), other.root.take(), &mut removed);
+        self.len -= removed;
+        self
+    }
+}
+
+// ========================
+// Busca / remoção por comparação
+// ========================
+//
+// `cmp(elem)` diz onde o alvo está em relação a `elem`:
+// `Less` → à esquerda, `Greater` → à direita, `Equal` → achou.
+
+impl<T, R> TreapSet<T, R> {
+    pub(crate) fn find_by(&self, cmp: impl Fn(&T) -> Ordering) -> Option<&T> {
+        let cmp = metrics::counted_cmp(cmp);
+        let mut cur = &self.root;
+
+        while let Some(node) = cur {
+            match cmp(&node.elem) {
+                Ordering::Less => cur = &node.left,
+                Ordering::Greater => cur = &node.right,
+                Ordering::Equal => return Some(&node.elem),
+            }
+        }
+
+        None
+    }
+
+    pub(crate) fn remove_by(&mut self, cmp: impl Fn(&T) -> Ordering) -> Option<T> {
+        // desce até o nó procurado; na volta, cada ancestral perde um
+        fn remove_rec<T>(link: &mut Link<T>, cmp: &impl Fn(&T) -> Ordering) -> Option<T> {
+            let node = link.as_mut()?;
+            let removed = match cmp(&node.elem) {
+                Ordering::Less => remove_rec(&mut node.left, cmp),
+                Ordering::Greater => remove_rec(&mut node.right, cmp),
+                Ordering::Equal => {
+                    // os dois filhos, já em ordem, ocupam o lugar do nó
+                    let mut node = link.take().unwrap();
+                    *link = merge(node.left.take(), node.right.take());
+                    return Some(node.elem);
+                }
+            };
+
+            if removed.is_some() {
+                node.size -= 1;
+            }
+            removed
+        }
+
+        let removed = remove_rec(&mut self.root, &metrics::counted_cmp(cmp));
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Remove o menor (`go_left`) ou o maior elemento. O nó removido tem no
+    /// máximo um filho, que sobe no lugar dele sem quebrar o heap.
+    fn take_extreme(&mut self, go_left: bool) -> Option<T> {
+        let mut cur = &mut self.root;
+        loop {
+            let next = cur.as_deref()?;
+            let child = if go_left { &next.left } else { &next.right };
+            if child.is_none() {
+                break;
+            }
+            // o extremo está abaixo deste nó
+            let node = cur.as_mut().unwrap();
+            node.size -= 1;
+            cur = if go_left { &mut node.left } else { &mut node.right };
+        }
+
+        let mut node = cur.take().unwrap();
+        *cur = if go_left { node.right.take() } else { node.left.take() };
+
+        self.len -= 1;
+        Some(node.elem)
+    }
+}
+
+impl<T: Ord, R: RngCore> TreapSet<T, R> {
+    /// Insere o elemento se ele ainda não existe. Retorna `true` se inseriu.
+    fn insert_value(&mut self, value: T) -> bool {
+        if self.find_by(|x| value.cmp(x)).is_some() {
+            return false;
+        }
+
+        let priority = self.rng.next_u64();
+
+        // desce enquanto os nós têm prioridade maior que a do novo;
+        // no ponto de parada, o novo vira raiz da subárvore cortada nele
+        let mut cur = &mut self.root;
+        while cur.as_ref().is_some_and(|node| node.priority > priority) {
+            let node = cur.as_mut().unwrap();
+            // o novo vai ficar abaixo deste nó
+            node.size += 1;
+            cur = match metrics::cmp(&value, &node.elem) {
+                Ordering::Less => &mut node.left,
+                _ => &mut node.right,
+            };
+        }
+
+        let (left, _, right) = split(cur.take(), &|x| metrics::cmp(&value, x));
+        *cur = Some(Box::new(Node {
+            elem: value,
+            priority,
+            size: 1 + size(&left) + size(&right),
+            left,
+            right,
+        }));
+
+        self.len += 1;
+        true
+    }
+}
+
+// ========================
+// Invariantes
+// ========================
+
+impl<T: Ord, R> TreapSet<T, R> {
+    /// Confere a estrutura da árvore: ordem de BST, `len`, ordem de heap
+    /// nas prioridades e tamanhos guardados nos nós. Devolve a primeira
+    /// regra quebrada. Custa O(n); pensado para testes.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        // devolve o tamanho real da subárvore
+        fn check_rec<T>(link: &Link<T>) -> Result<usize, InvariantError> {
+            let Some(node) = link else {
+                return Ok(0);
+            };
+
+            for child in [node.left.as_deref(), node.right.as_deref()].into_iter().flatten() {
+                if child.priority > node.priority {
+                    return Err(InvariantError::HeapOrder);
+                }
+            }
+
+            let actual = 1 + check_rec(&node.left)? + check_rec(&node.right)?;
+            if node.size != actual {
+                return Err(InvariantError::StaleSize {
+                    stored: node.size,
+                    actual,
+                });
+            }
+
+            Ok(actual)
+        }
+
+        invariants::check_order_and_len(self.iter(), self.len)?;
+        check_rec(&self.root)?;
+        Ok(())
+    }
+}
+
+// ========================
+// Consultas por empréstimo (ver `Lookup`)
+// ========================
+
+impl<T, Q, R> Lookup<T, Q> for TreapSet<T, R>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+    R: RngCore,
+{
+    fn get(&self, value: &Q) -> Option<&T> {
+        self.find_by(|x| value.cmp(x.borrow()))
+    }
+
+    fn take(&mut self, value: &Q) -> Option<T> {
+        self.remove_by(|x| value.cmp(x.borrow()))
+    }
+}
+
+// ========================
+// Implementação de Set<T>
+// ========================
+
+impl<T: Ord, R: RngCore> Set<T> for TreapSet<T, R> {
+    fn insert(&mut self, value: T) -> bool {
+        self.insert_value(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(TreapSet::iter(self))
+    }
+
+    fn sorted_by(&self) -> Option<CmpFn<T>> {
+        Some(T::cmp)
+    }
+}
+
+// ========================
+// Implementação de OrderedSet<T>
+// ========================
+
+impl<T: Ord, R: RngCore> OrderedSet<T> for TreapSet<T, R> {
+    fn min(&self) -> Option<&T> {
+        bst_nav::min(self.root.as_deref())
+    }
+
+    fn max(&self) -> Option<&T> {
+        bst_nav::max(self.root.as_deref())
+    }
+
+    fn floor(&self, value: &T) -> Option<&T> {
+        bst_nav::floor(self.root.as_deref(), value)
+    }
+
+    fn ceiling(&self, value: &T) -> Option<&T> {
+        bst_nav::ceiling(self.root.as_deref(), value)
+    }
+
+    fn successor(&self, value: &T) -> Option<&T> {
+        bst_nav::successor(self.root.as_deref(), value)
+    }
+
+    fn predecessor(&self, value: &T) -> Option<&T> {
+        bst_nav::predecessor(self.root.as_deref(), value)
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.take_extreme(true)
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        self.take_extreme(false)
+    }
+
+    fn range<B: RangeBounds<T>>(&self, range: B) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(InOrder::range(
+            self.root.as_deref(),
+            range.start_bound(),
+            range.end_bound(),
+        ))
+    }
+}
+
+// ========================
+// Drop
+// ========================
+
+impl<T, R> Drop for TreapSet<T, R> {
+    fn drop(&mut self) {
+        bst_nav::drop_tree(self.root.take());
+    }
+}
+
+// ========================
+// Iteradores
+// ========================
+
+pub struct TreapSetIter<'a, T> {
+    inner: InOrder<'a, T, Node<T>>,
+}
+
+impl<'a, T> Iterator for TreapSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Iterador em ordem que consome o conjunto.
+pub struct TreapSetIntoIter<T> {
+    inner: IntoInOrder<T, Node<T>>,
+}
+
+impl<T> Iterator for TreapSetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, R> IntoIterator for TreapSet<T, R> {
+    type Item = T;
+    type IntoIter = TreapSetIntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        TreapSetIntoIter {
+            inner: IntoInOrder::new(self.root.take(), self.len),
+        }
+    }
+}
+
+impl<'a, T, R> IntoIterator for &'a TreapSet<T, R> {
+    type Item = &'a T;
+    type IntoIter = TreapSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for TreapSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord, R: RngCore> Extend<T> for TreapSet<T, R> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T, R> crate::set::SetName for TreapSet<T, R> {
+    fn name() -> String {
+        "TreapSet".to_string()
+    }
+}
+
+impl<T> Default for TreapSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
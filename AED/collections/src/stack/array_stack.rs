@@ -38,6 +38,11 @@ impl<T> ArrayStack<T> {
         self.len
     }
 
+    /// Conveniência, espelho do trait
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     /// Garante que há capacidade ≥ min_capacity
     fn ensure_capacity(&mut self, min_capacity: usize) {
         if self.capacity >= min_capacity {
@@ -168,3 +173,35 @@ impl<T> Drop for ArrayStack<T> {
         }
     }
 }
+
+impl<T> Default for ArrayStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ========================
+// Serde (feature `serde`)
+// ========================
+
+/// Da base para o topo: empilhar os elementos na mesma ordem reconstrói a pilha.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ArrayStack<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // SAFETY: as posições 0..len estão inicializadas
+        let values = unsafe { std::slice::from_raw_parts(self.ptr, self.len) };
+        crate::serde_util::serialize_iter(self.len, values, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ArrayStack<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        let mut stack = Self::with_capacity(values.len());
+        for value in values {
+            stack.push(value);
+        }
+        Ok(stack)
+    }
+}
@@ -63,3 +63,48 @@ impl<T> Stack<T> for ListStack<T> {
         self.len == 0
     }
 }
+
+// Drop iterativo: o drop gerado pelo compilador derruba `head`, que derruba
+// o próximo nó, e assim por diante — uma recursão por elemento.
+impl<T> Drop for ListStack<T> {
+    fn drop(&mut self) {
+        let mut cur = self.head.take();
+        while let Some(mut node) = cur {
+            cur = node.next.take();
+        }
+    }
+}
+
+impl<T> Default for ListStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ========================
+// Serde (feature `serde`)
+// ========================
+
+/// Da base para o topo, como a `ArrayStack`: a lista guarda o topo na
+/// cabeça, então a ordem é invertida antes de gravar.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ListStack<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let values: Vec<&T> = std::iter::successors(self.head.as_deref(), |node| node.next.as_deref())
+            .map(|node| &node.elem)
+            .collect();
+        crate::serde_util::serialize_iter(self.len, values.into_iter().rev(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ListStack<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        let mut stack = Self::new();
+        for value in values {
+            stack.push(value);
+        }
+        Ok(stack)
+    }
+}
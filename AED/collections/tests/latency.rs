@@ -0,0 +1,63 @@
+use collections::benches::Histogram;
+
+// =======================
+// Histograma de latências
+// =======================
+
+#[test]
+fn histograma_vazio() {
+    let h = Histogram::new();
+    assert_eq!(h.count(), 0);
+    assert_eq!(h.max(), 0);
+    assert_eq!(h.percentile(0.5), 0);
+}
+
+#[test]
+fn histograma_valores_pequenos_sao_exatos() {
+    let mut h = Histogram::new();
+    for v in 1..=10 {
+        h.record(v);
+    }
+    assert_eq!(h.count(), 10);
+    assert_eq!(h.percentile(0.5), 5);
+    assert_eq!(h.percentile(0.9), 9);
+    assert_eq!(h.percentile(1.0), 10);
+    assert_eq!(h.max(), 10);
+}
+
+#[test]
+fn histograma_erro_relativo_limitado() {
+    let mut h = Histogram::new();
+    for v in 1..=100_000u64 {
+        h.record(v);
+    }
+    for q in [0.5, 0.9, 0.99, 0.999] {
+        let exato = (q * 100_000.0) as u64;
+        let aproximado = h.percentile(q);
+        // topo da faixa: nunca abaixo do exato, no máximo 1/16 acima
+        assert!(aproximado >= exato, "q = {q}: {aproximado} < {exato}");
+        assert!(aproximado as f64 <= exato as f64 * (1.0 + 1.0 / 16.0), "q = {q}: {aproximado}");
+    }
+    assert_eq!(h.percentile(1.0), 100_000);
+}
+
+#[test]
+fn histograma_pico_isolado_so_aparece_no_maximo() {
+    // como um rehash: 10 mil operações baratas e uma cara
+    let mut h = Histogram::new();
+    for _ in 0..10_000 {
+        h.record(100);
+    }
+    h.record(5_000_000);
+    assert!(h.percentile(0.999) < 110);
+    assert_eq!(h.max(), 5_000_000);
+}
+
+#[test]
+fn histograma_valores_extremos() {
+    let mut h = Histogram::new();
+    h.record(0);
+    h.record(u64::MAX);
+    assert_eq!(h.percentile(0.5), 0);
+    assert_eq!(h.percentile(1.0), u64::MAX);
+}
@@ -0,0 +1,233 @@
+use collections::map::{AvlMap, BstMap, HashChainingMap, HashProbingMap, ListMap, RbMap};
+use collections::map::{Entry, Map};
+use collections::set::{DoubleHashing, QuadraticProbing};
+use std::collections::hash_map::RandomState;
+
+// =======================
+// Helpers genéricos
+// =======================
+
+fn testa_map_basico<M: Map<i32, &'static str>>(mut m: M) {
+    assert!(m.is_empty());
+    assert_eq!(m.len(), 0);
+
+    // inserção
+    assert_eq!(m.insert(10, "dez"), None);
+    assert_eq!(m.insert(20, "vinte"), None);
+    assert_eq!(m.insert(30, "trinta"), None);
+
+    assert_eq!(m.len(), 3);
+    assert_eq!(m.get(&10), Some(&"dez"));
+    assert_eq!(m.get(&20), Some(&"vinte"));
+    assert!(m.contains_key(&30));
+    assert!(!m.contains_key(&99));
+    assert_eq!(m.get(&99), None);
+
+    // inserir chave repetida troca o valor
+    assert_eq!(m.insert(10, "DEZ"), Some("dez"));
+    assert_eq!(m.len(), 3);
+    assert_eq!(m.get(&10), Some(&"DEZ"));
+
+    // alteração no lugar
+    *m.get_mut(&20).unwrap() = "XX";
+    assert_eq!(m.get(&20), Some(&"XX"));
+
+    // remoção
+    assert_eq!(m.remove(&20), Some("XX"));
+    assert!(!m.contains_key(&20));
+    assert_eq!(m.len(), 2);
+
+    // remover quem não existe
+    assert_eq!(m.remove(&999), None);
+    assert_eq!(m.len(), 2);
+}
+
+/// Contagem de palavras com a Entry API.
+fn testa_map_entry<M: Map<String, usize>>(mut m: M) {
+    let texto = "o rato roeu a roupa do rei de roma e o rei ficou bravo com o rato";
+
+    for palavra in texto.split_whitespace() {
+        *m.entry(palavra.to_string()).or_insert(0) += 1;
+    }
+
+    assert_eq!(m.get(&"o".to_string()), Some(&3));
+    assert_eq!(m.get(&"rato".to_string()), Some(&2));
+    assert_eq!(m.get(&"rei".to_string()), Some(&2));
+    assert_eq!(m.get(&"roma".to_string()), Some(&1));
+    assert_eq!(m.len(), 13);
+
+    // and_modify + or_default
+    m.entry("rato".to_string()).and_modify(|c| *c *= 10).or_default();
+    m.entry("gato".to_string()).and_modify(|c| *c *= 10).or_default();
+    assert_eq!(m.get(&"rato".to_string()), Some(&20));
+    assert_eq!(m.get(&"gato".to_string()), Some(&0));
+
+    // entradas ocupadas e vagas
+    match m.entry("rei".to_string()) {
+        Entry::Occupied(mut e) => {
+            assert_eq!(e.key(), "rei");
+            assert_eq!(*e.get(), 2);
+            assert_eq!(e.insert(7), 2);
+            assert_eq!(e.remove(), 7);
+        }
+        Entry::Vacant(_) => panic!("rei deveria estar no mapa"),
+    }
+    assert!(!m.contains_key(&"rei".to_string()));
+
+    match m.entry("rainha".to_string()) {
+        Entry::Occupied(_) => panic!("rainha não deveria estar no mapa"),
+        Entry::Vacant(e) => {
+            assert_eq!(e.key(), "rainha");
+            *e.insert(1) += 1;
+        }
+    }
+    assert_eq!(m.get(&"rainha".to_string()), Some(&2));
+
+    let total: usize = m.iter().map(|(_, c)| *c).sum();
+    assert_eq!(m.iter().count(), m.len());
+    // 17 palavras; rato 2 → 20; rei (2) saiu; rainha entrou com 2
+    assert_eq!(total, 17 + 18 - 2 + 2);
+}
+
+/// `insert_vacant` com chaves ausentes: o valor devolvido é o do mapa.
+fn testa_insert_vacant<M: Map<i32, i32>>(mut m: M) {
+    for i in (0..100).rev() {
+        *m.insert_vacant(i, i) += 1;
+    }
+    assert_eq!(m.len(), 100);
+    assert!((0..100).all(|i| m.get(&i) == Some(&(i + 1))));
+}
+
+/// Muitas inserções e remoções: força redimensionamentos e rotações.
+fn testa_map_carga<M: Map<i32, i32>>(mut m: M) {
+    for i in 0..1000 {
+        assert_eq!(m.insert(i, i * i), None);
+    }
+    for i in (0..1000).step_by(2) {
+        assert_eq!(m.remove(&i), Some(i * i));
+    }
+    for i in 0..1000 {
+        *m.get_or_insert_with(i, || -1) += 1;
+    }
+
+    assert_eq!(m.len(), 1000);
+    for i in 0..1000 {
+        let esperado = if i % 2 == 0 { 0 } else { i * i + 1 };
+        assert_eq!(m.get(&i), Some(&esperado));
+    }
+}
+
+// =======================
+// ListMap
+// =======================
+
+#[test]
+fn listmap_basico() {
+    testa_map_basico(ListMap::new());
+    testa_map_entry(ListMap::new());
+    testa_map_carga(ListMap::new());
+}
+
+// =======================
+// Árvores
+// =======================
+
+#[test]
+fn bstmap_basico() {
+    testa_map_basico(BstMap::new());
+    testa_map_entry(BstMap::new());
+    testa_map_carga(BstMap::new());
+}
+
+#[test]
+fn avlmap_basico() {
+    testa_map_basico(AvlMap::new());
+    testa_map_entry(AvlMap::new());
+    testa_map_carga(AvlMap::new());
+}
+
+#[test]
+fn rbmap_basico() {
+    testa_map_basico(RbMap::new());
+    testa_map_entry(RbMap::new());
+    testa_map_carga(RbMap::new());
+}
+
+#[test]
+fn arvores_iteram_por_chave() {
+    let mut m: AvlMap<i32, char> = AvlMap::new();
+    for (k, v) in [(3, 'c'), (1, 'a'), (2, 'b')] {
+        m.insert(k, v);
+    }
+
+    let pares: Vec<(i32, char)> = m.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(pares, vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+}
+
+/// `or_insert` devolve a referência do valor recém-inserido mesmo quando a
+/// inserção rotaciona a árvore: chaves crescentes rotacionam a cada poucos
+/// passos, e as em zigue-zague forçam as rotações duplas.
+fn testa_entry_com_rotacoes<M: Map<i32, i32>>(mut m: M) {
+    let chaves: Vec<i32> = (0..200)
+        .chain((0..200).map(|i| if i % 2 == 0 { 1000 - i } else { 300 + i }))
+        .collect();
+
+    for &k in &chaves {
+        let v = m.entry(k).or_insert(k * 10);
+        assert_eq!(*v, k * 10);
+        *v += 1;
+    }
+    for &k in &chaves {
+        assert_eq!(m.get(&k), Some(&(k * 10 + 1)));
+    }
+
+    // `get_or_insert_with` passa pelo mesmo caminho
+    for k in 2000..2100 {
+        *m.get_or_insert_with(k, || 0) += k;
+        assert_eq!(m.get(&k), Some(&k));
+    }
+}
+
+#[test]
+fn arvores_entry_durante_rotacoes() {
+    testa_entry_com_rotacoes(AvlMap::new());
+    testa_entry_com_rotacoes(RbMap::new());
+}
+
+#[test]
+#[should_panic(expected = "a chave já estava no mapa")]
+fn avlmap_insert_vacant_com_chave_presente() {
+    let mut m = AvlMap::new();
+    m.insert(1, 1);
+    m.insert_vacant(1, 2);
+}
+
+#[test]
+fn insert_vacant_em_todos_os_mapas() {
+    testa_insert_vacant(ListMap::new());
+    testa_insert_vacant(BstMap::new());
+    testa_insert_vacant(AvlMap::new());
+    testa_insert_vacant(RbMap::new());
+    testa_insert_vacant(HashChainingMap::new());
+    testa_insert_vacant(HashProbingMap::<_, _>::new());
+}
+
+// =======================
+// Hash
+// =======================
+
+#[test]
+fn hashmap_chaining_basico() {
+    testa_map_basico(HashChainingMap::new());
+    testa_map_entry(HashChainingMap::new());
+    testa_map_carga(HashChainingMap::new());
+}
+
+#[test]
+fn hashmap_probing_basico() {
+    testa_map_basico(HashProbingMap::<_, _>::new());
+    testa_map_entry(HashProbingMap::<_, _>::new());
+    testa_map_carga(HashProbingMap::<_, _>::new());
+    testa_map_carga(HashProbingMap::<_, _, RandomState, QuadraticProbing>::new());
+    testa_map_carga(HashProbingMap::<_, _, RandomState, DoubleHashing>::new());
+}
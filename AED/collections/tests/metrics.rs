@@ -0,0 +1,180 @@
+use collections::metrics;
+use collections::set::{BstAvlSet, Set};
+
+// =======================
+// Sem a feature: tudo zerado
+// =======================
+
+#[cfg(not(feature = "metrics"))]
+#[test]
+fn metrics_desligado_nao_conta() {
+    metrics::reset();
+    let mut s = BstAvlSet::new();
+    for i in 0..1000 {
+        s.insert(i);
+    }
+    assert_eq!(metrics::snapshot(), metrics::Counts::default());
+}
+
+// =======================
+// Com a feature
+// =======================
+
+#[cfg(feature = "metrics")]
+mod ligado {
+    use super::*;
+    use collections::metrics::Counter;
+    use collections::set::{BstRBSet, HashChainingSet, HashCuckooSet, Rehash};
+
+    /// Comparações médias por busca bem-sucedida numa AVL com `n` elementos.
+    fn comparacoes_por_busca_avl(n: i32) -> f64 {
+        let mut s = BstAvlSet::new();
+        for i in 0..n {
+            s.insert(i);
+        }
+        metrics::reset();
+        for i in 0..n {
+            assert!(s.contains(&i));
+        }
+        metrics::snapshot().get(Counter::Comparisons) as f64 / n as f64
+    }
+
+    #[test]
+    fn metrics_busca_avl_logaritmica() {
+        for n in [1 << 8, 1 << 12, 1 << 16] {
+            let por_busca = comparacoes_por_busca_avl(n);
+            let log_n = (n as f64).log2();
+            // altura da AVL ≤ 1.44 log n
+            assert!(por_busca >= 1.0);
+            assert!(por_busca <= 1.45 * log_n + 2.0, "n = {n}: {por_busca}");
+        }
+    }
+
+    /// Comparações médias por inserção de `n` chaves novas, contra o limite
+    /// da altura da árvore.
+    fn confere_insercao<S: Set<i32> + Default>(fator_altura: f64) {
+        let n = 1 << 12;
+        let mut s = S::default();
+        metrics::reset();
+        for i in 0..n {
+            // chaves em ordem alternada: rotações dos dois lados
+            let k = if i % 2 == 0 { i } else { -i };
+            assert!(s.insert(k));
+        }
+        let por_insercao = metrics::snapshot().get(Counter::Comparisons) as f64 / n as f64;
+        let log_n = (n as f64).log2();
+        assert!(
+            por_insercao <= fator_altura * log_n + 2.0,
+            "{por_insercao} comparações por inserção"
+        );
+    }
+
+    #[test]
+    fn metrics_insercao_desce_uma_vez_so() {
+        // altura da AVL ≤ 1.44 log n; da rubro-negra ≤ 2 log n
+        confere_insercao::<BstAvlSet<i32>>(1.45);
+        confere_insercao::<BstRBSet<i32>>(2.0);
+    }
+
+    #[test]
+    fn metrics_entry_reaproveita_a_busca() {
+        use collections::map::{AvlMap, Map};
+
+        let n = 1 << 12;
+        let mut m = AvlMap::new();
+        for i in 0..n {
+            m.insert(2 * i, 0);
+        }
+        let altura = 1.45 * (n as f64).log2() + 2.0;
+
+        // ausente: a busca de `entry` e a descida da inserção
+        metrics::reset();
+        for i in 0..n {
+            *m.entry(2 * i + 1).or_insert(0) += 1;
+        }
+        let por_entry = metrics::snapshot().get(Counter::Comparisons) as f64 / n as f64;
+        assert!(por_entry <= 2.0 * altura, "{por_entry} comparações por entry");
+    }
+
+    #[test]
+    fn metrics_rubro_negra_rotaciona_e_troca_cores() {
+        metrics::reset();
+        let mut s = BstRBSet::new();
+        for i in 0..1000 {
+            s.insert(i);
+        }
+        let counts = metrics::snapshot();
+        assert!(counts.rotations > 0);
+        assert!(counts.color_flips > 0);
+        assert_eq!(counts.hashes, 0);
+    }
+
+    #[test]
+    fn metrics_from_sorted_iter_sem_rotacoes() {
+        // inserindo uma a uma: n log n comparações e rotações o tempo todo
+        metrics::reset();
+        let _ = BstRBSet::from_sorted_iter(0..10_000).unwrap();
+        let counts = metrics::snapshot();
+        assert_eq!(counts.comparisons, 9_999);
+        assert_eq!(counts.rotations, 0);
+        assert_eq!(counts.color_flips, 0);
+
+        metrics::reset();
+        let _ = BstAvlSet::from_sorted_iter(0..10_000).unwrap();
+        assert_eq!(metrics::snapshot().rotations, 0);
+    }
+
+    #[test]
+    fn metrics_cuckoo_busca_olha_no_maximo_duas_posicoes() {
+        let mut s = HashCuckooSet::new();
+        for i in 0..10_000 {
+            s.insert(i);
+        }
+        metrics::reset();
+        for i in 10_000..20_000 {
+            assert!(!s.contains(&i));
+        }
+        let counts = metrics::snapshot();
+        assert_eq!(counts.hashes, 10_000);
+        assert!(counts.probes <= 2 * 10_000);
+        assert_eq!(counts.rehashes, 0);
+    }
+
+    #[test]
+    fn metrics_encadeamento_conta_rehashes() {
+        metrics::reset();
+        let mut s = HashChainingSet::new();
+        for i in 0..10_000 {
+            s.insert(i);
+        }
+        let counts = metrics::snapshot();
+        assert!(counts.rehashes > 0);
+        assert!(counts.hashes >= 10_000);
+        assert_eq!(counts.rotations, 0);
+    }
+
+    /// Maior número de hashes calculados por uma única inserção.
+    fn pior_insercao(mut s: HashChainingSet<u32>) -> u64 {
+        let mut pior = 0;
+        for i in 0..100_000 {
+            metrics::reset();
+            s.insert(i);
+            pior = pior.max(metrics::snapshot().hashes);
+        }
+        pior
+    }
+
+    #[test]
+    fn metrics_rehash_incremental_limita_pior_insercao() {
+        let de_uma_vez = pior_insercao(HashChainingSet::new());
+        let incremental = pior_insercao(
+            HashChainingSet::builder()
+                .rehash(Rehash::Incremental { buckets_per_op: 4 })
+                .build(),
+        );
+        // de uma vez: o último crescimento re-hasheia dezenas de milhares
+        assert!(de_uma_vez > 40_000, "{de_uma_vez}");
+        // incremental: o hash do elemento + alguns buckets pequenos
+        assert!(incremental < 40, "{incremental}");
+    }
+}
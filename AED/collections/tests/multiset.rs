@@ -0,0 +1,129 @@
+use collections::multiset::{AvlMultiSet, HashMultiSet, ListMultiSet, MultiSet};
+use rand::prelude::*;
+use std::collections::HashMap;
+
+// =======================
+// Helpers genéricos
+// =======================
+
+fn testa_multiset_basico<M: MultiSet<i32>>(mut m: M) {
+    assert!(m.is_empty());
+    assert_eq!(m.total_len(), 0);
+    assert_eq!(m.distinct_len(), 0);
+
+    // repetidos incrementam a contagem
+    assert_eq!(m.insert(10), 1);
+    assert_eq!(m.insert(20), 1);
+    assert_eq!(m.insert(10), 2);
+    assert_eq!(m.insert(10), 3);
+
+    assert_eq!(m.count(&10), 3);
+    assert_eq!(m.count(&20), 1);
+    assert_eq!(m.count(&99), 0);
+    assert!(m.contains(&20));
+    assert!(!m.contains(&99));
+    assert_eq!(m.total_len(), 4);
+    assert_eq!(m.distinct_len(), 2);
+
+    // remove_one tira uma ocorrência por vez
+    assert!(m.remove_one(&10));
+    assert_eq!(m.count(&10), 2);
+    assert_eq!(m.total_len(), 3);
+
+    // a última ocorrência leva o elemento junto
+    assert!(m.remove_one(&20));
+    assert!(!m.contains(&20));
+    assert_eq!(m.distinct_len(), 1);
+    assert!(!m.remove_one(&20));
+
+    // remove_all devolve quantas havia
+    assert_eq!(m.remove_all(&10), 2);
+    assert_eq!(m.remove_all(&10), 0);
+    assert!(m.is_empty());
+    assert_eq!(m.total_len(), 0);
+}
+
+/// Histograma de palavras.
+fn testa_multiset_histograma<M: MultiSet<String> + FromIterator<String>>() {
+    let texto = "o rato roeu a roupa do rei de roma e o rei ficou bravo com o rato";
+    let m: M = texto.split_whitespace().map(String::from).collect();
+
+    assert_eq!(m.count(&"o".to_string()), 3);
+    assert_eq!(m.count(&"rato".to_string()), 2);
+    assert_eq!(m.count(&"roma".to_string()), 1);
+    assert_eq!(m.total_len(), 17);
+    assert_eq!(m.distinct_len(), 13);
+    assert_eq!(m.iter().map(|(_, c)| c).sum::<usize>(), 17);
+}
+
+/// Operações aleatórias contra um `HashMap` de contadores.
+fn testa_multiset_aleatorio<M: MultiSet<i32>>(mut m: M) {
+    let mut rng = StdRng::seed_from_u64(25);
+    let mut reference: HashMap<i32, usize> = HashMap::new();
+
+    for _ in 0..5_000 {
+        let x = rng.gen_range(0..50);
+        match rng.gen_range(0..10) {
+            0..=5 => {
+                let c = reference.entry(x).or_insert(0);
+                *c += 1;
+                assert_eq!(m.insert(x), *c);
+            }
+            6..=8 => {
+                let havia = reference.get(&x).is_some_and(|&c| c > 0);
+                if havia {
+                    *reference.get_mut(&x).unwrap() -= 1;
+                    reference.retain(|_, c| *c > 0);
+                }
+                assert_eq!(m.remove_one(&x), havia);
+            }
+            _ => assert_eq!(m.remove_all(&x), reference.remove(&x).unwrap_or(0)),
+        }
+    }
+
+    assert_eq!(m.distinct_len(), reference.len());
+    assert_eq!(m.total_len(), reference.values().sum::<usize>());
+    for (x, c) in m.iter() {
+        assert_eq!(reference.get(x), Some(&c));
+    }
+}
+
+// =======================
+// Implementações
+// =======================
+
+#[test]
+fn listmultiset_basico() {
+    testa_multiset_basico(ListMultiSet::new());
+    testa_multiset_histograma::<ListMultiSet<String>>();
+    testa_multiset_aleatorio(ListMultiSet::new());
+}
+
+#[test]
+fn avlmultiset_basico() {
+    testa_multiset_basico(AvlMultiSet::new());
+    testa_multiset_histograma::<AvlMultiSet<String>>();
+    testa_multiset_aleatorio(AvlMultiSet::new());
+}
+
+#[test]
+fn hashmultiset_basico() {
+    testa_multiset_basico(HashMultiSet::new());
+    testa_multiset_histograma::<HashMultiSet<String>>();
+    testa_multiset_aleatorio(HashMultiSet::new());
+}
+
+#[test]
+fn avlmultiset_itera_em_ordem() {
+    let m: AvlMultiSet<i32> = [3, 1, 3, 2, 1, 3].into_iter().collect();
+    assert_eq!(m.iter().collect::<Vec<_>>(), vec![(&1, 2), (&2, 1), (&3, 3)]);
+}
+
+#[test]
+fn hashmultiset_com_hasher_customizado() {
+    use std::collections::hash_map::RandomState;
+    let mut m = HashMultiSet::with_hasher(RandomState::new());
+    m.extend([7, 7, 8]);
+    assert_eq!(m.count(&7), 2);
+    assert_eq!(m.distinct_len(), 2);
+}
@@ -100,3 +100,14 @@ fn listqueue_deve_funcionar_com_tipo_nao_copy() {
     assert_eq!(q.dequeue().as_deref(), Some("tchau"));
     assert_eq!(q.dequeue(), None);
 }
+
+#[test]
+fn listqueue_com_milhoes_de_elementos_deve_ser_destruida_sem_estourar_a_pilha() {
+    let mut q = ListQueue::new();
+    for i in 0..3_000_000 {
+        q.enqueue(i);
+    }
+    assert_eq!(q.len(), 3_000_000);
+    assert_eq!(q.front(), Some(&0));
+    drop(q);
+}
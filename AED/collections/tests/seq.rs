@@ -1,4 +1,5 @@
-use collections::seq::{Seq, SeqError, ArraySeq, ListSeq};
+use collections::seq::{Seq, SeqError, ArraySeq, ListSeq, SkipListSeq};
+use rand::prelude::*;
 
 //
 // =======================
@@ -130,6 +131,96 @@ fn test_list_insert_out_of_bounds() {
     }
 }
 
+//
+// =======================
+// Testes SKIPLISTSEQ
+// =======================
+//
+
+#[test]
+fn test_skiplist_basic() {
+    let mut l = SkipListSeq::with_seed(7);
+
+    l.append(10);
+    l.append(20);
+    l.append(30);
+
+    assert_eq!(l[0], 10);
+    assert_eq!(l[1], 20);
+    assert_eq!(l[2], 30);
+
+    l.insert_at(1, 99).unwrap();
+    assert_eq!(l[1], 99);
+
+    let removed = l.remove_from(2);
+    assert_eq!(removed, Some(20));
+    assert_eq!(l.remove_from(3), None);
+}
+
+#[test]
+fn test_skiplist_iter() {
+    let mut l = SkipListSeq::with_seed(7);
+    l = fill_three(l);
+
+    let collected: Vec<_> = l.iter().copied().collect();
+    assert_eq!(collected, vec![1, 2, 3]);
+
+    for x in l.iter_mut() {
+        *x *= 2;
+    }
+
+    let collected2: Vec<_> = l.iter().copied().collect();
+    assert_eq!(collected2, vec![2, 4, 6]);
+}
+
+#[test]
+fn test_skiplist_insert_out_of_bounds() {
+    let mut l = SkipListSeq::new();
+    l.append(10);
+
+    let err = l.insert_at(5, 99).unwrap_err();
+    match err {
+        SeqError::OutOfBounds { index, len } => {
+            assert_eq!(index, 5);
+            assert_eq!(len, 1);
+        }
+        _ => panic!("erro errado"),
+    }
+}
+
+#[test]
+fn test_skiplist_random_against_vec() {
+    // inserções e remoções em posições aleatórias, comparando com um Vec
+    for seed in 0..10 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut l = SkipListSeq::with_seed(seed);
+        let mut v: Vec<i32> = Vec::new();
+
+        for step in 0..2_000 {
+            if v.is_empty() || rng.gen_bool(0.6) {
+                let i = rng.gen_range(0..=v.len());
+                l.insert_at(i, step).unwrap();
+                v.insert(i, step);
+            } else {
+                let i = rng.gen_range(0..v.len());
+                assert_eq!(l.remove_from(i), Some(v.remove(i)));
+            }
+
+            assert_eq!(l.len(), v.len());
+            if !v.is_empty() {
+                let i = rng.gen_range(0..v.len());
+                assert_eq!(l.get(i), v.get(i));
+            }
+        }
+
+        let collected: Vec<_> = l.iter().copied().collect();
+        assert_eq!(collected, v);
+        for (i, x) in v.iter().enumerate() {
+            assert_eq!(l[i], *x);
+        }
+    }
+}
+
 //
 // =======================
 // Teste comum
@@ -150,3 +241,15 @@ fn test_remove_out_of_bounds() {
     assert_eq!(a.remove_from(5), None);
     assert_eq!(l.remove_from(5), None);
 }
+
+#[test]
+fn test_list_drop_millions() {
+    // inserir sempre na cabeça é O(1): monta a lista rapidamente
+    let mut s = ListSeq::new();
+    for i in 0..3_000_000 {
+        s.insert_at(0, i).unwrap();
+    }
+    assert_eq!(s.len(), 3_000_000);
+    assert_eq!(s.get(0), Some(&2_999_999));
+    drop(s);
+}
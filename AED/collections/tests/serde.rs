@@ -0,0 +1,232 @@
+#![cfg(feature = "serde")]
+
+use collections::queue::{ArrayQueue, ListQueue, Queue};
+use collections::seq::{ArraySeq, ListSeq, Seq};
+use collections::set::{BTreeSet, BstAvlSet, BstRBSet, BstSet, PersistentAvlSet, SkipListSet, SplaySet, TreapSet};
+use collections::set::{DoubleHashing, HashChainingSet, HashCuckooSet, HashProbingSet, HashRobinHoodSet, ListSet};
+use collections::set::Set;
+use collections::stack::{ArrayStack, ListStack, Stack};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::hash_map::RandomState;
+
+fn ida_e_volta<C: Serialize + DeserializeOwned>(c: &C) -> (String, C) {
+    let json = serde_json::to_string(c).unwrap();
+    let back = serde_json::from_str(&json).unwrap();
+    (json, back)
+}
+
+// =======================
+// Sequências, pilhas e filas
+// =======================
+
+fn testa_seq<Q: Seq<i32> + Default + Serialize + DeserializeOwned>() {
+    let mut s = Q::default();
+    for x in [3, 1, 2] {
+        s.append(x);
+    }
+    s.prepend(0);
+
+    let (json, back) = ida_e_volta(&s);
+    assert_eq!(json, "[0,3,1,2]");
+    assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![0, 3, 1, 2]);
+
+    let vazia: Q = serde_json::from_str("[]").unwrap();
+    assert!(vazia.is_empty());
+}
+
+#[test]
+fn arrayseq_serde_ordem_logica() {
+    testa_seq::<ArraySeq<i32>>();
+}
+
+#[test]
+fn listseq_serde_ordem_logica() {
+    testa_seq::<ListSeq<i32>>();
+}
+
+fn testa_pilha<S: Stack<i32> + Default + Serialize + DeserializeOwned>() {
+    let mut s = S::default();
+    for x in 1..=5 {
+        s.push(x);
+    }
+
+    // da base para o topo
+    let (json, mut back) = ida_e_volta(&s);
+    assert_eq!(json, "[1,2,3,4,5]");
+    for x in (1..=5).rev() {
+        assert_eq!(back.pop(), Some(x));
+    }
+    assert!(back.is_empty());
+}
+
+#[test]
+fn arraystack_serde_base_ao_topo() {
+    testa_pilha::<ArrayStack<i32>>();
+}
+
+#[test]
+fn liststack_serde_base_ao_topo() {
+    testa_pilha::<ListStack<i32>>();
+}
+
+fn testa_fila<Q: Queue<i32> + Default + Serialize + DeserializeOwned>() {
+    // tira e põe para o anel da `ArrayQueue` dar a volta no arranjo
+    let mut q = Q::default();
+    for x in 0..4 {
+        q.enqueue(x);
+    }
+    q.dequeue();
+    q.dequeue();
+    for x in 4..7 {
+        q.enqueue(x);
+    }
+
+    let (json, mut back) = ida_e_volta(&q);
+    assert_eq!(json, "[2,3,4,5,6]");
+    for x in 2..7 {
+        assert_eq!(back.dequeue(), Some(x));
+    }
+    assert!(back.is_empty());
+}
+
+#[test]
+fn arrayqueue_serde_frente_ao_fim() {
+    testa_fila::<ArrayQueue<i32>>();
+}
+
+#[test]
+fn listqueue_serde_frente_ao_fim() {
+    testa_fila::<ListQueue<i32>>();
+}
+
+// =======================
+// Conjuntos
+// =======================
+
+/// Árvores: gravam em ordem crescente e aceitam entrada fora de ordem e
+/// com repetidos.
+fn testa_conjunto_ordenado<S>(check: impl Fn(&S))
+where
+    S: Set<i32> + FromIterator<i32> + Serialize + DeserializeOwned,
+{
+    let s: S = [5, 3, 8, 1, 9, 2].into_iter().collect();
+    let (json, back) = ida_e_volta(&s);
+    assert_eq!(json, "[1,2,3,5,8,9]");
+    assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 5, 8, 9]);
+    check(&back);
+
+    let bagunca: S = serde_json::from_str("[4, 2, 4, 0, 1, 3, 2, 0]").unwrap();
+    assert_eq!(bagunca.len(), 5);
+    assert_eq!(bagunca.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+    check(&bagunca);
+
+    let grande: Vec<i32> = (0..10_000).collect();
+    let json = serde_json::to_string(&grande).unwrap();
+    let grande: S = serde_json::from_str(&json).unwrap();
+    assert_eq!(grande.len(), 10_000);
+    check(&grande);
+}
+
+#[test]
+fn bstset_serde() {
+    testa_conjunto_ordenado::<BstSet<i32>>(|s| s.check_invariants().unwrap());
+}
+
+#[test]
+fn bstavlset_serde() {
+    testa_conjunto_ordenado::<BstAvlSet<i32>>(|s| s.check_invariants().unwrap());
+}
+
+#[test]
+fn bstrbset_serde() {
+    testa_conjunto_ordenado::<BstRBSet<i32>>(|s| s.check_invariants().unwrap());
+}
+
+#[test]
+fn splayset_serde() {
+    testa_conjunto_ordenado::<SplaySet<i32>>(|s| s.check_invariants().unwrap());
+}
+
+#[test]
+fn treapset_serde() {
+    testa_conjunto_ordenado::<TreapSet<i32>>(|s| s.check_invariants().unwrap());
+}
+
+#[test]
+fn btreeset_serde() {
+    testa_conjunto_ordenado::<BTreeSet<i32>>(|s| s.check_invariants().unwrap());
+}
+
+#[test]
+fn persistentavlset_serde() {
+    testa_conjunto_ordenado::<PersistentAvlSet<i32>>(|s| s.check_invariants().unwrap());
+}
+
+#[test]
+fn skiplistset_serde() {
+    testa_conjunto_ordenado::<SkipListSet<i32>>(|_| ());
+}
+
+#[test]
+fn bstset_desserializada_fica_balanceada() {
+    // entrada ordenada: inserida em sequência, a `BstSet` viraria uma lista
+    let s: BstSet<i32> = serde_json::from_str("[0, 1, 2, 3, 4, 5, 6]").unwrap();
+    let dot = s.to_dot();
+    // árvore perfeita 3 (1 (0 2)) (5 (4 6)); o DOT numera os filhos de
+    // cada nó ao visitá-lo
+    for (id, x) in [3, 1, 5, 0, 2, 4, 6].into_iter().enumerate() {
+        assert!(dot.contains(&format!("n{id} [label=\"{x}\"]")), "{dot}");
+    }
+}
+
+/// Tabelas de hash e lista: a ordem não importa, só o conteúdo.
+fn testa_conjunto_sem_ordem<S>()
+where
+    S: Set<i32> + FromIterator<i32> + Serialize + DeserializeOwned,
+{
+    let s: S = (0..500).collect();
+    let (json, back) = ida_e_volta(&s);
+
+    let mut gravados: Vec<i32> = serde_json::from_str(&json).unwrap();
+    gravados.sort_unstable();
+    assert_eq!(gravados, (0..500).collect::<Vec<_>>());
+
+    assert_eq!(back.len(), 500);
+    assert!((0..500).all(|x| back.contains(&x)));
+
+    let repetidos: S = serde_json::from_str("[7, 7, 3, 7]").unwrap();
+    assert_eq!(repetidos.len(), 2);
+}
+
+#[test]
+fn listset_serde() {
+    testa_conjunto_sem_ordem::<ListSet<i32>>();
+}
+
+#[test]
+fn hashchainingset_serde() {
+    testa_conjunto_sem_ordem::<HashChainingSet<i32>>();
+}
+
+#[test]
+fn hashprobingset_serde() {
+    testa_conjunto_sem_ordem::<HashProbingSet<i32>>();
+    testa_conjunto_sem_ordem::<HashProbingSet<i32, RandomState, DoubleHashing>>();
+}
+
+#[test]
+fn hashrobinhoodset_serde() {
+    testa_conjunto_sem_ordem::<HashRobinHoodSet<i32>>();
+}
+
+#[test]
+fn hashcuckooset_serde() {
+    testa_conjunto_sem_ordem::<HashCuckooSet<i32>>();
+}
+
+#[test]
+fn serde_rejeita_entrada_que_nao_e_lista() {
+    assert!(serde_json::from_str::<BstAvlSet<i32>>("{\"a\": 1}").is_err());
+    assert!(serde_json::from_str::<ArraySeq<i32>>("[1, \"dois\"]").is_err());
+}
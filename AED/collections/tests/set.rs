@@ -1,5 +1,14 @@
-use collections::set::{ListSet, BstSet, BstRBSet, BstAvlSet, HashChainingSet, HashProbingSet};
-use collections::set::Set;
+use collections::set::{ListSet, BstSet, BstRBSet, BstAvlSet, HashChainingSet, HashProbingSet, SkipListSet};
+use collections::set::{BTreeSet, PersistentAvlSet, SplaySet, TreapSet};
+use collections::set::{LinearProbing, QuadraticProbing, DoubleHashing};
+use collections::set::{HashCuckooSet, HashRobinHoodSet};
+use collections::set::{ChainingStats, IndexFn, Rehash};
+use collections::set::{ConcurrentSet, StripedHashSet};
+use std::collections::hash_map::RandomState;
+use collections::set::{InvariantError, Lookup, OrderedSet, Set, SetName, UnsortedError};
+use collections::set::{ByKey, Comparator, NaturalOrder, ReverseOrder};
+use rand::prelude::*;
+use std::collections::BTreeSet as StdBTreeSet;
 
 // =======================
 // Helpers genéricos
@@ -34,6 +43,147 @@ fn testa_set_basico<S: Set<i32>>(mut s: S) {
     assert_eq!(s.len(), 2);
 }
 
+/// Muitas inserções e remoções intercaladas: força vários
+/// redimensionamentos (e, nas tabelas abertas, acúmulo de lápides).
+fn testa_set_carga<S: Set<i32>>(mut s: S) {
+    for i in 0..1000 {
+        assert!(s.insert(i));
+    }
+    assert_eq!(s.len(), 1000);
+
+    // remove os pares
+    for i in (0..1000).step_by(2) {
+        assert!(s.remove(&i));
+    }
+    assert_eq!(s.len(), 500);
+
+    for i in 0..1000 {
+        assert_eq!(s.contains(&i), i % 2 == 1);
+    }
+
+    // reinsere os pares e alguns novos
+    for i in (0..1000).step_by(2) {
+        assert!(s.insert(i));
+    }
+    for i in 1000..1500 {
+        assert!(s.insert(i));
+    }
+    assert_eq!(s.len(), 1500);
+
+    for i in 0..1500 {
+        assert!(s.contains(&i));
+        assert!(!s.insert(i));
+    }
+    assert!(!s.contains(&-1));
+    assert!(!s.contains(&1500));
+}
+
+/// `iter`, `FromIterator`, `Extend` e `IntoIterator` (por valor e por referência).
+/// Devolve os elementos na ordem em que o conjunto os percorre.
+fn testa_set_iteradores<S>() -> Vec<i32>
+where
+    S: Set<i32> + FromIterator<i32> + Extend<i32> + IntoIterator<Item = i32>,
+    for<'a> &'a S: IntoIterator<Item = &'a i32>,
+{
+    let mut s: S = [5, 3, 8, 3, 1].into_iter().collect();
+    assert_eq!(s.len(), 4);
+
+    s.extend(vec![9, 1, 7]);
+    assert_eq!(s.len(), 6);
+
+    let mut por_iter: Vec<i32> = s.iter().copied().collect();
+    let por_ref: Vec<i32> = (&s).into_iter().copied().collect();
+    assert_eq!(por_iter, por_ref);
+    assert_eq!(por_iter.len(), 6);
+
+    let por_valor: Vec<i32> = s.into_iter().collect();
+    assert_eq!(por_valor, por_ref);
+
+    por_iter.sort();
+    assert_eq!(por_iter, vec![1, 3, 5, 7, 8, 9]);
+
+    por_valor
+}
+
+fn testa_ordered_set<S: OrderedSet<i32>>(mut s: S) {
+    assert_eq!(s.min(), None);
+    assert_eq!(s.max(), None);
+    assert_eq!(s.pop_min(), None);
+    assert_eq!(s.pop_max(), None);
+    assert_eq!(s.range(..).count(), 0);
+
+    // 0, 10, 20, ..., 90 em ordem embaralhada
+    for v in [50, 20, 80, 10, 30, 70, 90, 0, 40, 60] {
+        s.insert(v);
+    }
+
+    assert_eq!(s.min(), Some(&0));
+    assert_eq!(s.max(), Some(&90));
+
+    assert_eq!(s.floor(&35), Some(&30));
+    assert_eq!(s.floor(&30), Some(&30));
+    assert_eq!(s.floor(&-1), None);
+    assert_eq!(s.ceiling(&35), Some(&40));
+    assert_eq!(s.ceiling(&40), Some(&40));
+    assert_eq!(s.ceiling(&91), None);
+
+    assert_eq!(s.successor(&30), Some(&40));
+    assert_eq!(s.successor(&35), Some(&40));
+    assert_eq!(s.successor(&90), None);
+    assert_eq!(s.predecessor(&30), Some(&20));
+    assert_eq!(s.predecessor(&35), Some(&30));
+    assert_eq!(s.predecessor(&0), None);
+
+    let todos: Vec<i32> = s.range(..).copied().collect();
+    assert_eq!(todos, vec![0, 10, 20, 30, 40, 50, 60, 70, 80, 90]);
+
+    let meio: Vec<i32> = s.range(20..50).copied().collect();
+    assert_eq!(meio, vec![20, 30, 40]);
+
+    let fechado: Vec<i32> = s.range(15..=50).copied().collect();
+    assert_eq!(fechado, vec![20, 30, 40, 50]);
+
+    let cauda: Vec<i32> = s.range(75..).copied().collect();
+    assert_eq!(cauda, vec![80, 90]);
+
+    assert_eq!(s.range(41..49).count(), 0);
+    let (ini, fim) = (50, 30);
+    assert_eq!(s.range(ini..fim).count(), 0);
+
+    assert_eq!(s.pop_min(), Some(0));
+    assert_eq!(s.pop_max(), Some(90));
+    assert_eq!(s.len(), 8);
+    assert!(!s.contains(&0));
+    assert!(!s.contains(&90));
+
+    // esvazia alternando as pontas: sai sempre em ordem
+    let mut saida = Vec::new();
+    while let Some(v) = s.pop_min() {
+        saida.push(v);
+        if let Some(w) = s.pop_max() {
+            saida.push(w);
+        }
+    }
+    assert_eq!(saida, vec![10, 80, 20, 70, 30, 60, 40, 50]);
+    assert!(s.is_empty());
+}
+
+fn testa_ordered_set_carga<S: OrderedSet<i32>>(mut s: S) {
+    // inserções em ordem "embaralhada" determinística
+    for i in 0..1000 {
+        s.insert((i * 37) % 1000);
+    }
+
+    let intervalo: Vec<i32> = s.range(100..200).copied().collect();
+    assert_eq!(intervalo, (100..200).collect::<Vec<_>>());
+
+    for i in 0..500 {
+        assert_eq!(s.pop_min(), Some(i));
+        assert_eq!(s.pop_max(), Some(999 - i));
+    }
+    assert!(s.is_empty());
+}
+
 // =======================
 // ListSet
 // =======================
@@ -54,18 +204,456 @@ fn bstset_basico() {
     testa_set_basico(s);
 }
 
+#[test]
+fn bstset_ordenado() {
+    testa_ordered_set(BstSet::new());
+    testa_ordered_set_carga(BstSet::new());
+}
+
+#[test]
+fn bstset_degenerada_nao_estoura_pilha() {
+    // inserções em ordem viram um caminho de comprimento n; numa thread com
+    // pilha pequena, qualquer recursão por nó estouraria
+    let n = 10_000;
+
+    let t = std::thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(move || {
+            let mut s = BstSet::new();
+            for x in 0..n {
+                assert!(s.insert(x));
+            }
+            assert!(!s.insert(n - 1));
+            assert!(s.contains(&(n - 1)));
+            assert!(!s.contains(&n));
+
+            // remove do fundo do caminho e do meio
+            assert!(s.remove(&(n - 1)));
+            assert!(s.remove(&(n / 2)));
+            assert!(!s.remove(&(n / 2)));
+            assert_eq!(s.len(), n as usize - 2);
+            assert_eq!(s.max(), Some(&(n - 2)));
+
+            // decrescente: caminho só à esquerda
+            let mut d = BstSet::new();
+            for x in (0..n).rev() {
+                d.insert(x);
+            }
+            assert_eq!(d.min(), Some(&0));
+            // `s` e `d` são destruídos aqui
+        })
+        .unwrap();
+
+    t.join().unwrap();
+}
+
 #[test]
 fn bstavlset_basico() {
     let s = BstAvlSet::new();
     testa_set_basico(s);
 }
 
+#[test]
+fn bstavlset_ordenado() {
+    testa_ordered_set(BstAvlSet::new());
+    testa_ordered_set_carga(BstAvlSet::new());
+}
+
 #[test]
 fn bstrbset_basico() {
     let s = BstRBSet::new();
     testa_set_basico(s);
 }
 
+#[test]
+fn bstrbset_ordenado() {
+    testa_ordered_set(BstRBSet::new());
+    testa_ordered_set_carga(BstRBSet::new());
+}
+
+// =======================
+// SkipListSet
+// =======================
+
+#[test]
+fn skiplistset_basico() {
+    testa_set_basico(SkipListSet::new());
+    testa_set_carga(SkipListSet::new());
+}
+
+#[test]
+fn skiplistset_ordenado() {
+    testa_ordered_set(SkipListSet::with_seed(1));
+    testa_ordered_set_carga(SkipListSet::with_seed(2));
+}
+
+#[test]
+fn skiplistset_aleatorio_contra_btreeset() {
+    for seed in 0..10 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut s = SkipListSet::with_seed(seed);
+        let mut referencia = StdBTreeSet::new();
+
+        for _ in 0..2_000 {
+            let x = rng.gen_range(0..300);
+            if rng.gen_bool(0.6) {
+                assert_eq!(s.insert(x), referencia.insert(x));
+            } else {
+                assert_eq!(Set::remove(&mut s, &x), referencia.remove(&x));
+            }
+            assert_eq!(s.len(), referencia.len());
+        }
+
+        assert!(s.iter().eq(referencia.iter()));
+        assert!(s.range(100..200).eq(referencia.range(100..200)));
+    }
+}
+
+#[test]
+fn skiplistset_mesma_semente_mesmo_resultado() {
+    // a estrutura interna depende só da semente e das operações;
+    // o conteúdo visível tem de ser o mesmo de qualquer forma
+    let a: Vec<i32> = {
+        let mut s = SkipListSet::with_seed(42);
+        s.extend([5, 3, 9, 1, 7]);
+        s.into_iter().collect()
+    };
+    let b: Vec<i32> = {
+        let mut s = SkipListSet::with_seed(42);
+        s.extend([5, 3, 9, 1, 7]);
+        s.into_iter().collect()
+    };
+    assert_eq!(a, b);
+    assert_eq!(a, vec![1, 3, 5, 7, 9]);
+}
+
+// =======================
+// TreapSet
+// =======================
+
+#[test]
+fn treapset_basico() {
+    testa_set_basico(TreapSet::new());
+    testa_set_carga(TreapSet::new());
+}
+
+#[test]
+fn treapset_ordenado() {
+    testa_ordered_set(TreapSet::with_seed(1));
+    testa_ordered_set_carga(TreapSet::with_seed(2));
+}
+
+#[test]
+fn treapset_split_e_join() {
+    let s: TreapSet<i32> = (0..100).map(|x| x * 2).collect();
+
+    // chave presente
+    let (menores, meio, maiores) = s.split(&50);
+    assert_eq!(meio, Some(50));
+    assert_eq!(menores.iter().copied().collect::<Vec<_>>(), (0..25).map(|x| x * 2).collect::<Vec<_>>());
+    assert_eq!(maiores.iter().copied().collect::<Vec<_>>(), (26..100).map(|x| x * 2).collect::<Vec<_>>());
+    assert_eq!(menores.len(), 25);
+    assert_eq!(maiores.len(), 74);
+    menores.check_invariants().unwrap();
+    maiores.check_invariants().unwrap();
+
+    // chave ausente
+    let (a, meio, b) = maiores.split(&101);
+    assert_eq!(meio, None);
+    assert_eq!((a.len(), b.len()), (25, 49));
+    assert_eq!(a.max(), Some(&100));
+    assert_eq!(b.min(), Some(&102));
+
+    // join devolve tudo menos as chaves cortadas
+    let s = TreapSet::join(TreapSet::join(menores, a), b);
+    s.check_invariants().unwrap();
+    assert_eq!(s.len(), 99);
+    assert!(!s.contains(&50));
+    assert!(s.contains(&48) && s.contains(&52));
+
+    // corte nos extremos
+    let (vazio, _, tudo) = s.split(&-1);
+    assert!(vazio.is_empty());
+    assert_eq!(tudo.len(), 99);
+}
+
+#[test]
+fn treapset_split_depois_de_remocoes() {
+    // os tamanhos das partes saem dos nós: precisam acompanhar remoções,
+    // pop_min/pop_max e as operações em lote
+    let mut rng = StdRng::seed_from_u64(12);
+    let mut s = TreapSet::with_seed(12);
+    let mut esperado = StdBTreeSet::new();
+    for _ in 0..2000 {
+        let x = rng.gen_range(0..1000);
+        if rng.gen_bool(0.6) {
+            assert_eq!(s.insert(x), esperado.insert(x));
+        } else {
+            assert_eq!(s.remove(&x), esperado.remove(&x));
+        }
+    }
+    assert_eq!(s.pop_min(), esperado.pop_first());
+    assert_eq!(s.pop_max(), esperado.pop_last());
+    s.check_invariants().unwrap();
+
+    let extra: TreapSet<i32> = (900..1100).collect();
+    esperado.extend(900..1100);
+    let s = s.union_with(extra);
+
+    let (menores, meio, maiores) = s.split(&500);
+    assert_eq!(meio.is_some(), esperado.contains(&500));
+    assert_eq!(menores.len(), esperado.range(..500).count());
+    assert_eq!(maiores.len(), esperado.range(501..).count());
+    menores.check_invariants().unwrap();
+    maiores.check_invariants().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "se sobrepõem")]
+fn treapset_join_com_sobreposicao() {
+    let a: TreapSet<i32> = (0..10).collect();
+    let b: TreapSet<i32> = (5..15).collect();
+    TreapSet::join(a, b);
+}
+
+#[test]
+fn treapset_operacoes_em_lote_contra_btreeset() {
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for _ in 0..20 {
+        let n = rng.gen_range(0..300);
+        let m = rng.gen_range(0..300);
+        let xs: StdBTreeSet<i32> = (0..n).map(|_| rng.gen_range(0..500)).collect();
+        let ys: StdBTreeSet<i32> = (0..m).map(|_| rng.gen_range(0..500)).collect();
+
+        let a = || xs.iter().copied().collect::<TreapSet<i32>>();
+        let b = || ys.iter().copied().collect::<TreapSet<i32>>();
+
+        let casos = [
+            (a().union_with(b()), xs.union(&ys).copied().collect::<Vec<_>>()),
+            (a().intersection_with(b()), xs.intersection(&ys).copied().collect()),
+            (a().difference_with(b()), xs.difference(&ys).copied().collect()),
+        ];
+
+        for (resultado, esperado) in casos {
+            resultado.check_invariants().unwrap();
+            assert_eq!(resultado.len(), esperado.len());
+            assert_eq!(resultado.iter().copied().collect::<Vec<_>>(), esperado);
+        }
+    }
+}
+
+// =======================
+// SplaySet
+// =======================
+
+#[test]
+fn splayset_basico() {
+    testa_set_basico(SplaySet::new());
+    testa_set_carga(SplaySet::new());
+}
+
+#[test]
+fn splayset_ordenado() {
+    testa_ordered_set(SplaySet::new());
+    testa_ordered_set_carga(SplaySet::new());
+}
+
+#[test]
+fn splayset_buscas_reorganizam_sem_perder_elementos() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut s = SplaySet::new();
+    let mut referencia = StdBTreeSet::new();
+
+    for passo in 0..3000 {
+        let x = rng.gen_range(0..300);
+        match rng.gen_range(0..3) {
+            0 => assert_eq!(s.insert(x), referencia.insert(x)),
+            1 => assert_eq!(Set::remove(&mut s, &x), referencia.remove(&x)),
+            _ => assert_eq!(s.contains(&x), referencia.contains(&x)),
+        }
+
+        if let Err(e) = s.check_invariants() {
+            panic!("passo {passo}: {e}");
+        }
+    }
+
+    assert!(s.iter().eq(referencia.iter()));
+}
+
+#[test]
+fn splayset_busca_durante_iteracao() {
+    let s: SplaySet<i32> = (0..100).rev().collect();
+
+    // cada busca no meio do percurso não pode mudar a ordem visitada
+    let mut visitados = Vec::new();
+    for x in s.iter() {
+        assert!(s.contains(&(99 - x)));
+        visitados.push(*x);
+    }
+    assert_eq!(visitados, (0..100).collect::<Vec<_>>());
+
+    // sem iteradores vivos, as buscas voltam a reorganizar
+    assert!(s.contains(&50));
+    assert_eq!(s.get(&7), Some(&7));
+    s.check_invariants().unwrap();
+}
+
+#[test]
+fn splayset_caminho_longo_nao_estoura_pilha() {
+    com_pilha_pequena(|| {
+        // inserções crescentes deixam um caminho só à esquerda
+        let s: SplaySet<i32> = (0..10_000).collect();
+        assert!(s.contains(&0));
+        assert!(s.contains(&9_999));
+        assert_eq!(s.into_iter().count(), 10_000);
+    });
+}
+
+// =======================
+// PersistentAvlSet
+// =======================
+
+#[test]
+fn persistentavlset_basico() {
+    testa_set_basico(PersistentAvlSet::new());
+    testa_set_carga(PersistentAvlSet::new());
+}
+
+#[test]
+fn persistentavlset_ordenado() {
+    testa_ordered_set(PersistentAvlSet::new());
+    testa_ordered_set_carga(PersistentAvlSet::new());
+}
+
+#[test]
+fn persistentavlset_versoes_antigas_intactas() {
+    // pilha de "undo": guarda todas as versões e confere cada uma no fim
+    let mut rng = StdRng::seed_from_u64(20);
+    let mut versoes = vec![PersistentAvlSet::new()];
+    let mut esperados = vec![StdBTreeSet::new()];
+
+    for _ in 0..2_000 {
+        let x = rng.gen_range(0..300);
+        let atual = versoes.last().unwrap();
+        let mut esperado = esperados.last().unwrap().clone();
+        let proxima = if rng.gen_bool(0.6) {
+            esperado.insert(x);
+            atual.with(x)
+        } else {
+            esperado.remove(&x);
+            atual.without(&x)
+        };
+        versoes.push(proxima);
+        esperados.push(esperado);
+    }
+
+    for (versao, esperado) in versoes.iter().zip(&esperados) {
+        assert_eq!(versao.check_invariants(), Ok(()));
+        assert!(versao.iter().eq(esperado.iter()));
+    }
+}
+
+#[test]
+fn persistentavlset_operacao_sem_efeito_devolve_a_mesma_arvore() {
+    let v1: PersistentAvlSet<i32> = (0..100).collect();
+    assert!(v1.with(50).ptr_eq(&v1));
+    assert!(v1.without(&500).ptr_eq(&v1));
+    assert!(!v1.with(500).ptr_eq(&v1));
+    assert!(!v1.without(&50).ptr_eq(&v1));
+    assert!(v1.clone().ptr_eq(&v1));
+}
+
+thread_local! {
+    static CLONES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Inteiro que conta quantas vezes foi clonado.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct Contado(i32);
+
+impl Clone for Contado {
+    fn clone(&self) -> Self {
+        CLONES.with(|c| c.set(c.get() + 1));
+        Contado(self.0)
+    }
+}
+
+fn clones_durante(f: impl FnOnce()) -> usize {
+    CLONES.with(|c| c.set(0));
+    f();
+    CLONES.with(|c| c.get())
+}
+
+#[test]
+fn persistentavlset_copia_so_o_caminho() {
+    let n = 1 << 14;
+    let mut base = PersistentAvlSet::new();
+    // versão única: modifica no lugar, sem clonar nada
+    assert_eq!(clones_durante(|| base.extend((0..n).map(Contado))), 0);
+
+    // versão compartilhada: só o caminho (≈ altura, mais as rotações)
+    let altura_max = (1.45 * (n as f64).log2()) as usize + 2;
+    let mut nova = base.clone();
+    let copiados = clones_durante(|| {
+        nova.insert(Contado(n));
+    });
+    assert!(copiados > 0 && copiados <= altura_max + 4, "{copiados}");
+
+    let copiados = clones_durante(|| {
+        nova.remove(&Contado(n / 2));
+    });
+    assert!(copiados <= 2 * altura_max + 4, "{copiados}");
+
+    assert_eq!(base.len(), n as usize);
+    assert!(base.contains(&Contado(n / 2)));
+    assert!(!base.contains(&Contado(n)));
+    assert_eq!(nova.len(), n as usize);
+    assert!(!nova.contains(&Contado(n / 2)));
+    assert_eq!(base.check_invariants(), Ok(()));
+    assert_eq!(nova.check_invariants(), Ok(()));
+}
+
+// =======================
+// BTreeSet
+// =======================
+
+#[test]
+fn btreeset_basico() {
+    testa_set_basico(BTreeSet::<i32>::new());
+    testa_set_carga(BTreeSet::<i32>::new());
+    testa_set_carga(BTreeSet::<i32, 2>::new());
+}
+
+#[test]
+fn btreeset_ordenado() {
+    testa_ordered_set(BTreeSet::<i32>::new());
+    testa_ordered_set_carga(BTreeSet::<i32>::new());
+    testa_ordered_set(BTreeSet::<i32, 2>::new());
+    testa_ordered_set_carga(BTreeSet::<i32, 3>::new());
+}
+
+#[test]
+fn btreeset_poucas_folhas_e_raiz_baixa() {
+    // com B = 32 cabem 63 chaves por nó: 1000 elementos cabem em 2 níveis
+    let s: BTreeSet<i32, 32> = (0..1000).collect();
+    assert_eq!(s.check_invariants(), Ok(()));
+    assert!(s.iter().copied().eq(0..1000));
+}
+
+#[test]
+fn btreeset_nome_inclui_o_grau() {
+    // qualquer grau vira uma série própria no CSV dos benchmarks
+    assert_eq!(<BTreeSet<i32> as SetName>::name(), "BTreeSet<6>");
+    assert_eq!(<BTreeSet<i32, 2> as SetName>::name(), "BTreeSet<2>");
+    assert_eq!(<BTreeSet<i32, 7> as SetName>::name(), "BTreeSet<7>");
+    assert_eq!(<BTreeSet<i32, 1000> as SetName>::name(), "BTreeSet<1000>");
+    assert_eq!(
+        <BTreeSet<i32, { usize::MAX }> as SetName>::name(),
+        format!("BTreeSet<{}>", usize::MAX)
+    );
+}
+
 // =======================
 // HashSet 
 // =======================
@@ -82,3 +670,1133 @@ fn hashset_probing_basico() {
     testa_set_basico(s);
 }
 
+#[test]
+fn hashset_probing_linear_carga() {
+    let s: HashProbingSet<i32, RandomState, LinearProbing> = HashProbingSet::new();
+    testa_set_carga(s);
+}
+
+#[test]
+fn hashset_probing_quadratica_carga() {
+    let s: HashProbingSet<i32, RandomState, QuadraticProbing> = HashProbingSet::new();
+    testa_set_carga(s);
+}
+
+#[test]
+fn hashset_probing_duplo_carga() {
+    let s: HashProbingSet<i32, RandomState, DoubleHashing> = HashProbingSet::new();
+    testa_set_carga(s);
+}
+
+#[test]
+fn hashset_probing_lapides_nao_estouram_tabela() {
+    // insere e remove sempre o mesmo "tamanho" de conjunto:
+    // a carga real nunca passa de 8, mas as lápides acumulam.
+    let mut s: HashProbingSet<i32> = HashProbingSet::new();
+    for round in 0..1000 {
+        for i in 0..8 {
+            assert!(s.insert(round * 8 + i));
+        }
+        for i in 0..8 {
+            assert!(s.remove(&(round * 8 + i)));
+        }
+    }
+    assert!(s.is_empty());
+    // a tabela é reconstruída no lugar, sem crescer indefinidamente
+    assert!(s.capacity() <= 64);
+}
+
+#[test]
+fn hashset_chaining_carga() {
+    let s: HashChainingSet<i32> = HashChainingSet::new();
+    testa_set_carga(s);
+}
+
+#[test]
+fn hashset_robin_hood_basico() {
+    testa_set_basico(HashRobinHoodSet::new());
+    testa_set_carga(HashRobinHoodSet::new());
+}
+
+#[test]
+fn hashset_cuckoo_basico() {
+    testa_set_basico(HashCuckooSet::new());
+    testa_set_carga(HashCuckooSet::new());
+}
+
+/// Operações aleatórias contra o `HashSet` do `std`, com muitas remoções
+/// (o deslocamento para trás do Robin Hood e as reconstruções do cuco).
+fn testa_hash_aleatorio<S: Set<i32>>(mut s: S) {
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut referencia = std::collections::HashSet::new();
+
+    for _ in 0..20_000 {
+        let x = rng.gen_range(0..2_000);
+        match rng.gen_range(0..3) {
+            0 => assert_eq!(s.insert(x), referencia.insert(x)),
+            1 => assert_eq!(s.remove(&x), referencia.remove(&x)),
+            _ => assert_eq!(s.contains(&x), referencia.contains(&x)),
+        }
+        assert_eq!(s.len(), referencia.len());
+    }
+
+    let mut todos: Vec<i32> = s.iter().copied().collect();
+    todos.sort();
+    let mut esperado: Vec<i32> = referencia.into_iter().collect();
+    esperado.sort();
+    assert_eq!(todos, esperado);
+}
+
+#[test]
+fn hashset_robin_hood_aleatorio() {
+    testa_hash_aleatorio(HashRobinHoodSet::new());
+}
+
+#[test]
+fn hashset_cuckoo_aleatorio() {
+    testa_hash_aleatorio(HashCuckooSet::new());
+}
+
+/// Confere que o histograma descreve a tabela inteira.
+fn confere_stats(stats: &ChainingStats) {
+    assert_eq!(stats.histogram.iter().sum::<usize>(), stats.buckets);
+    let elementos: usize = stats.histogram.iter().enumerate().map(|(k, n)| k * n).sum();
+    assert_eq!(elementos, stats.len);
+    assert_eq!(stats.max_chain, stats.histogram.len() - 1);
+    assert!(stats.max_chain == 0 || stats.histogram[stats.max_chain] > 0);
+    assert!((stats.load_factor - stats.len as f64 / stats.buckets as f64).abs() < 1e-12);
+}
+
+#[test]
+fn hashset_chaining_funcoes_de_indice() {
+    for index in [IndexFn::Modulo, IndexFn::Mask, IndexFn::Fibonacci] {
+        let s: HashChainingSet<i32> = HashChainingSet::builder()
+            .index_fn(index)
+            .growth_factor(1.5)
+            .build();
+        testa_hash_aleatorio(s);
+
+        let mut s: HashChainingSet<i32> = HashChainingSet::builder().index_fn(index).build();
+        s.extend(0..5_000);
+        let stats = s.stats();
+        confere_stats(&stats);
+        assert_eq!(stats.len, 5_000);
+        assert!(stats.load_factor <= 0.75);
+        if index != IndexFn::Modulo {
+            assert!(stats.buckets.is_power_of_two());
+        }
+    }
+}
+
+#[test]
+fn hashset_chaining_stats_conjunto_vazio() {
+    let s: HashChainingSet<i32> = HashChainingSet::new();
+    let stats = s.stats();
+    confere_stats(&stats);
+    assert_eq!(stats.histogram, vec![16]);
+    assert_eq!(stats.max_chain, 0);
+    assert_eq!(stats.load_factor, 0.0);
+}
+
+#[test]
+fn hashset_chaining_capacidade_inicial_evita_rehash() {
+    let mut s: HashChainingSet<i32> = HashChainingSet::builder()
+        .initial_capacity(1_000)
+        .build();
+    let buckets = s.stats().buckets;
+    assert!(buckets as f64 * 0.75 >= 1_000.0);
+
+    s.extend(0..1_000);
+    assert_eq!(s.stats().buckets, buckets);
+    s.insert(1_000);
+    assert!(s.stats().buckets > buckets);
+}
+
+#[test]
+fn hashset_chaining_encolhe_apos_remocoes() {
+    let mut s: HashChainingSet<i32> = HashChainingSet::new();
+    s.extend(0..10_000);
+    let cheio = s.stats().buckets;
+    for x in 10..10_000 {
+        assert!(s.remove(&x));
+    }
+    let stats = s.stats();
+    confere_stats(&stats);
+    assert_eq!(stats.len, 10);
+    assert!(stats.buckets < cheio);
+    assert!(stats.load_factor >= 0.25);
+    for x in 0..10 {
+        assert!(s.contains(&x));
+    }
+    // nunca abaixo do tamanho inicial
+    for x in 0..10 {
+        assert!(s.remove(&x));
+    }
+    assert_eq!(s.stats().buckets, 16);
+
+    // `min_load_factor(0.0)` mantém o comportamento antigo: não encolhe
+    let mut s: HashChainingSet<i32> = HashChainingSet::builder().min_load_factor(0.0).build();
+    s.extend(0..10_000);
+    let cheio = s.stats().buckets;
+    for x in 0..10_000 {
+        s.remove(&x);
+    }
+    assert_eq!(s.stats().buckets, cheio);
+}
+
+#[test]
+fn hashset_chaining_hash_constante_uma_cadeia_so() {
+    let mut s: HashChainingSet<i32, HashConstante> = HashChainingSet::builder()
+        .hasher(HashConstante)
+        .build();
+    s.extend(0..100);
+    let stats = s.stats();
+    confere_stats(&stats);
+    assert_eq!(stats.max_chain, 100);
+    assert_eq!(stats.histogram[0], stats.buckets - 1);
+}
+
+#[test]
+fn hashset_chaining_rehash_incremental_aleatorio() {
+    for buckets_per_op in [1, 4] {
+        for index in [IndexFn::Modulo, IndexFn::Mask] {
+            let s: HashChainingSet<i32> = HashChainingSet::builder()
+                .rehash(Rehash::Incremental { buckets_per_op })
+                .index_fn(index)
+                .build();
+            testa_hash_aleatorio(s);
+        }
+    }
+}
+
+#[test]
+fn hashset_chaining_rehash_incremental_migra_aos_poucos() {
+    let mut s: HashChainingSet<i32> = HashChainingSet::builder()
+        .rehash(Rehash::Incremental { buckets_per_op: 8 })
+        .build();
+
+    // 16 buckets com carga máxima 0.75: o 13º elemento dispara o crescimento
+    s.extend(0..12);
+    assert_eq!(s.stats().pending_migration, 0);
+    s.insert(12);
+    let stats = s.stats();
+    confere_stats(&stats);
+    assert_eq!(stats.pending_migration, 16);
+    assert_eq!(stats.buckets, 32 + 16);
+
+    let confere_tudo_visivel = |s: &HashChainingSet<i32>, ate: i32| {
+        for y in 0..=ate {
+            assert!(s.contains(&y));
+        }
+        assert_eq!(s.iter().count(), s.len());
+        confere_stats(&s.stats());
+    };
+
+    // primeiro, cada operação cria 8 dos 32 buckets da tabela nova;
+    // enquanto isso a antiga continua inteira e recebe as inserções
+    for x in 13..17 {
+        s.insert(x);
+        assert_eq!(s.stats().pending_migration, 16);
+        confere_tudo_visivel(&s, x);
+    }
+
+    // depois, cada operação migra 8 buckets da antiga
+    s.insert(17);
+    assert_eq!(s.stats().pending_migration, 8);
+    confere_tudo_visivel(&s, 17);
+    assert!(s.remove(&0));
+    assert!(!s.contains(&0));
+    assert_eq!(s.stats().pending_migration, 0);
+
+    s.extend(100..104);
+    let stats = s.stats();
+    confere_stats(&stats);
+    assert_eq!(stats.pending_migration, 0);
+    assert_eq!(stats.buckets, 32);
+    let mut todos: Vec<i32> = s.into_iter().collect();
+    todos.sort();
+    assert_eq!(todos, (1..18).chain(100..104).collect::<Vec<_>>());
+}
+
+#[test]
+fn hashset_chaining_rehash_incremental_termina_antes_da_carga_maxima() {
+    // crescendo pouco de cada vez, 1 bucket por operação não daria conta:
+    // a migração precisa acelerar para acabar antes de a tabela nova encher
+    for growth_factor in [1.1, 1.5, 2.0] {
+        let mut s: HashChainingSet<i32> = HashChainingSet::builder()
+            .growth_factor(growth_factor)
+            .min_load_factor(0.0)
+            .rehash(Rehash::Incremental { buckets_per_op: 1 })
+            .build();
+
+        for x in 0..3000 {
+            s.insert(x);
+            let stats = s.stats();
+            let tabela_nova = stats.buckets - stats.pending_migration;
+            assert!(
+                stats.len as f64 <= 0.75 * tabela_nova as f64,
+                "fator {growth_factor}: {} elementos em {tabela_nova} buckets",
+                stats.len
+            );
+        }
+        confere_stats(&s.stats());
+        assert_eq!(s.len(), 3000);
+    }
+}
+
+#[test]
+#[should_panic(expected = "buckets_per_op precisa ser positivo")]
+fn hashset_chaining_builder_rejeita_migracao_vazia() {
+    let _: HashChainingSet<i32> = HashChainingSet::builder()
+        .rehash(Rehash::Incremental { buckets_per_op: 0 })
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "min_load_factor * growth_factor")]
+fn hashset_chaining_builder_rejeita_limites_incoerentes() {
+    let _: HashChainingSet<i32> = HashChainingSet::builder()
+        .max_load_factor(0.5)
+        .min_load_factor(0.3)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "growth_factor precisa ser maior que 1")]
+fn hashset_chaining_builder_rejeita_crescimento_menor_que_um() {
+    let _: HashChainingSet<i32> = HashChainingSet::builder().growth_factor(1.0).build();
+}
+
+#[test]
+fn hashset_robin_hood_carga_alta_sem_crescer() {
+    // 0.9 de carga: 57 elementos cabem em 64 posições
+    let mut s = HashRobinHoodSet::new();
+    for i in 0..57 {
+        s.insert(i);
+    }
+    assert_eq!(s.capacity(), 64);
+    assert!((0..57).all(|i| s.contains(&i)));
+    assert!(!s.contains(&57));
+}
+
+/// Hash constante: toda chave cai nas mesmas posições.
+#[derive(Default)]
+struct HashConstante;
+
+impl std::hash::BuildHasher for HashConstante {
+    type Hasher = HasherConstante;
+
+    fn build_hasher(&self) -> HasherConstante {
+        HasherConstante
+    }
+}
+
+struct HasherConstante;
+
+impl std::hash::Hasher for HasherConstante {
+    fn finish(&self) -> u64 {
+        42
+    }
+
+    fn write(&mut self, _: &[u8]) {}
+}
+
+#[test]
+fn hashset_robin_hood_hash_constante_ainda_funciona() {
+    let mut s: HashRobinHoodSet<i32, HashConstante> = HashRobinHoodSet::with_hasher(HashConstante);
+    for i in 0..100 {
+        assert!(s.insert(i));
+    }
+    for i in (0..100).step_by(2) {
+        assert!(s.remove(&i));
+    }
+    assert_eq!(s.len(), 50);
+    assert!((0..100).all(|i| s.contains(&i) == (i % 2 == 1)));
+}
+
+#[test]
+fn hashset_cuckoo_hash_constante_usa_o_estoque() {
+    // só existem duas posições possíveis para qualquer chave: o resto
+    // precisa ficar no estoque
+    let mut s: HashCuckooSet<i32, HashConstante> = HashCuckooSet::with_hasher(HashConstante);
+    for i in 0..100 {
+        assert!(s.insert(i));
+    }
+    assert!(!s.insert(7));
+    assert_eq!(s.len(), 100);
+    assert!((0..100).all(|i| s.contains(&i)));
+    assert!(!s.contains(&100));
+
+    let mut todos: Vec<i32> = s.iter().copied().collect();
+    todos.sort();
+    assert_eq!(todos, (0..100).collect::<Vec<_>>());
+
+    for i in (0..100).step_by(2) {
+        assert!(s.remove(&i));
+    }
+    assert!(!s.remove(&0));
+    assert_eq!(s.len(), 50);
+    assert!((0..100).all(|i| s.contains(&i) == (i % 2 == 1)));
+    assert_eq!(s.into_iter().count(), 50);
+}
+
+// =======================
+// Iteradores
+// =======================
+
+#[test]
+fn listset_iteradores() {
+    testa_set_iteradores::<ListSet<i32>>();
+}
+
+#[test]
+fn arvores_iteram_em_ordem() {
+    let ordem = vec![1, 3, 5, 7, 8, 9];
+    assert_eq!(testa_set_iteradores::<BstSet<i32>>(), ordem);
+    assert_eq!(testa_set_iteradores::<BstAvlSet<i32>>(), ordem);
+    assert_eq!(testa_set_iteradores::<BstRBSet<i32>>(), ordem);
+    assert_eq!(testa_set_iteradores::<SkipListSet<i32>>(), ordem);
+    assert_eq!(testa_set_iteradores::<BTreeSet<i32, 2>>(), ordem);
+    assert_eq!(testa_set_iteradores::<TreapSet<i32>>(), ordem);
+    assert_eq!(testa_set_iteradores::<SplaySet<i32>>(), ordem);
+    assert_eq!(testa_set_iteradores::<PersistentAvlSet<i32>>(), ordem);
+}
+
+#[test]
+fn hashsets_iteradores() {
+    testa_set_iteradores::<HashChainingSet<i32>>();
+    testa_set_iteradores::<HashProbingSet<i32, RandomState, LinearProbing>>();
+    testa_set_iteradores::<HashProbingSet<i32, RandomState, QuadraticProbing>>();
+    testa_set_iteradores::<HashProbingSet<i32, RandomState, DoubleHashing>>();
+    testa_set_iteradores::<HashRobinHoodSet<i32>>();
+    testa_set_iteradores::<HashCuckooSet<i32>>();
+}
+
+#[test]
+fn hashset_probing_iter_pula_lapides() {
+    let mut s: HashProbingSet<i32> = (0..100).collect();
+    for i in (0..100).filter(|i| i % 3 != 0) {
+        s.remove(&i);
+    }
+
+    let mut restantes: Vec<i32> = s.iter().copied().collect();
+    restantes.sort();
+    assert_eq!(restantes, (0..100).step_by(3).collect::<Vec<_>>());
+    assert_eq!(s.iter().size_hint(), (34, Some(34)));
+}
+
+// =======================
+// Operações de conjunto
+// =======================
+
+fn ordenado<'a>(it: impl Iterator<Item = &'a i32>) -> Vec<i32> {
+    let mut v: Vec<i32> = it.copied().collect();
+    v.sort();
+    v
+}
+
+/// A = {0..10}, B = {5..15}, C = {2, 4}, D = {20, 30}
+fn testa_algebra<A, B>()
+where
+    A: Set<i32> + FromIterator<i32>,
+    B: Set<i32> + FromIterator<i32>,
+{
+    let a: A = (0..10).collect();
+    let b: B = (5..15).collect();
+    let c: B = [2, 4].into_iter().collect();
+    let d: B = [20, 30].into_iter().collect();
+
+    assert_eq!(ordenado(a.union(&b)), (0..15).collect::<Vec<_>>());
+    assert_eq!(ordenado(a.intersection(&b)), (5..10).collect::<Vec<_>>());
+    assert_eq!(ordenado(a.difference(&b)), (0..5).collect::<Vec<_>>());
+    assert_eq!(
+        ordenado(a.symmetric_difference(&b)),
+        (0..5).chain(10..15).collect::<Vec<_>>()
+    );
+
+    assert!(c.is_subset(&a));
+    assert!(!a.is_subset(&c));
+    assert!(a.is_superset(&c));
+    assert!(!a.is_subset(&b));
+    assert!(a.is_subset(&a));
+
+    assert!(a.is_disjoint(&d));
+    assert!(!a.is_disjoint(&b));
+    assert_eq!(a.intersection(&d).count(), 0);
+}
+
+#[test]
+fn algebra_mesmo_backend() {
+    testa_algebra::<ListSet<i32>, ListSet<i32>>();
+    testa_algebra::<BstSet<i32>, BstSet<i32>>();
+    testa_algebra::<BstAvlSet<i32>, BstAvlSet<i32>>();
+    testa_algebra::<BstRBSet<i32>, BstRBSet<i32>>();
+    testa_algebra::<SkipListSet<i32>, SkipListSet<i32>>();
+    testa_algebra::<BTreeSet<i32>, BTreeSet<i32>>();
+    testa_algebra::<TreapSet<i32>, TreapSet<i32>>();
+    testa_algebra::<SplaySet<i32>, SplaySet<i32>>();
+    testa_algebra::<PersistentAvlSet<i32>, PersistentAvlSet<i32>>();
+    testa_algebra::<HashChainingSet<i32>, HashChainingSet<i32>>();
+    testa_algebra::<HashProbingSet<i32>, HashProbingSet<i32>>();
+    testa_algebra::<HashRobinHoodSet<i32>, HashRobinHoodSet<i32>>();
+    testa_algebra::<HashCuckooSet<i32>, HashCuckooSet<i32>>();
+}
+
+#[test]
+fn algebra_backends_diferentes() {
+    testa_algebra::<BstAvlSet<i32>, HashChainingSet<i32>>();
+    testa_algebra::<HashChainingSet<i32>, BstAvlSet<i32>>();
+    testa_algebra::<BstRBSet<i32>, BstSet<i32>>();
+    testa_algebra::<ListSet<i32>, HashProbingSet<i32>>();
+    testa_algebra::<HashProbingSet<i32>, BstRBSet<i32>>();
+    testa_algebra::<SkipListSet<i32>, BstAvlSet<i32>>();
+    testa_algebra::<BTreeSet<i32, 2>, HashChainingSet<i32>>();
+    testa_algebra::<TreapSet<i32>, BstRBSet<i32>>();
+    testa_algebra::<SplaySet<i32>, ListSet<i32>>();
+    testa_algebra::<PersistentAvlSet<i32>, BstAvlSet<i32>>();
+    testa_algebra::<HashRobinHoodSet<i32>, HashCuckooSet<i32>>();
+    testa_algebra::<BstAvlSet<i32>, HashCuckooSet<i32>>();
+}
+
+#[test]
+fn algebra_arvores_devolvem_em_ordem() {
+    // com dois lados ordenados a intercalação já sai em ordem, sem sort
+    let a: BstAvlSet<i32> = [9, 1, 7, 3, 5].into_iter().collect();
+    let b: BstRBSet<i32> = [8, 2, 6, 4, 5].into_iter().collect();
+
+    let uniao: Vec<i32> = a.union(&b).copied().collect();
+    assert_eq!(uniao, (1..10).collect::<Vec<_>>());
+
+    let simetrica: Vec<i32> = a.symmetric_difference(&b).copied().collect();
+    assert_eq!(simetrica, vec![1, 2, 3, 4, 6, 7, 8, 9]);
+}
+
+// =======================
+// Invariantes das árvores
+// =======================
+
+/// Inserções e remoções aleatórias, conferindo a estrutura depois de
+/// cada operação. O `BTreeSet` do `std` serve de referência para o conteúdo.
+fn testa_invariantes<S, F>(check: F)
+where
+    S: Set<i32> + Default,
+    F: Fn(&S) -> Result<(), InvariantError>,
+{
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut s = S::default();
+        let mut referencia = StdBTreeSet::new();
+
+        for passo in 0..500 {
+            let x = rng.gen_range(0..200);
+            if rng.gen_bool(0.6) {
+                assert_eq!(s.insert(x), referencia.insert(x));
+            } else {
+                assert_eq!(s.remove(&x), referencia.remove(&x));
+            }
+
+            if let Err(e) = check(&s) {
+                panic!("seed {seed}, passo {passo}: {e}");
+            }
+            assert_eq!(s.len(), referencia.len());
+        }
+
+        // esvazia tudo, inclusive a raiz no final
+        for x in referencia {
+            assert!(s.remove(&x));
+            check(&s).unwrap();
+        }
+        assert!(s.is_empty());
+    }
+
+    // sequências crescente e decrescente: o pior caso das rotações
+    let mut s = S::default();
+    for x in (0..300).chain((300..600).rev()) {
+        s.insert(x);
+        check(&s).unwrap();
+    }
+    for x in (0..600).step_by(3) {
+        s.remove(&x);
+        check(&s).unwrap();
+    }
+}
+
+#[test]
+fn bstset_invariantes() {
+    testa_invariantes(BstSet::<i32>::check_invariants);
+}
+
+#[test]
+fn bstavlset_invariantes() {
+    testa_invariantes(BstAvlSet::<i32>::check_invariants);
+}
+
+#[test]
+fn bstrbset_invariantes() {
+    testa_invariantes(BstRBSet::<i32>::check_invariants);
+}
+
+#[test]
+fn persistentavlset_invariantes() {
+    testa_invariantes(PersistentAvlSet::<i32>::check_invariants);
+}
+
+#[test]
+fn btreeset_invariantes() {
+    testa_invariantes(BTreeSet::<i32, 2>::check_invariants);
+    testa_invariantes(BTreeSet::<i32, 3>::check_invariants);
+    testa_invariantes(BTreeSet::<i32>::check_invariants);
+}
+
+#[test]
+fn treapset_invariantes() {
+    testa_invariantes(TreapSet::<i32>::check_invariants);
+}
+
+#[test]
+fn splayset_invariantes() {
+    testa_invariantes(SplaySet::<i32>::check_invariants);
+}
+
+#[test]
+fn invariantes_conjunto_vazio() {
+    assert_eq!(BstSet::<i32>::new().check_invariants(), Ok(()));
+    assert_eq!(BstAvlSet::<i32>::new().check_invariants(), Ok(()));
+    assert_eq!(BstRBSet::<i32>::new().check_invariants(), Ok(()));
+    assert_eq!(BTreeSet::<i32>::new().check_invariants(), Ok(()));
+    assert_eq!(TreapSet::<i32>::new().check_invariants(), Ok(()));
+    assert_eq!(SplaySet::<i32>::new().check_invariants(), Ok(()));
+}
+
+// =======================
+// Destruição sem recursão
+// =======================
+
+/// Roda `f` numa thread com pilha pequena: qualquer recursão por nó estoura.
+fn com_pilha_pequena(f: impl FnOnce() + Send + 'static) {
+    std::thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(f)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn listset_drop_nao_estoura_pilha() {
+    // ListSet é O(n) por inserção: não dá para chegar a milhões,
+    // então usamos a pilha pequena para detectar a recursão
+    com_pilha_pequena(|| {
+        let s: ListSet<i32> = (0..10_000).collect();
+        assert_eq!(s.len(), 10_000);
+    });
+}
+
+#[test]
+fn arvores_balanceadas_com_milhoes_de_elementos() {
+    let n = 2_000_000;
+
+    let mut avl = BstAvlSet::new();
+    let mut rb = BstRBSet::new();
+    for x in 0..n {
+        avl.insert(x);
+        rb.insert(x);
+    }
+    assert_eq!(avl.len(), n as usize);
+    assert_eq!(rb.len(), n as usize);
+    drop(avl);
+    drop(rb);
+}
+
+#[test]
+fn into_iter_largado_no_meio_nao_estoura_pilha() {
+    com_pilha_pequena(|| {
+        // caminho só à direita: a pilha do iterador guarda a raiz com
+        // todo o resto da árvore pendurado
+        let s: BstSet<i32> = (0..10_000).collect();
+        let mut it = s.into_iter();
+        assert_eq!(it.next(), Some(0));
+        assert_eq!(it.next(), Some(1));
+        drop(it);
+
+        let s: BstAvlSet<i32> = (0..10_000).collect();
+        let mut it = s.into_iter();
+        assert_eq!(it.next(), Some(0));
+    });
+}
+
+// =======================
+// Consultas por empréstimo
+// =======================
+
+macro_rules! testa_borrow {
+    ($set:expr) => {{
+        let mut s = $set;
+        for palavra in ["pera", "uva", "maçã"] {
+            s.insert(palavra.to_string());
+        }
+
+        // consulta com `&str`, sem montar uma `String`
+        assert!(s.contains("uva"));
+        assert!(!s.contains("kiwi"));
+        assert_eq!(s.get("pera").map(String::as_str), Some("pera"));
+        assert_eq!(s.get("kiwi"), None);
+
+        assert!(s.remove("uva"));
+        assert!(!s.remove("uva"));
+        assert_eq!(s.take("maçã"), Some("maçã".to_string()));
+        assert_eq!(s.take("maçã"), None);
+        assert_eq!(s.len(), 1);
+
+        // o método do trait continua aceitando `&T`
+        assert!(Set::contains(&s, &"pera".to_string()));
+    }};
+}
+
+#[test]
+fn consultas_com_str_em_conjuntos_de_string() {
+    testa_borrow!(ListSet::new());
+    testa_borrow!(BstSet::new());
+    testa_borrow!(BstAvlSet::new());
+    testa_borrow!(BstRBSet::new());
+    testa_borrow!(SkipListSet::new());
+    testa_borrow!(BTreeSet::<String>::new());
+    testa_borrow!(TreapSet::<String>::new());
+    testa_borrow!(SplaySet::<String>::new());
+    testa_borrow!(PersistentAvlSet::<String>::new());
+    testa_borrow!(HashChainingSet::new());
+    testa_borrow!(HashProbingSet::<String>::new());
+    testa_borrow!(HashRobinHoodSet::<String>::new());
+    testa_borrow!(HashCuckooSet::<String>::new());
+}
+
+/// Código genérico também consulta com `&str`, pelo trait `Lookup`.
+fn testa_lookup_generico<S>(mut s: S)
+where
+    S: Set<String> + Lookup<String, str>,
+{
+    s.insert("pera".to_string());
+    s.insert("uva".to_string());
+
+    assert_eq!(s.get("uva").map(String::as_str), Some("uva"));
+    assert!(s.get("kiwi").is_none());
+    assert_eq!(s.take("pera"), Some("pera".to_string()));
+    assert_eq!(s.len(), 1);
+}
+
+#[test]
+fn lookup_em_codigo_generico() {
+    testa_lookup_generico(ListSet::new());
+    testa_lookup_generico(BstSet::new());
+    testa_lookup_generico(BstAvlSet::new());
+    testa_lookup_generico(BstRBSet::new());
+    testa_lookup_generico(SkipListSet::new());
+    testa_lookup_generico(BTreeSet::<String>::new());
+    testa_lookup_generico(TreapSet::<String>::new());
+    testa_lookup_generico(SplaySet::<String>::new());
+    testa_lookup_generico(PersistentAvlSet::<String>::new());
+    testa_lookup_generico(HashChainingSet::new());
+    testa_lookup_generico(HashProbingSet::<String>::new());
+    testa_lookup_generico(HashRobinHoodSet::<String>::new());
+    testa_lookup_generico(HashCuckooSet::<String>::new());
+}
+
+// =======================
+// Exportação DOT
+// =======================
+
+fn conta(dot: &str, trecho: &str) -> usize {
+    dot.matches(trecho).count()
+}
+
+#[test]
+fn to_dot_arvore_vazia() {
+    let dot = BstSet::<i32>::new().to_dot();
+    assert!(dot.starts_with("digraph BstSet {"));
+    assert!(dot.trim_end().ends_with('}'));
+    assert_eq!(conta(&dot, "label="), 0);
+}
+
+#[test]
+fn to_dot_nos_e_filhos_vazios() {
+    let s: BstSet<i32> = [5, 3, 8, 1].into_iter().collect();
+    let dot = s.to_dot();
+    // um rótulo por nó, n + 1 filhos vazios e duas arestas por nó
+    assert_eq!(conta(&dot, "label="), 4);
+    assert_eq!(conta(&dot, "[shape=point]"), 5);
+    assert_eq!(conta(&dot, " -> "), 2 * 4);
+    assert!(dot.contains("n0 [label=\"5\"]"));
+}
+
+#[test]
+fn to_dot_avl_mostra_altura_e_balanceamento() {
+    let s: BstAvlSet<i32> = (1..=3).collect();
+    let dot = s.to_dot();
+    assert!(dot.contains("n0 [label=\"2\\nh=2 bf=0\"]"));
+    assert_eq!(conta(&dot, "h=1 bf=0"), 2);
+}
+
+#[test]
+fn to_dot_rubro_negra_pinta_links_vermelhos() {
+    let mut s = BstRBSet::new();
+    for x in [10, 20, 30, 15] {
+        s.insert(x);
+    }
+    let dot = s.to_dot();
+    // 20 no topo; 15 preto com 10 vermelho à esquerda
+    assert_eq!(conta(&dot, "color=red"), 1);
+    assert!(dot.contains("n1 -> n3 [color=red, penwidth=2]"));
+    assert!(dot.contains("n3 [label=\"10\"]"));
+}
+
+#[test]
+fn to_dot_escapa_aspas() {
+    let s: BstSet<String> = ["a\"b".to_string()].into_iter().collect();
+    assert!(s.to_dot().contains("label=\"a\\\"b\""));
+}
+
+#[test]
+fn to_dot_arvore_degenerada_nao_estoura_pilha() {
+    com_pilha_pequena(|| {
+        let mut s = BstSet::new();
+        for x in 0..10_000 {
+            s.insert(x);
+        }
+        let dot = s.to_dot();
+        assert_eq!(conta(&dot, "label="), 10_000);
+    });
+}
+
+// =======================
+// Construção a partir de entrada ordenada
+// =======================
+
+#[test]
+fn from_sorted_iter_todos_os_tamanhos_pequenos() {
+    for n in 0..300 {
+        let avl = BstAvlSet::from_sorted_iter(0..n).unwrap();
+        avl.check_invariants().unwrap();
+        assert_eq!(avl.len(), n as usize);
+        assert!(avl.iter().copied().eq(0..n));
+
+        let rb = BstRBSet::from_sorted_iter(0..n).unwrap();
+        rb.check_invariants().unwrap();
+        assert_eq!(rb.len(), n as usize);
+        assert!(rb.iter().copied().eq(0..n));
+    }
+}
+
+#[test]
+fn from_sorted_iter_avl_tem_altura_minima() {
+    // 1000 elementos: ⌈log2(1001)⌉ = 10 níveis
+    let avl = BstAvlSet::from_sorted_iter(0..1000).unwrap();
+    assert!(avl.to_dot().contains("n0 [label=\"500\\nh=10 "));
+
+    // 1023 = 2^10 - 1: árvore perfeita, todos os nós com bf = 0
+    let cheia = BstAvlSet::from_sorted_iter(0..1023).unwrap();
+    let dot = cheia.to_dot();
+    assert_eq!(dot.matches("bf=0").count(), 1023);
+}
+
+#[test]
+fn from_sorted_iter_recusa_entrada_fora_de_ordem() {
+    assert_eq!(
+        BstAvlSet::from_sorted_iter([1, 3, 2, 4]).err(),
+        Some(UnsortedError { position: 2 })
+    );
+    assert_eq!(
+        BstRBSet::from_sorted_iter([1, 3, 2, 4]).err(),
+        Some(UnsortedError { position: 2 })
+    );
+
+    // repetidos também não são estritamente crescentes
+    assert_eq!(
+        BstRBSet::from_sorted_iter([5, 5]).err(),
+        Some(UnsortedError { position: 1 })
+    );
+    assert!(BstAvlSet::from_sorted_iter(["a", "b", "b"]).is_err());
+}
+
+#[test]
+fn from_sorted_iter_arvore_segue_valida_depois_de_alteracoes() {
+    let mut avl = BstAvlSet::from_sorted_iter((0..2000).map(|x| x * 2)).unwrap();
+    let mut rb = BstRBSet::from_sorted_iter((0..2000).map(|x| x * 2)).unwrap();
+    let mut rng = StdRng::seed_from_u64(23);
+
+    for _ in 0..4000 {
+        let x = rng.gen_range(0..4000);
+        if rng.gen_bool(0.5) {
+            assert_eq!(avl.insert(x), rb.insert(x));
+        } else {
+            assert_eq!(Set::remove(&mut avl, &x), Set::remove(&mut rb, &x));
+        }
+    }
+    avl.check_invariants().unwrap();
+    rb.check_invariants().unwrap();
+    assert!(avl.iter().eq(rb.iter()));
+}
+
+#[test]
+fn from_sorted_iter_muitos_elementos() {
+    let n = 1_000_000;
+    let avl = BstAvlSet::from_sorted_iter(0..n).unwrap();
+    avl.check_invariants().unwrap();
+    let rb = BstRBSet::from_sorted_iter(0..n).unwrap();
+    rb.check_invariants().unwrap();
+    assert!(rb.contains(&(n - 1)) && !rb.contains(&n));
+}
+
+// =======================
+// Conjunto concorrente (StripedHashSet)
+// =======================
+
+#[test]
+fn stripedhashset_basico() {
+    let s = StripedHashSet::with_stripes(4);
+    assert!(s.is_empty());
+    assert!(s.insert(10));
+    assert!(!s.insert(10));
+    assert!(s.insert(20));
+    assert_eq!(s.len(), 2);
+    assert!(s.contains(&10));
+    assert!(!s.contains(&30));
+    assert_eq!(s.take(&10), Some(10));
+    assert!(!s.remove(&10));
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.stripes(), 4);
+}
+
+#[test]
+fn stripedhashset_consultas_com_str() {
+    let s: StripedHashSet<String> = ["a", "b"].into_iter().map(String::from).collect();
+    assert!(s.contains("a"));
+    assert!(s.remove("b"));
+    assert!(!s.contains("b"));
+}
+
+#[test]
+fn stripedhashset_insercoes_concorrentes_sobrepostas() {
+    // 8 threads inserem as mesmas chaves: cada chave dá `true` uma vez só
+    let s = StripedHashSet::new();
+    let n = 20_000;
+    let ganhos: usize = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let s = &s;
+                scope.spawn(move || {
+                    // ordens diferentes por thread para aumentar a disputa
+                    (0..n)
+                        .map(|i| if t % 2 == 0 { i } else { n - 1 - i })
+                        .filter(|&k| s.insert(k))
+                        .count()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    });
+
+    assert_eq!(ganhos, n as usize);
+    assert_eq!(s.len(), n as usize);
+    assert!((0..n).all(|k| s.contains(&k)));
+}
+
+#[test]
+fn stripedhashset_remocoes_concorrentes() {
+    let s: StripedHashSet<i32> = (0..10_000).collect();
+    let removidos: usize = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let s = &s;
+                scope.spawn(move || (0..10_000).filter(|k| s.remove(k)).count())
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    });
+
+    assert_eq!(removidos, 10_000);
+    assert!(s.is_empty());
+}
+
+#[test]
+fn stripedhashset_cresce_sob_carga() {
+    // poucas faixas: cada uma re-hasheia várias vezes enquanto as outras
+    // threads continuam lendo e escrevendo
+    let s = StripedHashSet::with_stripes(2);
+    std::thread::scope(|scope| {
+        for t in 0..4 {
+            let s = &s;
+            scope.spawn(move || {
+                for k in (t..40_000).step_by(4) {
+                    assert!(s.insert(k));
+                    assert!(s.contains(&k));
+                }
+            });
+        }
+    });
+
+    assert_eq!(s.len(), 40_000);
+    let mut todos: Vec<_> = s.into_iter().collect();
+    todos.sort_unstable();
+    assert_eq!(todos, (0..40_000).collect::<Vec<_>>());
+}
+
+#[test]
+fn stripedhashset_for_each_visita_todos() {
+    let s: StripedHashSet<i32> = (0..100).collect();
+    let mut soma = 0;
+    s.for_each(|&x| soma += x);
+    assert_eq!(soma, (0..100).sum());
+}
+
+#[test]
+fn concurrentset_mutex_como_referencia() {
+    fn conta_novos<C: ConcurrentSet<i32>>(c: &C) -> usize {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(move || (0..1_000).filter(|&k| c.insert(k)).count()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum()
+        })
+    }
+
+    let m = std::sync::Mutex::new(HashChainingSet::new());
+    assert_eq!(conta_novos(&m), 1_000);
+    assert_eq!(ConcurrentSet::len(&m), 1_000);
+
+    let s = StripedHashSet::new();
+    assert_eq!(conta_novos(&s), 1_000);
+    assert!(ConcurrentSet::contains(&s, &999));
+}
+
+// =======================
+// Comparadores
+// =======================
+
+fn testa_ordem_reversa<S>(mut s: S)
+where
+    S: OrderedSet<i32>,
+{
+    for x in [5, 1, 9, 3, 7] {
+        assert!(s.insert(x));
+    }
+    assert!(!s.insert(3));
+
+    assert!(s.iter().copied().eq([9, 7, 5, 3, 1]));
+    assert_eq!(s.min(), Some(&9));
+    assert_eq!(s.max(), Some(&1));
+
+    // "menor ou igual" na ordem do comparador
+    assert_eq!(s.floor(&6), Some(&7));
+    assert_eq!(s.ceiling(&6), Some(&5));
+    assert_eq!(s.successor(&5), Some(&3));
+    assert_eq!(s.predecessor(&5), Some(&7));
+    // os limites também seguem o comparador: 8 vem antes de 3
+    let (de, ate) = (8, 3);
+    assert!(s.range(de..=ate).copied().eq([7, 5, 3]));
+
+    assert_eq!(s.pop_min(), Some(9));
+    assert_eq!(s.pop_max(), Some(1));
+    assert!(s.remove(&5));
+    assert!(!s.contains(&5));
+    assert!(s.iter().copied().eq([7, 3]));
+}
+
+#[test]
+fn comparador_reverso_nas_tres_arvores() {
+    testa_ordem_reversa(BstSet::with_comparator(ReverseOrder(NaturalOrder)));
+    testa_ordem_reversa(BstAvlSet::with_comparator(ReverseOrder(NaturalOrder)));
+    testa_ordem_reversa(BstRBSet::with_comparator(ReverseOrder(NaturalOrder)));
+}
+
+#[test]
+fn comparador_por_chave_sem_diferenciar_maiusculas() {
+    let mut s = BstRBSet::with_comparator(ByKey(|s: &String| s.to_lowercase()));
+    assert!(s.insert("Maria".to_string()));
+    assert!(s.insert("ana".to_string()));
+    assert!(!s.insert("ANA".to_string()));
+
+    // a busca e a remoção também usam o comparador
+    assert!(s.contains(&"MARIA".to_string()));
+    assert!(s.remove(&"maria".to_string()));
+    assert_eq!(s.len(), 1);
+    // o elemento guardado é o primeiro inserido
+    assert_eq!(s.iter().next().map(String::as_str), Some("ana"));
+    s.check_invariants().unwrap();
+}
+
+#[test]
+fn comparador_closure_em_tuplas() {
+    // ordena pelo segundo campo; o primeiro não participa da igualdade
+    let mut s = BstAvlSet::with_comparator(|a: &(u8, i32), b: &(u8, i32)| a.1.cmp(&b.1));
+    for (i, x) in [30, 10, 20].into_iter().enumerate() {
+        assert!(s.insert((i as u8, x)));
+    }
+    assert!(!s.insert((9, 20)));
+    assert!(s.contains(&(0, 10)));
+    assert!(s.iter().map(|p| p.1).eq([10, 20, 30]));
+    assert_eq!(s.iter().find(|p| p.1 == 20), Some(&(2, 20)));
+    s.check_invariants().unwrap();
+}
+
+#[test]
+fn comparador_aleatorio_contra_btreeset_std() {
+    use std::cmp::Reverse;
+
+    let mut rng = StdRng::seed_from_u64(24);
+    let mut bst = BstSet::with_comparator(ReverseOrder(NaturalOrder));
+    let mut avl = BstAvlSet::with_comparator(ReverseOrder(NaturalOrder));
+    let mut rb = BstRBSet::with_comparator(ReverseOrder(NaturalOrder));
+    let mut reference = StdBTreeSet::new();
+
+    for _ in 0..5_000 {
+        let x: i32 = rng.gen_range(0..500);
+        if rng.gen_bool(0.6) {
+            let esperado = reference.insert(Reverse(x));
+            assert_eq!(bst.insert(x), esperado);
+            assert_eq!(avl.insert(x), esperado);
+            assert_eq!(rb.insert(x), esperado);
+        } else {
+            let esperado = reference.remove(&Reverse(x));
+            assert_eq!(bst.remove(&x), esperado);
+            assert_eq!(avl.remove(&x), esperado);
+            assert_eq!(rb.remove(&x), esperado);
+        }
+    }
+
+    let esperado: Vec<i32> = reference.iter().map(|r| r.0).collect();
+    assert_eq!(bst.iter().copied().collect::<Vec<_>>(), esperado);
+    assert_eq!(avl.iter().copied().collect::<Vec<_>>(), esperado);
+    assert_eq!(rb.iter().copied().collect::<Vec<_>>(), esperado);
+    bst.check_invariants().unwrap();
+    avl.check_invariants().unwrap();
+    rb.check_invariants().unwrap();
+}
+
+#[test]
+fn comparador_invariantes_usam_a_ordem_do_comparador() {
+    // crescente na ordem natural = fora de ordem na reversa
+    assert_eq!(
+        BstAvlSet::from_sorted_iter_by(0..10, ReverseOrder(NaturalOrder)).err(),
+        Some(UnsortedError { position: 1 })
+    );
+
+    let rb = BstRBSet::from_sorted_iter_by((0..100).rev(), ReverseOrder(NaturalOrder)).unwrap();
+    rb.check_invariants().unwrap();
+    assert!(rb.iter().copied().eq((0..100).rev()));
+    assert_eq!(rb.comparator().compare(&1, &2), std::cmp::Ordering::Greater);
+}
+
+#[test]
+fn comparador_algebra_com_ordens_diferentes() {
+    let mut natural = BstAvlSet::new();
+    let mut reversa = BstRBSet::with_comparator(ReverseOrder(NaturalOrder));
+    natural.extend(0..10);
+    reversa.extend(5..15);
+
+    // ordens diferentes não podem ser intercaladas: cai na busca
+    assert!(reversa.sorted_by().is_none());
+    assert_eq!(ordenado(natural.union(&reversa)), (0..15).collect::<Vec<_>>());
+    assert_eq!(ordenado(natural.intersection(&reversa)), (5..10).collect::<Vec<_>>());
+    assert_eq!(ordenado(reversa.difference(&natural)), (10..15).collect::<Vec<_>>());
+    assert!(!natural.is_subset(&reversa));
+}
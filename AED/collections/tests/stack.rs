@@ -102,3 +102,14 @@ fn liststack_deve_funcionar_com_tipo_nao_copy() {
     assert_eq!(s.pop().as_deref(), Some("oi"));
     assert_eq!(s.pop(), None);
 }
+
+#[test]
+fn liststack_com_milhoes_de_elementos_deve_ser_destruida_sem_estourar_a_pilha() {
+    let mut s = ListStack::new();
+    for i in 0..3_000_000 {
+        s.push(i);
+    }
+    assert_eq!(s.len(), 3_000_000);
+    assert_eq!(s.peek(), Some(&2_999_999));
+    drop(s);
+}